@@ -5,16 +5,36 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::BufWriter;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::exercise::{Exercise, InfoToml, find_pack_root, load_exercises, load_info_toml};
-use crate::verify::VerifyResult;
+use crate::exercise::{
+    Exercise, InfoToml, closest_names, load_exercises, load_info_toml,
+};
+use crate::hints::hints_used_count;
+use crate::lock::{self, LockStatus, ProgressLock};
+use crate::theme::ThemeName;
+use crate::verify::{OutputView, StackSummary, VerifyResult};
 
 const PROGRESS_FILENAME: &str = ".zenlings-progress.json";
 
+/// Environment variable overriding where progress is stored
+pub const PROGRESS_ENV: &str = "ZENLINGS_PROGRESS";
+
+/// Failed attempts after which an unsolved exercise can be reset to its solution
+pub const RESET_MIN_ATTEMPTS: u32 = 3;
+
+/// How long a pass stays on screen before auto-advance moves on
+pub const AUTO_ADVANCE_DELAY: Duration = Duration::from_secs(3);
+
+/// Counter to keep temp file names unique within a single process
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 /// Persisted progress data
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct ProgressFile {
@@ -26,6 +46,30 @@ pub struct ProgressFile {
     pub hints_used: HashMap<String, u32>,
     pub started_at: Option<String>,
     pub last_activity: Option<String>,
+    /// Whether the welcome message has been shown in a previous session
+    #[serde(default)]
+    pub welcome_shown: bool,
+    /// Seconds spent with each exercise open, by exercise name
+    #[serde(default)]
+    pub time_spent_secs: HashMap<String, u64>,
+    /// Content hash of each exercise file as of its last passing run
+    #[serde(default)]
+    pub passed_hash: HashMap<String, String>,
+    /// Verification runs of each exercise, up to and including the one that passed
+    #[serde(default)]
+    pub attempts: HashMap<String, u32>,
+    /// Last theme chosen with `--theme`
+    #[serde(default)]
+    pub theme: Option<ThemeName>,
+    /// Exercises whose solution the learner has opened
+    #[serde(default)]
+    pub solutions_viewed: HashSet<String>,
+    /// Seed of the `--shuffle` order, reused when resuming
+    #[serde(default)]
+    pub shuffle_seed: Option<u64>,
+    /// Exercises the learner moved on from without solving, with their reason
+    #[serde(default)]
+    pub skipped: HashMap<String, Option<String>>,
 }
 
 impl ProgressFile {
@@ -37,6 +81,14 @@ impl ProgressFile {
             hints_used: HashMap::new(),
             started_at: Some(Self::now_iso()),
             last_activity: Some(Self::now_iso()),
+            welcome_shown: false,
+            time_spent_secs: HashMap::new(),
+            passed_hash: HashMap::new(),
+            attempts: HashMap::new(),
+            theme: None,
+            solutions_viewed: HashSet::new(),
+            shuffle_seed: None,
+            skipped: HashMap::new(),
         }
     }
 
@@ -49,6 +101,84 @@ impl ProgressFile {
     }
 }
 
+/// Stable hash of file contents (64-bit FNV-1a, hex encoded)
+///
+/// `DefaultHasher` isn't guaranteed stable across Rust releases, and these
+/// hashes are persisted.
+fn content_hash(bytes: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Permutation of `0..len` determined by `seed` (Fisher-Yates over splitmix64)
+///
+/// Hand-rolled rather than a library RNG so a seed gives the same order on
+/// every platform and release.
+fn shuffled_order(len: usize, seed: u64) -> Vec<usize> {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    let mut order: Vec<usize> = (0..len).collect();
+    for i in (1..len).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        order.swap(i, j);
+    }
+    order
+}
+
+/// Contents of an exercise file followed by its extra watched files
+///
+/// `None` if any of them can't be read.
+fn watched_content(exercise: &Exercise) -> Option<Vec<u8>> {
+    let mut content = fs::read(&exercise.path).ok()?;
+    for path in &exercise.watch {
+        content.extend(fs::read(path).ok()?);
+    }
+    Some(content)
+}
+
+/// How many navigation/completion events `AppState::undo` can step back through
+const UNDO_LIMIT: usize = 20;
+
+/// A reversible change to the current exercise or completion state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryEvent {
+    /// Moved away from the exercise at this index
+    Moved { from: usize },
+    /// Marked an exercise completed, replacing this passed-file hash
+    Completed {
+        name: String,
+        previous_hash: Option<String>,
+    },
+}
+
+/// Totals shown on the completion screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionStats {
+    pub solved: usize,
+    pub total: usize,
+    pub time_spent_secs: u64,
+    pub hints_used: u32,
+    /// Mean attempts per solved exercise, if any attempts were recorded
+    pub average_attempts: Option<f64>,
+    /// Verification runs across all exercises, solved or not
+    pub total_attempts: u32,
+    /// No solution in the pack was ever opened
+    pub no_solutions_viewed: bool,
+}
+
+/// How long a notice stays on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(2);
+
 /// Main application state
 pub struct AppState {
     pub pack_root: PathBuf,
@@ -57,47 +187,191 @@ pub struct AppState {
 
     progress_path: PathBuf,
     pub progress: ProgressFile,
+    /// Whether progress is read from and written to `progress_path`
+    persist: bool,
+    /// Held while this session saves to `progress_path`
+    lock: Option<ProgressLock>,
+    /// Pid of another live session saving to the same progress file
+    pub lock_holder: Option<u32>,
 
     pub current_index: usize,
 
+    /// When time started accruing to the current exercise
+    current_since: Instant,
+
     /// Last verification result (if any)
     pub last_verify: Option<VerifyResult>,
 
     /// Whether we're currently running a verification
     pub verifying: bool,
+
+    /// When the running verification started
+    pub verify_started: Option<Instant>,
+
+    /// Spinner animation frame, advanced once per main loop tick while verifying
+    pub spinner_tick: usize,
+
+    /// How many lines the output view is scrolled up from the newest line
+    pub output_scroll: usize,
+
+    /// Show raw ZenML CLI output instead of the structured summary
+    pub show_raw_zenml: bool,
+
+    /// Which of the run's output streams are shown
+    pub output_view: OutputView,
+
+    /// Pack author mode (`--author`): solution files can be verified
+    pub author_mode: bool,
+
+    /// Whether the running/last verification was against the solution file
+    pub solution_check: bool,
+
+    /// Whether file changes trigger verification (toggled at runtime)
+    pub watch_enabled: bool,
+
+    /// Whether a pass moves on to the next incomplete exercise by itself
+    pub auto_advance: bool,
+
+    /// When the pending auto-advance happens (any key cancels it)
+    pub auto_advance_at: Option<Instant>,
+
+    /// Active ZenML stack, once it has been queried
+    pub stack_summary: Option<StackSummary>,
+
+    /// Recent navigation/completion events, newest last (bounded by `UNDO_LIMIT`)
+    history: VecDeque<HistoryEvent>,
+
+    /// Short confirmation shown above the footer, and when it was set
+    notice: Option<(String, Instant)>,
 }
 
 impl AppState {
     /// Load application state from pack root
     pub fn load(pack_root: PathBuf) -> Result<Self> {
+        Self::load_with_progress(pack_root, None, true)
+    }
+
+    /// Load application state, keeping progress in `progress_file` (else
+    /// `$ZENLINGS_PROGRESS`, else the pack root)
+    ///
+    /// Without `persist`, progress starts fresh and lives only in memory.
+    pub fn load_with_progress(pack_root: PathBuf, progress_file: Option<PathBuf>, persist: bool) -> Result<Self> {
         let info_path = pack_root.join("info.toml");
         let info = load_info_toml(&info_path)?;
         let exercises = load_exercises(&pack_root, &info)?;
 
-        let progress_path = pack_root.join(PROGRESS_FILENAME);
-        let progress = Self::load_progress(&progress_path)?;
+        let override_path = progress_file
+            .or_else(|| std::env::var_os(PROGRESS_ENV).filter(|p| !p.is_empty()).map(PathBuf::from));
+        let progress_path = match override_path {
+            Some(path) => {
+                if let Some(parent) = path.parent().filter(|p| persist && !p.as_os_str().is_empty()) {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Failed to create progress directory: {:?}", parent))?;
+                }
+                path
+            }
+            None => pack_root.join(PROGRESS_FILENAME),
+        };
+        let progress = if persist {
+            Self::load_progress(&progress_path)?
+        } else {
+            ProgressFile::new()
+        };
+
+        let mut state = Self::from_parts(pack_root, info, exercises, progress_path, progress);
+        state.persist = persist;
+        if persist {
+            match lock::acquire(&lock::lock_path(&state.progress_path))? {
+                LockStatus::Acquired(lock) => state.lock = Some(lock),
+                LockStatus::HeldBy(pid) => state.lock_holder = Some(pid),
+            }
+        }
+        Ok(state)
+    }
+
+    /// Put the exercises in the order given by `seed` and remember it
+    ///
+    /// Progress is keyed by name, so completion carries over; the current
+    /// exercise stays current.
+    pub fn shuffle(&mut self, seed: u64) {
+        let mut slots: Vec<Option<Exercise>> = self.exercises.drain(..).map(Some).collect();
+        self.exercises = shuffled_order(slots.len(), seed)
+            .into_iter()
+            .filter_map(|idx| slots[idx].take())
+            .collect();
+        self.current_index = Self::resolve_current_index(&self.exercises, &self.progress);
+        self.progress.shuffle_seed = Some(seed);
+    }
+
+    /// Narrow the active exercises to those tagged `tag`; progress is untouched
+    ///
+    /// Returns how many exercises carry the tag. With none, nothing is
+    /// filtered out (an empty pack would have nothing to show).
+    pub fn filter_by_tag(&mut self, tag: &str) -> usize {
+        let matching = self.exercises.iter().filter(|e| e.has_tag(tag)).count();
+        if matching > 0 {
+            self.exercises.retain(|e| e.has_tag(tag));
+            self.current_index = Self::resolve_current_index(&self.exercises, &self.progress);
+        }
+        matching
+    }
+
+    /// Keep going without saving, leaving the lock to the other session
+    pub fn continue_read_only(&mut self) {
+        self.persist = false;
+        self.lock_holder = None;
+    }
 
+    /// Claim the lock from the other session; its later saves may be lost
+    pub fn take_over_lock(&mut self) -> Result<()> {
+        self.lock = Some(lock::take_over(&lock::lock_path(&self.progress_path))?);
+        self.lock_holder = None;
+        Ok(())
+    }
+
+    /// Whether progress changes are saved
+    pub fn is_read_only(&self) -> bool {
+        !self.persist
+    }
+
+    /// Assemble state from already-loaded pieces
+    fn from_parts(
+        pack_root: PathBuf,
+        info: InfoToml,
+        exercises: Vec<Exercise>,
+        progress_path: PathBuf,
+        progress: ProgressFile,
+    ) -> Self {
         // Determine current index from progress
         let current_index = Self::resolve_current_index(&exercises, &progress);
 
-        Ok(Self {
+        Self {
             pack_root,
             info,
             exercises,
             progress_path,
             progress,
+            persist: true,
+            lock: None,
+            lock_holder: None,
             current_index,
+            current_since: Instant::now(),
             last_verify: None,
             verifying: false,
-        })
-    }
-
-    /// Load from current directory (auto-discover pack root)
-    pub fn load_from_cwd() -> Result<Self> {
-        let cwd = std::env::current_dir()
-            .context("Failed to get current directory")?;
-        let pack_root = find_pack_root(&cwd)?;
-        Self::load(pack_root)
+            verify_started: None,
+            spinner_tick: 0,
+            output_scroll: 0,
+            show_raw_zenml: false,
+            output_view: OutputView::default(),
+            author_mode: false,
+            solution_check: false,
+            watch_enabled: true,
+            auto_advance: false,
+            auto_advance_at: None,
+            stack_summary: None,
+            history: VecDeque::new(),
+            notice: None,
+        }
     }
 
     /// Load progress file or create default
@@ -137,25 +411,48 @@ impl AppState {
 
     /// Save progress to file
     pub fn save_progress(&mut self) -> Result<()> {
+        self.record_time_spent();
+
         // Update timestamps
         self.progress.last_activity = Some(ProgressFile::now_iso());
         self.progress.current = Some(self.current_exercise().name.clone());
 
-        // Write atomically via temp file
-        let tmp_path = self.progress_path.with_extension("json.tmp");
-        let content = serde_json::to_string_pretty(&self.progress)
-            .context("Failed to serialize progress")?;
+        if !self.persist {
+            return Ok(());
+        }
+        write_json_atomic(&self.progress_path, &self.progress)
+    }
 
-        let mut file = fs::File::create(&tmp_path)
-            .with_context(|| format!("Failed to create temp file: {:?}", tmp_path))?;
-        file.write_all(content.as_bytes())
-            .context("Failed to write progress")?;
-        file.sync_all()?;
+    /// Credit time since the last checkpoint to the current exercise
+    fn record_time_spent(&mut self) {
+        let elapsed = self.current_since.elapsed().as_secs();
+        self.current_since = Instant::now();
+        if elapsed > 0 {
+            let name = self.current_exercise().name.clone();
+            *self.progress.time_spent_secs.entry(name).or_insert(0) += elapsed;
+        }
+    }
 
-        fs::rename(&tmp_path, &self.progress_path)
-            .with_context(|| "Failed to rename temp progress file")?;
+    /// Seconds spent on an exercise so far
+    pub fn time_spent_secs(&self, exercise_name: &str) -> u64 {
+        self.progress
+            .time_spent_secs
+            .get(exercise_name)
+            .copied()
+            .unwrap_or(0)
+    }
 
-        Ok(())
+    /// Show a short confirmation in the main view for a couple of seconds
+    pub fn set_notice(&mut self, message: impl Into<String>) {
+        self.notice = Some((message.into(), Instant::now()));
+    }
+
+    /// The notice to show, if it hasn't expired
+    pub fn current_notice(&self) -> Option<&str> {
+        self.notice
+            .as_ref()
+            .filter(|(_, set_at)| set_at.elapsed() < NOTICE_DURATION)
+            .map(|(message, _)| message.as_str())
     }
 
     /// Get current exercise
@@ -171,40 +468,252 @@ impl AppState {
     /// Mark an exercise as completed
     pub fn mark_completed(&mut self, exercise_name: &str) {
         if !self.is_completed(exercise_name) {
+            log::debug!("state: completed {}", exercise_name);
             self.progress.completed.push(exercise_name.to_string());
+            self.progress.skipped.remove(exercise_name);
+            self.push_history(HistoryEvent::Completed {
+                name: exercise_name.to_string(),
+                previous_hash: self.progress.passed_hash.get(exercise_name).cloned(),
+            });
+        }
+    }
+
+    /// Record that the learner skipped an exercise (it stays incomplete)
+    pub fn skip(&mut self, exercise_name: &str, reason: Option<String>) {
+        log::debug!("state: skipped {} ({:?})", exercise_name, reason);
+        self.progress.skipped.insert(exercise_name.to_string(), reason);
+    }
+
+    /// Whether an exercise was skipped and hasn't been solved since
+    pub fn is_skipped(&self, exercise_name: &str) -> bool {
+        self.progress.skipped.contains_key(exercise_name)
+    }
+
+    /// Why an exercise was skipped, if the learner said
+    pub fn skip_reason(&self, exercise_name: &str) -> Option<&str> {
+        self.progress.skipped.get(exercise_name)?.as_deref()
+    }
+
+    /// Count a verification run of an exercise
+    ///
+    /// Runs after the exercise is completed don't count, so the total is
+    /// how many runs it took to solve.
+    pub fn record_attempt(&mut self, exercise_name: &str) {
+        if !self.is_completed(exercise_name) {
+            *self.progress.attempts.entry(exercise_name.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    /// Note that the learner opened an exercise's solution
+    ///
+    /// This is only remembered for the completion summary; a solved exercise
+    /// stays solved.
+    pub fn record_solution_viewed(&mut self, exercise_name: &str) {
+        self.progress.solutions_viewed.insert(exercise_name.to_string());
+    }
+
+    /// Verification runs recorded for an exercise
+    pub fn attempts(&self, exercise_name: &str) -> u32 {
+        self.progress.attempts.get(exercise_name).copied().unwrap_or(0)
+    }
+
+    /// Whether the learner may overwrite an exercise with its solution:
+    /// once solved, or after `RESET_MIN_ATTEMPTS` tries
+    pub fn can_reset_to_solution(&self, exercise_name: &str) -> bool {
+        self.is_completed(exercise_name) || self.attempts(exercise_name) >= RESET_MIN_ATTEMPTS
+    }
+
+    /// Dashboard link for the current exercise's last run: the one ZenML
+    /// gave, else one built from the server's dashboard URL and the run id
+    pub fn last_run_url(&self) -> Option<String> {
+        let result = self.last_verify.as_ref()?;
+        if let Some(url) = &result.run_url {
+            return Some(url.clone());
         }
+        let dashboard = self.stack_summary.as_ref()?.dashboard_url.as_deref()?;
+        let run_id = result.run_id.as_deref()?;
+        Some(format!("{}/runs/{}", dashboard.trim_end_matches('/'), run_id))
     }
 
-    /// Move to next exercise
+    /// Remember what an exercise file looked like when it passed
+    pub fn record_passed_hash(&mut self, exercise_name: &str) {
+        let Some(exercise) = self.exercises.iter().find(|e| e.name == exercise_name) else {
+            return;
+        };
+        if let Some(content) = watched_content(exercise) {
+            self.progress
+                .passed_hash
+                .insert(exercise_name.to_string(), content_hash(&content));
+        }
+    }
+
+    /// Hash of the current exercise's files, to tell whether an edit changed them
+    pub fn current_content_hash(&self) -> Option<String> {
+        watched_content(self.current_exercise()).map(|content| content_hash(&content))
+    }
+
+    /// Whether the current exercise file is unchanged since it last passed
+    pub fn unchanged_since_pass(&self) -> bool {
+        let exercise = self.current_exercise();
+        let Some(stored) = self.progress.passed_hash.get(&exercise.name) else {
+            return false;
+        };
+        watched_content(exercise).is_some_and(|content| content_hash(&content) == *stored)
+    }
+
+    /// Move to next exercise (no-op on the last one)
     pub fn next(&mut self) {
-        if self.current_index < self.exercises.len() - 1 {
-            self.current_index += 1;
-            self.last_verify = None;
+        if self.current_index + 1 < self.exercises.len() {
+            self.move_to(self.current_index + 1);
         }
     }
 
-    /// Move to previous exercise
+    /// Move to previous exercise (no-op on the first one)
     pub fn prev(&mut self) {
         if self.current_index > 0 {
-            self.current_index -= 1;
-            self.last_verify = None;
+            self.move_to(self.current_index - 1);
+        }
+    }
+
+    /// Move to the exercise at `idx` (no-op if it's current or out of range)
+    pub fn jump_to(&mut self, idx: usize) {
+        if idx < self.exercises.len() && idx != self.current_index {
+            self.move_to(idx);
         }
     }
 
     /// Set current exercise by name
+    ///
+    /// Also accepts the 1-based index shown in the exercise list, and
+    /// falls back to the closest name if exactly one is a near miss.
     pub fn set_current_by_name(&mut self, name: &str) -> Result<()> {
+        let idx = self.resolve_exercise_index(name)?;
+        if idx != self.current_index {
+            self.move_to(idx);
+        }
+        Ok(())
+    }
+
+    fn move_to(&mut self, idx: usize) {
+        self.record_time_spent();
+        self.push_history(HistoryEvent::Moved {
+            from: self.current_index,
+        });
+        self.current_index = idx;
+        self.last_verify = None;
+        self.auto_advance_at = None;
+        log::debug!("state: moved to {}", self.exercises[idx].name);
+    }
+
+    /// The first incomplete exercise after the current one, wrapping around
+    /// to earlier ones (`None` if every other exercise is done)
+    pub fn next_incomplete_index(&self) -> Option<usize> {
+        let len = self.exercises.len();
+        (1..len)
+            .map(|offset| (self.current_index + offset) % len)
+            .find(|&idx| !self.is_completed(&self.exercises[idx].name))
+    }
+
+    fn push_history(&mut self, event: HistoryEvent) {
+        if self.history.len() == UNDO_LIMIT {
+            self.history.pop_front();
+        }
+        self.history.push_back(event);
+    }
+
+    /// Revert the most recent navigation or completion
+    ///
+    /// Returns the event that was undone, or `None` if there's nothing to
+    /// undo. The caller is responsible for saving progress afterwards.
+    pub fn undo(&mut self) -> Option<HistoryEvent> {
+        let event = self.history.pop_back()?;
+        match &event {
+            HistoryEvent::Moved { from } => {
+                self.record_time_spent();
+                self.current_index = *from;
+            }
+            HistoryEvent::Completed {
+                name,
+                previous_hash,
+            } => {
+                self.progress.completed.retain(|n| n != name);
+                match previous_hash {
+                    Some(hash) => self.progress.passed_hash.insert(name.clone(), hash.clone()),
+                    None => self.progress.passed_hash.remove(name),
+                };
+            }
+        }
+        self.last_verify = None;
+        Some(event)
+    }
+
+    /// Resolve optional `--from`/`--to` exercises to an inclusive index range
+    ///
+    /// Missing ends default to the first/last exercise.
+    pub fn exercise_range(&self, from: Option<&str>, to: Option<&str>) -> Result<RangeInclusive<usize>> {
+        let start = match from {
+            Some(name) => self.resolve_exercise_index(name).context("Invalid --from")?,
+            None => 0,
+        };
+        let end = match to {
+            Some(name) => self.resolve_exercise_index(name).context("Invalid --to")?,
+            None => self.exercises.len() - 1,
+        };
+        if start > end {
+            anyhow::bail!(
+                "--from '{}' (#{}) comes after --to '{}' (#{})",
+                self.exercises[start].name,
+                start + 1,
+                self.exercises[end].name,
+                end + 1
+            );
+        }
+        Ok(start..=end)
+    }
+
+    /// Resolve an exercise name (or 1-based list index) to an index
+    fn resolve_exercise_index(&self, name: &str) -> Result<usize> {
         if let Some(idx) = self.exercises.iter().position(|e| e.name == name) {
-            self.current_index = idx;
-            self.last_verify = None;
-            Ok(())
-        } else {
-            anyhow::bail!("Exercise not found: {}", name)
+            return Ok(idx);
+        }
+
+        if let Ok(number) = name.parse::<usize>() {
+            if (1..=self.exercises.len()).contains(&number) {
+                return Ok(number - 1);
+            }
+            anyhow::bail!(
+                "Exercise number {} is out of range (1-{})",
+                number,
+                self.exercises.len()
+            );
+        }
+
+        let suggestions = closest_names(name, self.exercises.iter().map(|e| e.name.as_str()));
+        match suggestions.as_slice() {
+            [only] => Ok(self
+                .exercises
+                .iter()
+                .position(|e| e.name == *only)
+                .expect("suggestion comes from exercise list")),
+            [] => anyhow::bail!("Exercise not found: {}", name),
+            many => anyhow::bail!(
+                "Exercise not found: {}. Did you mean: {}?",
+                name,
+                many.join(", ")
+            ),
         }
     }
 
     /// Count completed exercises
+    ///
+    /// Only exercises present in the pack count, so stale names left in the
+    /// progress file (e.g. from a renamed exercise) can't push this past
+    /// `total_count()`.
     pub fn completed_count(&self) -> usize {
-        self.progress.completed.len()
+        self.exercises
+            .iter()
+            .filter(|e| self.is_completed(&e.name))
+            .count()
     }
 
     /// Total number of exercises
@@ -212,11 +721,83 @@ impl AppState {
         self.exercises.len()
     }
 
+    /// Exercises not completed yet
+    pub fn remaining_count(&self) -> usize {
+        self.total_count() - self.completed_count()
+    }
+
+    /// Rough seconds left to finish the pack, from the average time spent on
+    /// the exercises solved so far
+    ///
+    /// `None` until at least one solved exercise has recorded time.
+    pub fn estimated_time_remaining(&self) -> Option<u64> {
+        let times: Vec<u64> = self
+            .exercises
+            .iter()
+            .filter(|e| self.is_completed(&e.name))
+            .map(|e| self.time_spent_secs(&e.name))
+            .filter(|&secs| secs > 0)
+            .collect();
+        if times.is_empty() {
+            return None;
+        }
+        let average = times.iter().sum::<u64>() / times.len() as u64;
+        Some(average * self.remaining_count() as u64)
+    }
+
     /// Check if all exercises are completed
     pub fn all_completed(&self) -> bool {
         self.completed_count() >= self.total_count()
     }
 
+    /// Title for an exercise directory: its `[[sections]]` title, or the dir
+    pub fn section_title<'a>(&'a self, dir: &'a str) -> &'a str {
+        self.info
+            .sections
+            .iter()
+            .find(|s| s.dir == dir)
+            .map_or(dir, |s| s.title.as_str())
+    }
+
+    /// Completed and total exercises in a directory
+    pub fn section_progress(&self, dir: &str) -> (usize, usize) {
+        let in_dir = || self.exercises.iter().filter(|e| e.dir == dir);
+        let completed = in_dir().filter(|e| self.is_completed(&e.name)).count();
+        (completed, in_dir().count())
+    }
+
+    /// Totals over the pack's exercises for the completion screen
+    ///
+    /// Exercises solved before attempts were recorded are left out of the
+    /// average rather than counted as zero.
+    pub fn completion_stats(&self) -> CompletionStats {
+        let names = || self.exercises.iter().map(|e| e.name.as_str());
+        let attempts: Vec<u32> = names()
+            .filter(|name| self.is_completed(name))
+            .map(|name| self.attempts(name))
+            .filter(|&n| n > 0)
+            .collect();
+
+        CompletionStats {
+            solved: self.completed_count(),
+            total: self.total_count(),
+            time_spent_secs: names().map(|name| self.time_spent_secs(name)).sum(),
+            hints_used: names().map(|name| hints_used_count(&self.progress, name)).sum(),
+            average_attempts: (!attempts.is_empty())
+                .then(|| attempts.iter().sum::<u32>() as f64 / attempts.len() as f64),
+            total_attempts: names().map(|name| self.attempts(name)).sum(),
+            no_solutions_viewed: !names().any(|name| self.progress.solutions_viewed.contains(name)),
+        }
+    }
+
+    /// Whether to greet the user with the welcome message
+    ///
+    /// Only first-time users see it; returning users (who have started
+    /// and already seen the welcome) go straight to their exercise.
+    pub fn should_show_welcome(&self) -> bool {
+        !(self.progress.started_at.is_some() && self.progress.welcome_shown)
+    }
+
     /// Get the welcome message
     pub fn welcome_message(&self) -> Option<&str> {
         self.info.welcome_message.as_deref()
@@ -227,3 +808,646 @@ impl AppState {
         self.info.final_message.as_deref()
     }
 }
+
+/// Removes a temp file on drop unless it has been persisted
+struct TempFileGuard {
+    path: PathBuf,
+    armed: bool,
+}
+
+impl TempFileGuard {
+    fn new(path: PathBuf) -> Self {
+        Self { path, armed: true }
+    }
+
+    /// Keep the file (it has been renamed into place)
+    fn disarm(&mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Build a temp path next to `path`, unique per process and per call
+fn unique_tmp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| PROGRESS_FILENAME.to_string());
+    let seq = TMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!("{}.{}.{}.tmp", file_name, std::process::id(), seq))
+}
+
+/// Serialize `value` as pretty JSON and atomically replace `path` with it.
+///
+/// The data is written to a uniquely-named temp file in the same directory
+/// and renamed into place. The temp file is removed on any error.
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let tmp_path = unique_tmp_path(path);
+    let file = fs::File::create(&tmp_path)
+        .with_context(|| format!("Failed to create temp file: {:?}", tmp_path))?;
+    let mut guard = TempFileGuard::new(tmp_path);
+
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, value)
+        .context("Failed to serialize progress")?;
+    let file = writer.into_inner().context("Failed to write progress")?;
+    file.sync_all()?;
+
+    fs::rename(&guard.path, path)
+        .with_context(|| "Failed to rename temp progress file")?;
+    guard.disarm();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exercise::ExerciseEntry;
+
+    /// Build an in-memory state with the given exercises (no files touched)
+    fn test_state(names: &[&str], completed: &[&str]) -> AppState {
+        test_state_in(PathBuf::from("/tmp/zenlings"), names, completed)
+    }
+
+    fn test_state_in(pack_root: PathBuf, names: &[&str], completed: &[&str]) -> AppState {
+        let exercises: Vec<Exercise> = names
+            .iter()
+            .map(|name| {
+                let entry = ExerciseEntry {
+                    name: name.to_string(),
+                    dir: "00_intro".to_string(),
+                    ..Default::default()
+                };
+                Exercise::from_entry(&entry, &pack_root, None)
+            })
+            .collect();
+
+        let mut progress = ProgressFile::new();
+        progress.completed = completed.iter().map(|s| s.to_string()).collect();
+
+        let info = InfoToml {
+            format_version: 1,
+            ..Default::default()
+        };
+        let progress_path = pack_root.join(PROGRESS_FILENAME);
+        AppState::from_parts(pack_root, info, exercises, progress_path, progress)
+    }
+
+    /// A pack on disk with a single empty exercise, `intro1`
+    fn temp_pack() -> tempfile::TempDir {
+        let pack = tempfile::tempdir().unwrap();
+        fs::write(
+            pack.path().join("info.toml"),
+            "format_version = 1\n[[exercises]]\nname = \"intro1\"\ndir = \"00_intro\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(pack.path().join("exercises/00_intro")).unwrap();
+        fs::write(pack.path().join("exercises/00_intro/intro1.py"), "").unwrap();
+        pack
+    }
+
+    #[test]
+    fn test_exercise_range() {
+        let state = test_state(&["intro1", "intro2", "load1", "load2"], &[]);
+        assert_eq!(state.exercise_range(None, None).unwrap(), 0..=3);
+        assert_eq!(state.exercise_range(Some("intro2"), Some("load1")).unwrap(), 1..=2);
+        assert_eq!(state.exercise_range(Some("load1"), None).unwrap(), 2..=3);
+        assert_eq!(state.exercise_range(None, Some("2")).unwrap(), 0..=1);
+        assert_eq!(state.exercise_range(Some("load1"), Some("load1")).unwrap(), 2..=2);
+
+        let err = state.exercise_range(Some("load2"), Some("intro1")).unwrap_err();
+        assert!(err.to_string().contains("comes after"));
+        assert!(state.exercise_range(Some("nonsense"), None).is_err());
+    }
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        // FNV-1a reference values
+        assert_eq!(content_hash(b""), "cbf29ce484222325");
+        assert_eq!(content_hash(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_unchanged_since_pass() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = test_state_in(dir.path().to_path_buf(), &["intro1"], &[]);
+        let path = state.current_exercise().path.clone();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "print('hi')\n").unwrap();
+
+        assert!(!state.unchanged_since_pass());
+
+        state.record_passed_hash("intro1");
+        assert!(state.unchanged_since_pass());
+
+        fs::write(&path, "print('changed')\n").unwrap();
+        assert!(!state.unchanged_since_pass());
+    }
+
+    #[test]
+    fn test_unchanged_since_pass_covers_watched_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = test_state_in(dir.path().to_path_buf(), &["intro1"], &[]);
+        let path = state.current_exercise().path.clone();
+        let helper = path.with_file_name("steps.py");
+        state.exercises[0].watch = vec![helper.clone()];
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "import steps\n").unwrap();
+        fs::write(&helper, "def step(): ...\n").unwrap();
+
+        state.record_passed_hash("intro1");
+        assert!(state.unchanged_since_pass());
+
+        fs::write(&helper, "def step(): return 1\n").unwrap();
+        assert!(!state.unchanged_since_pass());
+    }
+
+    #[test]
+    fn test_next_prev_single_exercise() {
+        let mut state = test_state(&["intro1"], &[]);
+        assert_eq!(state.current_index, 0);
+
+        state.next();
+        assert_eq!(state.current_index, 0);
+        state.prev();
+        assert_eq!(state.current_index, 0);
+        assert_eq!(state.current_exercise().name, "intro1");
+    }
+
+    #[test]
+    fn test_next_prev_boundaries() {
+        let mut state = test_state(&["a", "b", "c"], &[]);
+
+        state.prev();
+        assert_eq!(state.current_index, 0);
+
+        state.next();
+        state.next();
+        assert_eq!(state.current_index, 2);
+        state.next();
+        assert_eq!(state.current_index, 2);
+
+        state.prev();
+        assert_eq!(state.current_index, 1);
+    }
+
+    #[test]
+    fn test_shuffled_order() {
+        let order = shuffled_order(10, 42);
+        assert_eq!(order, shuffled_order(10, 42));
+        assert_ne!(order, shuffled_order(10, 43));
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+        assert!(shuffled_order(0, 42).is_empty());
+    }
+
+    #[test]
+    fn test_shuffle_keeps_progress_and_current() {
+        let names = ["a", "b", "c", "d", "e", "f"];
+        let mut state = test_state(&names, &["a"]);
+        state.jump_to(2);
+        state.progress.current = Some("c".to_string());
+
+        state.shuffle(7);
+        assert_eq!(state.current_exercise().name, "c");
+        assert!(state.is_completed("a"));
+        assert_eq!(state.progress.shuffle_seed, Some(7));
+        let order: Vec<_> = state.exercises.iter().map(|e| e.name.clone()).collect();
+        assert_ne!(order, names);
+
+        // The same seed gives the same order
+        let mut again = test_state(&names, &["a"]);
+        again.shuffle(7);
+        let order_again: Vec<_> = again.exercises.iter().map(|e| e.name.clone()).collect();
+        assert_eq!(order, order_again);
+    }
+
+    #[test]
+    fn test_next_incomplete_index() {
+        let mut state = test_state(&["a", "b", "c", "d"], &["b"]);
+        assert_eq!(state.next_incomplete_index(), Some(2));
+
+        // Wraps around past the end
+        state.jump_to(3);
+        assert_eq!(state.next_incomplete_index(), Some(0));
+
+        // Nothing left but the current exercise
+        let state = test_state(&["a", "b"], &["b"]);
+        assert_eq!(state.next_incomplete_index(), None);
+    }
+
+    #[test]
+    fn test_attempts_stop_counting_once_completed() {
+        let mut state = test_state(&["a", "b"], &[]);
+        assert_eq!(state.attempts("a"), 0);
+
+        state.record_attempt("a");
+        state.record_attempt("a");
+        state.mark_completed("a");
+        state.record_attempt("a");
+
+        assert_eq!(state.attempts("a"), 2);
+        assert_eq!(state.attempts("b"), 0);
+    }
+
+    #[test]
+    fn test_completion_stats() {
+        let mut state = test_state(&["a", "b", "c"], &["a", "b"]);
+        state.progress.attempts.insert("a".to_string(), 1);
+        state.progress.attempts.insert("b".to_string(), 4);
+        state.progress.attempts.insert("c".to_string(), 9);
+        state.progress.hints_used.insert("a".to_string(), 2);
+        state.progress.hints_used.insert("gone".to_string(), 5);
+        state.progress.time_spent_secs.insert("a".to_string(), 60);
+        state.progress.time_spent_secs.insert("c".to_string(), 30);
+
+        let stats = state.completion_stats();
+        assert_eq!(stats.solved, 2);
+        assert_eq!(stats.total, 3);
+        assert_eq!(stats.time_spent_secs, 90);
+        assert_eq!(stats.hints_used, 2);
+        // c isn't solved, so its attempts don't count
+        assert_eq!(stats.average_attempts, Some(2.5));
+        assert_eq!(stats.total_attempts, 14);
+    }
+
+    #[test]
+    fn test_solution_views_dont_unsolve() {
+        let mut state = test_state(&["a", "b"], &["a", "b"]);
+        // Views of exercises no longer in the pack don't count
+        state.record_solution_viewed("gone");
+        assert!(state.completion_stats().no_solutions_viewed);
+
+        state.record_solution_viewed("a");
+        assert!(state.is_completed("a"));
+        assert!(state.progress.solutions_viewed.contains("a"));
+        assert!(!state.completion_stats().no_solutions_viewed);
+    }
+
+    #[test]
+    fn test_can_reset_to_solution() {
+        let mut state = test_state(&["a", "b"], &["a"]);
+        assert!(state.can_reset_to_solution("a"));
+        assert!(!state.can_reset_to_solution("b"));
+        for _ in 0..RESET_MIN_ATTEMPTS {
+            state.record_attempt("b");
+        }
+        assert!(state.can_reset_to_solution("b"));
+    }
+
+    #[test]
+    fn test_completion_stats_without_attempts() {
+        let state = test_state(&["a"], &["a"]);
+        assert_eq!(state.completion_stats().average_attempts, None);
+    }
+
+    #[test]
+    fn test_estimated_time_remaining() {
+        let mut state = test_state(&["a", "b", "c", "d"], &["a", "b"]);
+        assert_eq!(state.remaining_count(), 2);
+        assert_eq!(state.estimated_time_remaining(), None);
+
+        state.progress.time_spent_secs.insert("a".to_string(), 60);
+        state.progress.time_spent_secs.insert("b".to_string(), 180);
+        // Time on unsolved exercises doesn't skew the average
+        state.progress.time_spent_secs.insert("c".to_string(), 3000);
+        assert_eq!(state.estimated_time_remaining(), Some(240));
+    }
+
+    #[test]
+    fn test_sections() {
+        let mut state = test_state(&["a", "b"], &["a"]);
+        state.info.sections.push(crate::exercise::Section {
+            dir: "00_intro".to_string(),
+            title: "Getting started".to_string(),
+        });
+
+        assert_eq!(state.section_title("00_intro"), "Getting started");
+        assert_eq!(state.section_title("01_other"), "01_other");
+        assert_eq!(state.section_progress("00_intro"), (1, 2));
+        assert_eq!(state.section_progress("01_other"), (0, 0));
+    }
+
+    #[test]
+    fn test_notice_expires() {
+        let mut state = test_state(&["a"], &[]);
+        assert_eq!(state.current_notice(), None);
+
+        state.set_notice("Copied");
+        assert_eq!(state.current_notice(), Some("Copied"));
+
+        state.notice = Some(("Copied".to_string(), Instant::now() - NOTICE_DURATION));
+        assert_eq!(state.current_notice(), None);
+    }
+
+    #[test]
+    fn test_last_run_url() {
+        let mut state = test_state(&["a"], &[]);
+        assert_eq!(state.last_run_url(), None);
+
+        let mut result = VerifyResult::cached_pass("a");
+        result.run_id = Some("3f2a".to_string());
+        state.last_verify = Some(result.clone());
+        // No link from ZenML and no known dashboard
+        assert_eq!(state.last_run_url(), None);
+
+        state.stack_summary = Some(StackSummary {
+            dashboard_url: Some("http://127.0.0.1:8237/".to_string()),
+            ..StackSummary::default()
+        });
+        assert_eq!(state.last_run_url().as_deref(), Some("http://127.0.0.1:8237/runs/3f2a"));
+
+        result.run_url = Some("https://cloud.example.com/runs/3f2a".to_string());
+        state.last_verify = Some(result);
+        assert_eq!(state.last_run_url().as_deref(), Some("https://cloud.example.com/runs/3f2a"));
+    }
+
+    #[test]
+    fn test_skip_stays_incomplete_until_solved() {
+        let mut state = test_state(&["a", "b"], &["b"]);
+        state.skip("a", Some("stuck on map".to_string()));
+        assert!(state.is_skipped("a"));
+        assert_eq!(state.skip_reason("a"), Some("stuck on map"));
+        assert!(!state.is_completed("a"));
+        assert!(!state.all_completed());
+
+        state.mark_completed("a");
+        assert!(!state.is_skipped("a"));
+        assert!(state.all_completed());
+
+        state.skip("b", None);
+        assert!(state.is_skipped("b"));
+        assert_eq!(state.skip_reason("b"), None);
+    }
+
+    #[test]
+    fn test_filter_by_tag_keeps_progress() {
+        let mut state = test_state(&["a", "b", "c"], &["a", "b"]);
+        state.exercises[0].tags = vec!["map".to_string()];
+        state.exercises[2].tags = vec!["Map".to_string(), "io".to_string()];
+
+        assert_eq!(state.filter_by_tag("nope"), 0);
+        assert_eq!(state.total_count(), 3);
+
+        assert_eq!(state.filter_by_tag("map"), 2);
+        let names: Vec<&str> = state.exercises.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["a", "c"]);
+        assert_eq!(state.current_exercise().name, "c");
+        assert_eq!(state.completed_count(), 1);
+        // Completions outside the filter are still recorded
+        assert!(state.is_completed("b"));
+    }
+
+    #[test]
+    fn test_undo_navigation() {
+        let mut state = test_state(&["a", "b", "c"], &[]);
+        assert_eq!(state.undo(), None);
+
+        state.next();
+        state.next();
+        state.next(); // no-op on the last exercise, nothing recorded
+        assert_eq!(state.current_index, 2);
+
+        assert_eq!(state.undo(), Some(HistoryEvent::Moved { from: 1 }));
+        assert_eq!(state.current_index, 1);
+        state.undo();
+        assert_eq!(state.current_index, 0);
+        assert_eq!(state.undo(), None);
+    }
+
+    #[test]
+    fn test_undo_completion() {
+        let mut state = test_state(&["a", "b"], &["b"]);
+        state.progress.passed_hash.insert("a".to_string(), "old".to_string());
+
+        state.mark_completed("a");
+        state.progress.passed_hash.insert("a".to_string(), "new".to_string());
+        state.mark_completed("b"); // already completed, nothing recorded
+        state.next();
+
+        state.undo();
+        assert_eq!(state.current_index, 0);
+        assert!(state.is_completed("a"));
+
+        state.undo();
+        assert!(!state.is_completed("a"));
+        assert!(state.is_completed("b"));
+        assert_eq!(state.progress.passed_hash["a"], "old");
+    }
+
+    #[test]
+    fn test_undo_history_is_bounded() {
+        let names: Vec<String> = (0..30).map(|i| format!("ex{}", i)).collect();
+        let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+        let mut state = test_state(&names, &[]);
+        for _ in 0..29 {
+            state.next();
+        }
+
+        let mut undone = 0;
+        while state.undo().is_some() {
+            undone += 1;
+        }
+        assert_eq!(undone, UNDO_LIMIT);
+        assert_eq!(state.current_index, 29 - UNDO_LIMIT);
+    }
+
+    #[test]
+    fn test_all_completed_lands_on_last_exercise() {
+        let mut state = test_state(&["a", "b"], &["a", "b"]);
+        assert_eq!(state.current_index, 1);
+        assert!(state.all_completed());
+
+        state.next();
+        assert_eq!(state.current_exercise().name, "b");
+
+        let single = test_state(&["a"], &["a"]);
+        assert_eq!(single.current_index, 0);
+        assert!(single.all_completed());
+    }
+
+    #[test]
+    fn test_set_current_by_name_exact_and_index() {
+        let mut state = test_state(&["load1", "load2", "map1"], &[]);
+
+        state.set_current_by_name("map1").unwrap();
+        assert_eq!(state.current_index, 2);
+
+        state.set_current_by_name("2").unwrap();
+        assert_eq!(state.current_exercise().name, "load2");
+
+        assert!(state.set_current_by_name("0").is_err());
+        assert!(state.set_current_by_name("4").is_err());
+    }
+
+    #[test]
+    fn test_set_current_by_name_fuzzy() {
+        let mut state = test_state(&["load1", "load2", "map1"], &[]);
+
+        // Single close match is auto-selected
+        state.set_current_by_name("mpa1").unwrap();
+        assert_eq!(state.current_exercise().name, "map1");
+
+        // Ambiguous near misses produce suggestions
+        let err = state.set_current_by_name("load").unwrap_err().to_string();
+        assert!(err.contains("Did you mean: load1, load2?"), "{}", err);
+
+        let err = state.set_current_by_name("capstone").unwrap_err().to_string();
+        assert_eq!(err, "Exercise not found: capstone");
+        assert_eq!(state.current_exercise().name, "map1");
+    }
+
+    #[test]
+    fn test_should_show_welcome() {
+        let mut state = test_state(&["a"], &[]);
+        assert!(state.should_show_welcome());
+
+        state.progress.welcome_shown = true;
+        assert!(!state.should_show_welcome());
+
+        // Legacy progress files without `welcome_shown` still parse
+        let legacy: ProgressFile =
+            serde_json::from_str(r#"{"version":1,"current":"a","started_at":"1"}"#).unwrap();
+        assert!(!legacy.welcome_shown);
+    }
+
+    #[test]
+    fn test_time_spent_accrues_to_current_exercise() {
+        let mut state = test_state(&["a", "b"], &[]);
+
+        state.current_since = Instant::now() - std::time::Duration::from_secs(90);
+        state.next();
+        assert_eq!(state.time_spent_secs("a"), 90);
+        assert_eq!(state.time_spent_secs("b"), 0);
+
+        state.current_since = Instant::now() - std::time::Duration::from_secs(30);
+        state.record_time_spent();
+        assert_eq!(state.time_spent_secs("b"), 30);
+    }
+
+    #[test]
+    fn test_completed_count_ignores_unknown_exercises() {
+        let state = test_state(&["a", "b"], &["a", "renamed", "gone"]);
+        assert_eq!(state.completed_count(), 1);
+        assert!(!state.all_completed());
+    }
+
+    /// A value whose serialization always fails
+    struct Unserializable;
+
+    impl Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("injected failure"))
+        }
+    }
+
+    fn dir_entries(dir: &Path) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_custom_progress_file_round_trips() {
+        let pack = temp_pack();
+        let home = tempfile::tempdir().unwrap();
+        let progress = home.path().join("zenlings/pack.json");
+
+        let mut state =
+            AppState::load_with_progress(pack.path().to_path_buf(), Some(progress.clone()), true).unwrap();
+        state.mark_completed("intro1");
+        state.save_progress().unwrap();
+
+        assert!(progress.exists());
+        assert!(!pack.path().join(PROGRESS_FILENAME).exists());
+        let reloaded = AppState::load_with_progress(pack.path().to_path_buf(), Some(progress), true).unwrap();
+        assert!(reloaded.is_completed("intro1"));
+    }
+
+    #[test]
+    fn test_no_save_ignores_and_keeps_progress_file() {
+        let pack = temp_pack();
+        let progress_path = pack.path().join(PROGRESS_FILENAME);
+        let mut saved = ProgressFile::new();
+        saved.completed.push("intro1".to_string());
+        write_json_atomic(&progress_path, &saved).unwrap();
+        let before = fs::read_to_string(&progress_path).unwrap();
+
+        let mut state = AppState::load_with_progress(pack.path().to_path_buf(), None, false).unwrap();
+        assert!(!state.is_completed("intro1"));
+        state.mark_completed("intro1");
+        state.save_progress().unwrap();
+
+        assert_eq!(fs::read_to_string(&progress_path).unwrap(), before);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_locked_progress_can_continue_read_only() {
+        let pack = temp_pack();
+
+        // Our own lock is released when the state goes away
+        let state = AppState::load(pack.path().to_path_buf()).unwrap();
+        let lock_file = lock::lock_path(&pack.path().join(PROGRESS_FILENAME));
+        assert!(lock_file.exists());
+        drop(state);
+        assert!(!lock_file.exists());
+
+        let mut other = std::process::Command::new("sleep").arg("5").spawn().unwrap();
+        fs::write(&lock_file, other.id().to_string()).unwrap();
+        let mut state = AppState::load(pack.path().to_path_buf()).unwrap();
+        assert_eq!(state.lock_holder, Some(other.id()));
+
+        state.continue_read_only();
+        state.mark_completed("intro1");
+        state.save_progress().unwrap();
+        assert!(state.is_read_only());
+        assert!(!pack.path().join(PROGRESS_FILENAME).exists());
+        other.kill().unwrap();
+        other.wait().unwrap();
+    }
+
+    #[test]
+    fn test_write_json_atomic_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PROGRESS_FILENAME);
+
+        write_json_atomic(&path, &ProgressFile::new()).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let parsed: ProgressFile = serde_json::from_str(&content).unwrap();
+        assert_eq!(parsed.version, 1);
+        assert_eq!(dir_entries(dir.path()), vec![PROGRESS_FILENAME.to_string()]);
+    }
+
+    #[test]
+    fn test_write_json_atomic_failure_removes_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PROGRESS_FILENAME);
+
+        assert!(write_json_atomic(&path, &Unserializable).is_err());
+
+        assert!(dir_entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_unique_tmp_path_differs_per_call() {
+        let path = Path::new("/tmp/pack").join(PROGRESS_FILENAME);
+        let a = unique_tmp_path(&path);
+        let b = unique_tmp_path(&path);
+
+        assert_ne!(a, b);
+        assert_eq!(a.parent(), path.parent());
+        assert!(a.to_string_lossy().ends_with(".tmp"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Cache of startup probe results.
+//!
+//! Probing the Python version and ZenML installation spawns several slow
+//! subprocesses. Successful results are stored in `.zenlings-checks.json`
+//! in the pack root and reused until the resolved binaries or the pack's
+//! `.venv` change.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::app_state::write_json_atomic;
+use crate::verify::{PythonVersion, VerifyOptions, ZenmlProbe, resolve_binary};
+
+const CACHE_FILENAME: &str = ".zenlings-checks.json";
+
+/// What the cached probes depend on; any change invalidates the cache
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheKey {
+    python_bin: Option<PathBuf>,
+    python_mtime: Option<u64>,
+    zenml_bin: Option<PathBuf>,
+    zenml_mtime: Option<u64>,
+    venv_mtime: Option<u64>,
+}
+
+/// Probe results from a previous launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedChecks {
+    key: CacheKey,
+    pub python_version: PythonVersion,
+    pub zenml: ZenmlProbe,
+}
+
+impl CacheKey {
+    /// Build the key for the binaries `opts` would run today
+    pub fn current(pack_root: &Path, opts: &VerifyOptions) -> Self {
+        let python_bin = resolve_binary(&opts.python_bin);
+        let zenml_bin = resolve_binary(&opts.zenml_bin);
+        Self {
+            python_mtime: python_bin.as_deref().and_then(mtime_secs),
+            python_bin,
+            zenml_mtime: zenml_bin.as_deref().and_then(mtime_secs),
+            zenml_bin,
+            venv_mtime: mtime_secs(&pack_root.join(".venv")),
+        }
+    }
+}
+
+/// Load cached results if they were recorded under the same key
+pub fn load(pack_root: &Path, key: &CacheKey) -> Option<CachedChecks> {
+    let content = fs::read_to_string(pack_root.join(CACHE_FILENAME)).ok()?;
+    let cached: CachedChecks = serde_json::from_str(&content).ok()?;
+    (cached.key == *key).then_some(cached)
+}
+
+/// Record successful probe results for the next launch
+pub fn save(pack_root: &Path, key: CacheKey, python_version: PythonVersion, zenml: ZenmlProbe) {
+    let cached = CachedChecks {
+        key,
+        python_version,
+        zenml,
+    };
+    // A cache that can't be written just means probing again next time
+    if let Err(e) = write_json_atomic(&pack_root.join(CACHE_FILENAME), &cached) {
+        log::warn!("could not write startup check cache: {:#}", e);
+    }
+}
+
+/// Modification time in Unix seconds
+fn mtime_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    modified
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe() -> ZenmlProbe {
+        ZenmlProbe {
+            zenml_version: Some("0.80.0".to_string()),
+            python_import_ok: true,
+            zenml_cli_ok: true,
+            zenml_cli_version: Some("0.80.0".to_string()),
+        }
+    }
+
+    fn version() -> PythonVersion {
+        PythonVersion { major: 3, minor: 11, patch: 5 }
+    }
+
+    fn opts_in(dir: &Path) -> VerifyOptions {
+        let python = dir.join("python");
+        let zenml = dir.join("zenml");
+        fs::write(&python, "").unwrap();
+        fs::write(&zenml, "").unwrap();
+        VerifyOptions {
+            python_bin: python.to_string_lossy().to_string(),
+            zenml_bin: zenml.to_string_lossy().to_string(),
+            working_dir: dir.to_path_buf(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_with_same_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = CacheKey::current(dir.path(), &opts_in(dir.path()));
+        assert!(load(dir.path(), &key).is_none());
+
+        save(dir.path(), key.clone(), version(), probe());
+        let cached = load(dir.path(), &key).unwrap();
+        assert_eq!(cached.python_version, version());
+        assert_eq!(cached.zenml.zenml_version.as_deref(), Some("0.80.0"));
+    }
+
+    #[test]
+    fn test_changed_key_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let opts = opts_in(dir.path());
+        let key = CacheKey::current(dir.path(), &opts);
+        save(dir.path(), key, version(), probe());
+
+        // Creating a .venv changes the key
+        fs::create_dir(dir.path().join(".venv")).unwrap();
+        let key = CacheKey::current(dir.path(), &opts);
+        assert!(load(dir.path(), &key).is_none());
+    }
+}
@@ -0,0 +1,25 @@
+//! Copying text to the system clipboard.
+//!
+//! Uses `arboard`, which talks to the clipboard directly (X11 and Wayland on
+//! Linux), so no `xclip`/`wl-copy` needs to be installed.
+
+use anyhow::{Context, Result};
+use arboard::Clipboard;
+use std::sync::Mutex;
+
+/// Kept open for the whole session: on Linux the copied text is served by
+/// this process and would vanish if the handle were dropped
+static CLIPBOARD: Mutex<Option<Clipboard>> = Mutex::new(None);
+
+/// Copy `text` to the clipboard
+pub fn copy(text: &str) -> Result<()> {
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    if clipboard.is_none() {
+        *clipboard = Some(Clipboard::new().context("No clipboard available")?);
+    }
+    clipboard
+        .as_mut()
+        .expect("clipboard was just opened")
+        .set_text(text)
+        .context("Failed to copy to the clipboard")
+}
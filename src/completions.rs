@@ -0,0 +1,219 @@
+//! Shell completion scripts for `zenlings completions <shell>`.
+//!
+//! The scripts are generated by `clap_complete` from the clap command
+//! definition, so new flags and subcommands are picked up without touching
+//! this module. Exercise names are dynamic: flags that take one
+//! (`value_name = "EXERCISE"`, e.g. `--exercise`, `--from`, `--to`) are
+//! patched afterwards to call back into `zenlings __complete-exercises`,
+//! which reads `info.toml` from the pack around the current directory and
+//! prints nothing when there isn't one.
+
+use clap::{Arg, Command};
+use std::path::Path;
+
+use crate::exercise;
+
+/// Name of the hidden subcommand the scripts call for exercise names
+pub const EXERCISES_HELPER: &str = "__complete-exercises";
+
+/// Value name that marks a flag as taking an exercise name
+const EXERCISE_VALUE_NAME: &str = "EXERCISE";
+
+/// Shells `zenlings completions` can generate a script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl From<Shell> for clap_complete::Shell {
+    fn from(shell: Shell) -> Self {
+        match shell {
+            Shell::Bash => clap_complete::Shell::Bash,
+            Shell::Zsh => clap_complete::Shell::Zsh,
+            Shell::Fish => clap_complete::Shell::Fish,
+        }
+    }
+}
+
+/// Completion script for `shell`
+pub fn generate(shell: Shell, cmd: &Command) -> String {
+    let mut cmd = cmd.clone();
+    let bin = cmd.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(clap_complete::Shell::from(shell), &mut cmd, &bin, &mut script);
+    let script = String::from_utf8_lossy(&script);
+
+    let helper = format!("{} {} 2>/dev/null", bin, EXERCISES_HELPER);
+    match shell {
+        Shell::Bash => hook_bash(&script, &exercise_flags(&cmd), &helper),
+        Shell::Zsh => hook_zsh(&script, &helper),
+        Shell::Fish => hook_fish(&script, &exercise_flags(&cmd), &helper),
+    }
+}
+
+/// Exercise names of the pack containing `start`, or none outside a pack
+pub fn exercise_names(start: &Path) -> Vec<String> {
+    let Ok(root) = exercise::find_pack_root(start) else {
+        return Vec::new();
+    };
+    exercise::load_info_toml(&root.join("info.toml"))
+        .and_then(|info| exercise::load_exercises(&root, &info))
+        .map(|exercises| exercises.into_iter().map(|e| e.name).collect())
+        .unwrap_or_default()
+}
+
+fn takes_exercise(arg: &Arg) -> bool {
+    arg.get_value_names()
+        .is_some_and(|names| names.iter().any(|name| name == EXERCISE_VALUE_NAME))
+}
+
+/// Long names (and visible aliases) of every flag that takes an exercise
+fn exercise_flags(cmd: &Command) -> Vec<String> {
+    let mut flags: Vec<String> = std::iter::once(cmd)
+        .chain(cmd.get_subcommands())
+        .flat_map(Command::get_arguments)
+        .filter(|arg| takes_exercise(arg))
+        .flat_map(|arg| arg.get_long_and_visible_aliases().unwrap_or_default())
+        .map(str::to_string)
+        .collect();
+    flags.sort();
+    flags.dedup();
+    flags
+}
+
+/// bash: the `--flag)` case of each exercise flag completes with the helper
+fn hook_bash(script: &str, flags: &[String], helper: &str) -> String {
+    let mut out = String::new();
+    let mut in_case = false;
+    for line in script.lines() {
+        let trimmed = line.trim_start();
+        if flags.iter().any(|flag| trimmed == format!("--{})", flag)) {
+            in_case = true;
+        } else if in_case && trimmed.starts_with("COMPREPLY=") {
+            let indent = &line[..line.len() - trimmed.len()];
+            out.push_str(&format!("{}COMPREPLY=($(compgen -W \"$({})\" -- \"${{cur}}\"))\n", indent, helper));
+            in_case = false;
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// zsh: exercise values are completed by the helper instead of `_default`
+fn hook_zsh(script: &str, helper: &str) -> String {
+    script.replace(
+        &format!(":{}:_default'", EXERCISE_VALUE_NAME),
+        &format!(":{}:{{compadd -- ${{(f)\"$({})\"}}}}'", EXERCISE_VALUE_NAME, helper),
+    )
+}
+
+/// fish: exercise flags take no files, only the helper's names
+fn hook_fish(script: &str, flags: &[String], helper: &str) -> String {
+    let mut out = String::new();
+    for line in script.lines() {
+        out.push_str(line);
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let exercise_flag = words
+            .windows(2)
+            .any(|pair| pair[0] == "-l" && flags.iter().any(|flag| flag == pair[1]));
+        if line.starts_with("complete ") && exercise_flag {
+            out.push_str(&format!(" -f -a '({})'", helper));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueHint;
+    use std::fs;
+
+    fn command() -> Command {
+        Command::new("zenlings")
+            .arg(
+                Arg::new("exercise")
+                    .long("exercise")
+                    .value_name("EXERCISE")
+                    .help("Jump to an exercise"),
+            )
+            .arg(
+                Arg::new("path")
+                    .long("path")
+                    .global(true)
+                    .value_hint(ValueHint::AnyPath),
+            )
+            .subcommand(
+                Command::new("verify").about("Verify exercises").arg(
+                    Arg::new("to")
+                        .long("to")
+                        .visible_alias("until")
+                        .value_name("EXERCISE"),
+                ),
+            )
+            .subcommand(Command::new(EXERCISES_HELPER).hide(true))
+    }
+
+    #[test]
+    fn test_exercise_flags() {
+        assert_eq!(exercise_flags(&command()), ["exercise", "to", "until"]);
+    }
+
+    #[test]
+    fn test_generate_bash() {
+        let script = generate(Shell::Bash, &command());
+        assert!(script.contains("complete -F _zenlings"), "{script}");
+        let hooked = "COMPREPLY=($(compgen -W \"$(zenlings __complete-exercises 2>/dev/null)\" -- \"${cur}\"))";
+        for flag in ["--exercise)", "--to)", "--until)"] {
+            let case = script.split(flag).nth(1).unwrap_or_else(|| panic!("no {flag} case: {script}"));
+            assert!(case.lines().nth(1).unwrap().trim() == hooked, "{flag}: {case}");
+        }
+        // Other flags keep clap_complete's completion
+        let path = script.split("--path)").nth(1).unwrap();
+        assert!(!path.lines().nth(1).unwrap().contains(EXERCISES_HELPER));
+    }
+
+    #[test]
+    fn test_generate_zsh() {
+        let script = generate(Shell::Zsh, &command());
+        assert!(script.starts_with("#compdef zenlings\n"), "{script}");
+        assert!(script.contains(
+            "'--exercise=[Jump to an exercise]:EXERCISE:{compadd -- ${(f)\"$(zenlings __complete-exercises 2>/dev/null)\"}}'"
+        ));
+        assert!(!script.contains(":EXERCISE:_default"));
+    }
+
+    #[test]
+    fn test_generate_fish() {
+        let script = generate(Shell::Fish, &command());
+        let hook = " -f -a '(zenlings __complete-exercises 2>/dev/null)'";
+        let exercise = script.lines().find(|l| l.contains("-l exercise")).unwrap();
+        assert!(exercise.ends_with(hook), "{exercise}");
+        let to = script.lines().find(|l| l.contains("-l to")).unwrap();
+        assert!(to.ends_with(hook), "{to}");
+        assert!(script.lines().filter(|l| l.contains("-l path")).all(|l| !l.contains(hook)));
+    }
+
+    #[test]
+    fn test_exercise_names() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(exercise_names(dir.path()).is_empty());
+
+        fs::write(
+            dir.path().join("info.toml"),
+            "format_version = 1\n\n[[exercises]]\nname = \"intro1\"\ndir = \"00_intro\"\n\n[[exercises]]\nname = \"intro2\"\ndir = \"00_intro\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("exercises/00_intro")).unwrap();
+        fs::write(dir.path().join("exercises/00_intro/intro1.py"), "").unwrap();
+        fs::write(dir.path().join("exercises/00_intro/intro2.py"), "").unwrap();
+
+        let nested = dir.path().join("exercises/00_intro");
+        assert_eq!(exercise_names(&nested), vec!["intro1", "intro2"]);
+    }
+}
@@ -4,22 +4,60 @@
 //! and resolving exercise file paths.
 
 use anyhow::{Context, Result, bail};
-use serde::Deserialize;
+use regex::Regex;
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Component, Path, PathBuf};
+
+/// The info.toml `format_version` this build understands
+pub const SUPPORTED_FORMAT_VERSION: u32 = 1;
+
+/// Pipeline name for exercises that don't set one and whose pack has no template
+const DEFAULT_PIPELINE_NAME_TEMPLATE: &str = "{name}_pipeline";
+
+/// Solution lines kept for display; past this they're only counted
+pub const SOLUTION_PREVIEW_LINES: usize = 300;
 
 /// Root structure of info.toml
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Default)]
 pub struct InfoToml {
     pub format_version: u32,
     pub welcome_message: Option<String>,
     pub final_message: Option<String>,
+    /// Environment variables for every exercise run and ZenML CLI call
+    #[serde(default)]
+    pub env: HashMap<String, String>,
     #[serde(default)]
     pub exercises: Vec<ExerciseEntry>,
+    /// Find exercise files by glob instead of (or as well as) listing them
+    #[serde(default)]
+    pub discover: Option<Discover>,
+    /// Human-readable titles for exercise directories
+    #[serde(default)]
+    pub sections: Vec<Section>,
+    /// Pipeline name for exercises without one, e.g. `"ex_{name}"`
+    #[serde(default)]
+    pub pipeline_name_template: Option<String>,
+}
+
+/// `[[sections]]` entry of info.toml
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Section {
+    pub dir: String,
+    pub title: String,
+}
+
+/// `[discover]` section of info.toml
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct Discover {
+    /// Pattern relative to the pack root, e.g. `exercises/**/*.py`
+    pub glob: String,
 }
 
 /// Raw exercise entry from info.toml
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct ExerciseEntry {
     pub name: String,
     pub dir: String,
@@ -27,10 +65,74 @@ pub struct ExerciseEntry {
     pub hint: Option<String>,
     #[serde(default)]
     pub pipeline_name: Option<String>,
-    #[serde(default)]
-    pub verify_status: Option<String>,
+    /// Accepted run status(es): `"completed"` or `["completed", "cached"]`
+    #[serde(default, deserialize_with = "string_or_list")]
+    pub verify_status: Option<Vec<String>>,
     #[serde(default)]
     pub verify_step_count: Option<u64>,
+    /// Command (argv) to run instead of the ZenML status check
+    #[serde(default)]
+    pub verify_command: Option<Vec<String>>,
+    /// Regex the verify command's stdout must match
+    #[serde(default)]
+    pub verify_expect: Option<String>,
+    /// Tags the latest pipeline run must carry (e.g. set with `add_tags`)
+    #[serde(default)]
+    pub verify_tags: Vec<String>,
+    /// Text the exercise's own stdout must contain
+    #[serde(default)]
+    pub expect_stdout: Option<String>,
+    /// Treat `expect_stdout` as a regex instead of a substring
+    #[serde(default)]
+    pub expect_regex: bool,
+    /// Environment variables for this exercise (override the pack's `[env]`)
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Other files the learner edits for this exercise, relative to its directory
+    #[serde(default)]
+    pub watch: Vec<String>,
+    /// ZenML integrations the exercise needs installed (e.g. "mlflow")
+    #[serde(default)]
+    pub requires_integrations: Vec<String>,
+    /// Orchestrator flavor the exercise is written for (e.g. "local");
+    /// a different one only gets a warning
+    #[serde(default)]
+    pub required_orchestrator: Option<String>,
+    /// Stack the exercise is written for; a different one only gets a warning
+    #[serde(default)]
+    pub required_stack: Option<String>,
+    /// `"verified"` (the default) or `"manual"` for exercises the learner
+    /// marks done themselves
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// What the learner should achieve, always shown (unlike the hint)
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Dotted module run with `python -m` from the working dir instead of the
+    /// file (e.g. "exercises.05_dynamic.runner"), so relative imports work
+    #[serde(default)]
+    pub module: Option<String>,
+    /// Topics, for grouping and `--tag` filtering (unrelated to `verify_tags`)
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Deserialize a field given as either a single string or a list of strings
+fn string_or_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match StringOrList::deserialize(deserializer)? {
+        StringOrList::One(s) => Some(vec![s]),
+        StringOrList::Many(v) => Some(v),
+    })
 }
 
 /// Resolved exercise with full paths
@@ -47,35 +149,92 @@ pub struct Exercise {
 
     /// Pipeline name for verification (explicit or derived from name)
     pub pipeline_name: String,
-    /// Expected status for verification (default: "completed")
-    pub verify_status: String,
-    /// Optional: expected step count
+    /// Accepted statuses for verification (default: ["completed"])
+    pub verify_status: Vec<String>,
+    /// Optional: steps the run must have, when ZenML lists them
     pub verify_step_count: Option<u64>,
+    /// Optional: custom verification command replacing the ZenML status check
+    pub verify_command: Option<Vec<String>>,
+    /// Optional: regex the custom command's stdout must match
+    pub verify_expect: Option<String>,
+    /// Tags the passing run must have; empty skips the tag check
+    pub verify_tags: Vec<String>,
+    /// Optional: substring (or regex, with `expect_regex`) the script's stdout must contain
+    pub expect_stdout: Option<String>,
+    pub expect_regex: bool,
+    /// Extra environment variables for this exercise's runs
+    pub env: HashMap<String, String>,
+    /// Additional files whose changes re-run verification
+    pub watch: Vec<PathBuf>,
+    /// ZenML integrations checked for before verifying
+    pub requires_integrations: Vec<String>,
+    /// Expected orchestrator flavor and stack, warned about when different
+    pub required_orchestrator: Option<String>,
+    pub required_stack: Option<String>,
+    /// Never verified; the learner marks it done
+    pub manual: bool,
+    pub description: Option<String>,
+    /// Run as `python -m <module>`; `path` is then the module's file
+    pub module: Option<String>,
+    /// Topics the exercise covers, shown in the list
+    pub tags: Vec<String>,
 }
 
 impl Exercise {
-    /// Create a resolved Exercise from an ExerciseEntry and pack root
-    pub fn from_entry(entry: &ExerciseEntry, pack_root: &Path) -> Self {
-        let path = pack_root
-            .join("exercises")
-            .join(&entry.dir)
-            .join(format!("{}.py", &entry.name));
+    /// Whether the exercise is meant to produce a failed pipeline run, in
+    /// which case the script exiting non-zero is expected too
+    pub fn expects_failed_run(&self) -> bool {
+        self.verify_status.iter().any(|s| s == "failed")
+    }
 
-        let solution_path = pack_root
-            .join("solutions")
-            .join(&entry.dir)
-            .join(format!("{}.py", &entry.name));
+    /// Create a resolved Exercise from an ExerciseEntry and pack root
+    ///
+    /// `pipeline_name_template` names the pipeline when the entry doesn't;
+    /// its `{name}` is replaced with the exercise name.
+    pub fn from_entry(entry: &ExerciseEntry, pack_root: &Path, pipeline_name_template: Option<&str>) -> Self {
+        let (path, solution_path) = match &entry.module {
+            // The module's file, mirrored under solutions/ when it lives in exercises/
+            Some(module) => {
+                let rel = PathBuf::from(format!("{}.py", module.replace('.', "/")));
+                let solution = match rel.strip_prefix("exercises") {
+                    Ok(rest) => pack_root.join("solutions").join(rest),
+                    Err(_) => pack_root
+                        .join("solutions")
+                        .join(&entry.dir)
+                        .join(rel.file_name().unwrap_or_default()),
+                };
+                (pack_root.join(&rel), solution)
+            }
+            None => (
+                pack_root
+                    .join("exercises")
+                    .join(&entry.dir)
+                    .join(format!("{}.py", &entry.name)),
+                pack_root
+                    .join("solutions")
+                    .join(&entry.dir)
+                    .join(format!("{}.py", &entry.name)),
+            ),
+        };
 
         // Use explicit pipeline_name or derive from exercise name
-        let pipeline_name = entry
-            .pipeline_name
-            .clone()
-            .unwrap_or_else(|| format!("{}_pipeline", &entry.name));
+        let pipeline_name = entry.pipeline_name.clone().unwrap_or_else(|| {
+            pipeline_name_template
+                .unwrap_or(DEFAULT_PIPELINE_NAME_TEMPLATE)
+                .replace("{name}", &entry.name)
+        });
 
         let verify_status = entry
             .verify_status
             .clone()
-            .unwrap_or_else(|| "completed".to_string());
+            .unwrap_or_else(|| vec!["completed".to_string()]);
+
+        let exercise_dir = pack_root.join("exercises").join(&entry.dir);
+        let watch = entry
+            .watch
+            .iter()
+            .map(|rel| normalize_path(&exercise_dir.join(rel)))
+            .collect();
 
         Self {
             name: entry.name.clone(),
@@ -86,12 +245,180 @@ impl Exercise {
             pipeline_name,
             verify_status,
             verify_step_count: entry.verify_step_count,
+            verify_command: entry.verify_command.clone(),
+            verify_expect: entry.verify_expect.clone(),
+            verify_tags: entry.verify_tags.clone(),
+            expect_stdout: entry.expect_stdout.clone(),
+            expect_regex: entry.expect_regex,
+            env: entry.env.clone(),
+            watch,
+            requires_integrations: entry.requires_integrations.clone(),
+            required_orchestrator: entry.required_orchestrator.clone(),
+            required_stack: entry.required_stack.clone(),
+            manual: entry.kind.as_deref() == Some("manual"),
+            description: entry.description.clone(),
+            module: entry.module.clone(),
+            tags: entry.tags.clone(),
+        }
+    }
+
+    /// Whether the exercise carries `tag` (case-insensitively)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Module to run for the solution: the `solutions.` twin of an
+    /// `exercises.` module, or none (run the solution file directly)
+    pub fn solution_module(&self) -> Option<String> {
+        self.module
+            .as_deref()?
+            .strip_prefix("exercises.")
+            .map(|rest| format!("solutions.{}", rest))
+    }
+
+    /// Whether a change to `path` should re-run this exercise
+    pub fn watches(&self, path: &Path) -> bool {
+        path == self.path || self.watch.iter().any(|p| p == path)
+    }
+
+    /// Where `reset_to_solution` will keep the learner's version:
+    /// `<name>.py.bak`, or `<name>.py.bak.2`, `.bak.3`, ... if that's taken,
+    /// so an earlier backup is never overwritten
+    pub fn backup_path(&self) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(".bak");
+        let mut path = PathBuf::from(&name);
+        let mut n = 2;
+        while path.exists() {
+            path = PathBuf::from(format!("{}.{}", name.to_string_lossy(), n));
+            n += 1;
         }
+        path
+    }
+
+    /// Copy the solution over the exercise file, backing the learner's
+    /// version up to `backup_path` first; returns the backup's path
+    pub fn reset_to_solution(&self) -> Result<PathBuf> {
+        if !self.solution_path.is_file() {
+            bail!("No solution file for this exercise ({})", self.solution_path.display());
+        }
+        let backup = self.backup_path();
+        if self.path.exists() {
+            fs::copy(&self.path, &backup).with_context(|| {
+                format!("Failed to back up {} to {}", self.path.display(), backup.display())
+            })?;
+        }
+        fs::copy(&self.solution_path, &self.path)
+            .with_context(|| format!("Failed to copy the solution to {}", self.path.display()))?;
+        Ok(backup)
+    }
+
+    /// The solution for display: at most `SOLUTION_PREVIEW_LINES` lines,
+    /// then a note saying how many were left out
+    pub fn read_solution(&self) -> io::Result<String> {
+        let (text, more) = read_head(&self.solution_path, SOLUTION_PREVIEW_LINES)?;
+        if more == 0 {
+            return Ok(text);
+        }
+        let noun = if more == 1 { "line" } else { "lines" };
+        Ok(format!("{}\n… file has {} more {}", text.trim_end(), more, noun))
     }
 
     /// Get the display path relative to exercises/
     pub fn display_path(&self) -> String {
-        format!("{}/{}.py", self.dir, self.name)
+        match &self.module {
+            Some(module) => {
+                let rel = module.replace('.', "/");
+                format!("{}.py", rel.strip_prefix("exercises/").unwrap_or(&rel))
+            }
+            None => format!("{}/{}.py", self.dir, self.name),
+        }
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+///
+/// Watch events carry plain paths, so `exercises/01/../shared.py` has to be
+/// spelled `exercises/shared.py` to compare equal.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Longest line `read_head` keeps; the rest of a longer one is cut to `…`
+const MAX_LINE_BYTES: usize = 4096;
+
+/// The first `max_lines` lines of a file, and how many lines follow them
+///
+/// Lines past the cutoff are counted a buffer at a time and never kept, and
+/// kept lines are cut at `MAX_LINE_BYTES`, so a huge file costs one scan
+/// rather than its size in memory. Invalid UTF-8 is replaced rather than
+/// failing the read.
+pub fn read_head(path: &Path, max_lines: usize) -> io::Result<(String, usize)> {
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    let mut text = String::new();
+    let mut line = Vec::new();
+    for _ in 0..max_lines {
+        line.clear();
+        let read = (&mut reader).take(MAX_LINE_BYTES as u64).read_until(b'\n', &mut line)?;
+        if read == 0 {
+            return Ok((text, 0));
+        }
+        text.push_str(&String::from_utf8_lossy(&line));
+        if read == MAX_LINE_BYTES && !line.ends_with(b"\n") {
+            if skip_line(&mut reader)? > 0 {
+                text.push('…');
+            }
+            text.push('\n');
+        }
+    }
+
+    let mut more = 0;
+    let mut last = b'\n';
+    loop {
+        let buf = reader.fill_buf()?;
+        let Some(&end) = buf.last() else { break };
+        more += buf.iter().filter(|&&b| b == b'\n').count();
+        last = end;
+        let len = buf.len();
+        reader.consume(len);
+    }
+    // A final line without a newline still counts
+    if last != b'\n' {
+        more += 1;
+    }
+    Ok((text, more))
+}
+
+/// Consume the rest of the current line, through its newline; returns how
+/// many bytes came before the newline
+fn skip_line(reader: &mut impl BufRead) -> io::Result<usize> {
+    let mut skipped = 0;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(skipped);
+        }
+        match buf.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(skipped + end);
+            }
+            None => {
+                let len = buf.len();
+                skipped += len;
+                reader.consume(len);
+            }
+        }
     }
 }
 
@@ -103,22 +430,77 @@ pub fn load_info_toml(info_path: &Path) -> Result<InfoToml> {
     let info: InfoToml = toml::from_str(&content)
         .with_context(|| "Failed to parse info.toml")?;
 
-    if info.format_version != 1 {
+    check_format_version(info.format_version)?;
+    if let Some(template) = &info.pipeline_name_template {
+        check_pipeline_name_template(template)?;
+    }
+
+    Ok(info)
+}
+
+/// Reject a `pipeline_name_template` that would give every exercise the same pipeline
+fn check_pipeline_name_template(template: &str) -> Result<()> {
+    if !template.contains("{name}") {
         bail!(
-            "Unsupported info.toml format version: {} (expected 1)",
-            info.format_version
+            "pipeline_name_template '{}' in info.toml must contain {{name}} (e.g. \"ex_{{name}}\")",
+            template
         );
     }
+    Ok(())
+}
 
-    Ok(info)
+/// Reject packs written for a different info.toml format
+fn check_format_version(version: u32) -> Result<()> {
+    let zenlings = concat!("zenlings ", env!("CARGO_PKG_VERSION"));
+    if version > SUPPORTED_FORMAT_VERSION {
+        bail!(
+            "This pack requires a newer zenlings (pack format v{}, {} supports v{}) — please upgrade",
+            version,
+            zenlings,
+            SUPPORTED_FORMAT_VERSION
+        );
+    }
+    if version < SUPPORTED_FORMAT_VERSION {
+        bail!(
+            "This pack is too old (pack format v{}, {} supports v{}) — \
+             its info.toml needs updating",
+            version,
+            zenlings,
+            SUPPORTED_FORMAT_VERSION
+        );
+    }
+    Ok(())
+}
+
+/// Check that the pack has a non-empty exercises/ directory
+pub fn check_exercises_dir(pack_root: &Path) -> Result<()> {
+    let exercises_dir = pack_root.join("exercises");
+
+    if !exercises_dir.is_dir() {
+        bail!("exercises/ directory not found under {:?}", pack_root);
+    }
+
+    let is_empty = fs::read_dir(&exercises_dir)
+        .with_context(|| format!("Failed to read {:?}", exercises_dir))?
+        .next()
+        .is_none();
+    if is_empty {
+        bail!("exercises/ directory under {:?} is empty", pack_root);
+    }
+
+    Ok(())
 }
 
 /// Load all exercises from info.toml with resolved paths
 pub fn load_exercises(pack_root: &Path, info: &InfoToml) -> Result<Vec<Exercise>> {
-    let mut exercises = Vec::with_capacity(info.exercises.len());
+    let entries = match &info.discover {
+        Some(discover) => discover_entries(pack_root, &discover.glob, &info.exercises)?,
+        None => info.exercises.clone(),
+    };
+    let mut exercises = Vec::with_capacity(entries.len());
 
-    for entry in &info.exercises {
-        let exercise = Exercise::from_entry(entry, pack_root);
+    for entry in &entries {
+        let exercise = Exercise::from_entry(entry, pack_root, info.pipeline_name_template.as_deref());
 
         // Verify the exercise file exists
         if !exercise.path.exists() {
@@ -129,6 +511,37 @@ pub fn load_exercises(pack_root: &Path, info: &InfoToml) -> Result<Vec<Exercise>
             );
         }
 
+        if let Some(kind) = entry.kind.as_deref().filter(|k| !["verified", "manual"].contains(k)) {
+            bail!(
+                "Exercise '{}' has unknown kind '{}' (expected \"verified\" or \"manual\")",
+                entry.name,
+                kind
+            );
+        }
+        if exercise.verify_status.is_empty() {
+            bail!("Exercise '{}' has an empty verify_status list", entry.name);
+        }
+        if matches!(&exercise.verify_command, Some(cmd) if cmd.is_empty()) {
+            bail!("Exercise '{}' has an empty verify_command", entry.name);
+        }
+        if let Some(missing) = exercise.watch.iter().find(|p| !p.is_file()) {
+            bail!(
+                "Watched file not found: {:?} (listed for exercise '{}')",
+                missing,
+                entry.name
+            );
+        }
+        if let Some(ref pattern) = exercise.verify_expect {
+            Regex::new(pattern).with_context(|| {
+                format!("Invalid verify_expect regex for exercise '{}'", entry.name)
+            })?;
+        }
+        if let (Some(pattern), true) = (&exercise.expect_stdout, exercise.expect_regex) {
+            Regex::new(pattern).with_context(|| {
+                format!("Invalid expect_stdout regex for exercise '{}'", entry.name)
+            })?;
+        }
+
         exercises.push(exercise);
     }
 
@@ -139,7 +552,166 @@ pub fn load_exercises(pack_root: &Path, info: &InfoToml) -> Result<Vec<Exercise>
     Ok(exercises)
 }
 
+/// Exercise entries for files matching `pattern`, merged with `listed`
+///
+/// Each matched `exercises/<dir>/<name>.py` becomes an entry with default
+/// settings. Listed entries win over discovered ones with the same name, and
+/// the result is sorted by path.
+fn discover_entries(
+    pack_root: &Path,
+    pattern: &str,
+    listed: &[ExerciseEntry],
+) -> Result<Vec<ExerciseEntry>> {
+    let pattern: Vec<&str> = pattern.split('/').filter(|p| !p.is_empty()).collect();
+    // Only walk below the pattern's literal prefix
+    let literal = pattern
+        .iter()
+        .take_while(|p| !p.contains(['*', '?']))
+        .count();
+    let base: PathBuf = pattern[..literal].iter().collect();
+
+    let mut files = Vec::new();
+    collect_files(&pack_root.join(&base), &base, &mut files)?;
+
+    let mut by_name: HashMap<String, ExerciseEntry> = HashMap::new();
+    for rel in files {
+        let parts: Vec<&str> = rel.split('/').collect();
+        if !glob_match(&pattern, &parts) {
+            continue;
+        }
+        let entry = match parts.as_slice() {
+            ["exercises", dirs @ .., file] if !dirs.is_empty() && file.ends_with(".py") => {
+                ExerciseEntry {
+                    name: file.trim_end_matches(".py").to_string(),
+                    dir: dirs.join("/"),
+                    ..Default::default()
+                }
+            }
+            _ => bail!(
+                "Discovered file {} is not of the form exercises/<dir>/<name>.py",
+                rel
+            ),
+        };
+        if let Some(other) = by_name.get(&entry.name) {
+            bail!(
+                "Discovered exercises {}/{}.py and {}/{}.py share the name '{}'",
+                other.dir,
+                other.name,
+                entry.dir,
+                entry.name,
+                entry.name
+            );
+        }
+        by_name.insert(entry.name.clone(), entry);
+    }
+
+    for entry in listed {
+        by_name.insert(entry.name.clone(), entry.clone());
+    }
+
+    let mut entries: Vec<ExerciseEntry> = by_name.into_values().collect();
+    entries.sort_by(|a, b| (&a.dir, &a.name).cmp(&(&b.dir, &b.name)));
+    Ok(entries)
+}
+
+/// Collect files under `dir` as `/`-separated paths starting with `rel`
+fn collect_files(dir: &Path, rel: &Path, files: &mut Vec<String>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let entries = fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))?;
+    for entry in entries {
+        let entry = entry.with_context(|| format!("Failed to read {:?}", dir))?;
+        let rel = rel.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            collect_files(&entry.path(), &rel, files)?;
+        } else {
+            let parts: Vec<String> = rel
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            files.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// Match path segments against glob segments
+///
+/// `**` matches any number of directories; within a segment `*` matches any
+/// run of characters and `?` a single one.
+fn glob_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_match(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                segment_match(segment.as_bytes(), name.as_bytes()) && glob_match(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn segment_match(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| segment_match(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && segment_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && segment_match(rest, &name[1..]),
+    }
+}
+
+/// Edit distance between two strings (insertions, deletions, substitutions)
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b_chars.len()).collect();
+    let mut curr = vec![0; b_chars.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b_chars.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b_chars.len()]
+}
+
+/// Names within a small edit distance of `query`, closest first
+pub fn closest_names<'a>(query: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    const MAX_DISTANCE: usize = 2;
+
+    let mut scored: Vec<(usize, &str)> = names
+        .into_iter()
+        .map(|name| (levenshtein(query, name), name))
+        .filter(|(distance, _)| *distance <= MAX_DISTANCE)
+        .collect();
+    scored.sort();
+
+    // Keep only the best-scoring candidates
+    let best = scored.first().map(|(d, _)| *d);
+    scored
+        .into_iter()
+        .take_while(|(d, _)| Some(*d) == best)
+        .map(|(_, name)| name)
+        .collect()
+}
+
 /// Find the pack root by searching for info.toml in parent directories
+/// The pack root given with `--path`, or else found from `start`, made
+/// absolute with symlinks resolved so it matches the paths file watchers
+/// report (e.g. macOS FSEvents); left as is if it can't be resolved
+pub fn resolve_pack_root(path: Option<&Path>, start: &Path) -> Result<PathBuf> {
+    let root = match path {
+        Some(path) => path.to_path_buf(),
+        None => find_pack_root(start)?,
+    };
+    Ok(fs::canonicalize(&root).unwrap_or(root))
+}
+
 pub fn find_pack_root(start: &Path) -> Result<PathBuf> {
     let mut current = start.to_path_buf();
 
@@ -177,15 +749,377 @@ mod tests {
         let entry = ExerciseEntry {
             name: "load1".to_string(),
             dir: "01_loading".to_string(),
-            hint: None,
-            pipeline_name: None,
-            verify_status: None,
-            verify_step_count: None,
+            ..Default::default()
         };
 
-        let exercise = Exercise::from_entry(&entry, Path::new("/tmp/zenlings"));
+        let exercise = Exercise::from_entry(&entry, Path::new("/tmp/zenlings"), None);
         assert_eq!(exercise.display_path(), "01_loading/load1.py");
         assert_eq!(exercise.pipeline_name, "load1_pipeline");
-        assert_eq!(exercise.verify_status, "completed");
+        assert_eq!(exercise.verify_status, vec!["completed"]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let pattern = ["exercises", "**", "*.py"];
+        assert!(glob_match(&pattern, &["exercises", "01_a", "x.py"]));
+        assert!(glob_match(&pattern, &["exercises", "01_a", "deep", "x.py"]));
+        assert!(glob_match(&pattern, &["exercises", "x.py"]));
+        assert!(!glob_match(&pattern, &["exercises", "01_a", "x.txt"]));
+        assert!(!glob_match(&pattern, &["solutions", "01_a", "x.py"]));
+        assert!(glob_match(&["exercises", "0?_*", "load*.py"], &["exercises", "01_a", "load2.py"]));
+    }
+
+    fn discover_pack(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_discover_populates_sorted_entries() {
+        let pack = discover_pack(&[
+            "exercises/02_steps/steps1.py",
+            "exercises/01_intro/intro2.py",
+            "exercises/01_intro/intro1.py",
+            "exercises/01_intro/README.md",
+        ]);
+        let info: InfoToml = toml::from_str(
+            r#"
+            format_version = 1
+
+            [discover]
+            glob = "exercises/**/*.py"
+            "#,
+        )
+        .unwrap();
+
+        let exercises = load_exercises(pack.path(), &info).unwrap();
+        let paths: Vec<String> = exercises.iter().map(|e| e.display_path()).collect();
+        assert_eq!(
+            paths,
+            ["01_intro/intro1.py", "01_intro/intro2.py", "02_steps/steps1.py"]
+        );
+        assert_eq!(exercises[2].pipeline_name, "steps1_pipeline");
+    }
+
+    #[test]
+    fn test_discover_keeps_listed_settings() {
+        let pack = discover_pack(&[
+            "exercises/01_intro/intro1.py",
+            "exercises/01_intro/intro2.py",
+        ]);
+        let info: InfoToml = toml::from_str(
+            r#"
+            format_version = 1
+
+            [discover]
+            glob = "exercises/**/*.py"
+
+            [[exercises]]
+            name = "intro2"
+            dir = "01_intro"
+            hint = "Look closer"
+            pipeline_name = "custom"
+            "#,
+        )
+        .unwrap();
+
+        let exercises = load_exercises(pack.path(), &info).unwrap();
+        assert_eq!(exercises.len(), 2);
+        assert_eq!(exercises[0].hint, None);
+        assert_eq!(exercises[1].hint.as_deref(), Some("Look closer"));
+        assert_eq!(exercises[1].pipeline_name, "custom");
+    }
+
+    #[test]
+    fn test_discover_rejects_duplicate_names() {
+        let pack = discover_pack(&["exercises/01_a/same.py", "exercises/02_b/same.py"]);
+        let info = InfoToml {
+            format_version: 1,
+            discover: Some(Discover {
+                glob: "exercises/**/*.py".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let err = load_exercises(pack.path(), &info).unwrap_err();
+        assert!(err.to_string().contains("share the name 'same'"), "{err}");
+    }
+
+    #[test]
+    fn test_exercise_kind() {
+        let pack = discover_pack(&["exercises/00_intro/a.py", "exercises/00_intro/b.py"]);
+        let entry = |name: &str, kind: Option<&str>| ExerciseEntry {
+            name: name.to_string(),
+            dir: "00_intro".to_string(),
+            kind: kind.map(|k| k.to_string()),
+            ..Default::default()
+        };
+        let mut info = InfoToml {
+            format_version: 1,
+            exercises: vec![entry("a", Some("manual")), entry("b", None)],
+            ..Default::default()
+        };
+
+        let exercises = load_exercises(pack.path(), &info).unwrap();
+        assert!(exercises[0].manual);
+        assert!(!exercises[1].manual);
+
+        info.exercises[1].kind = Some("quiz".to_string());
+        let err = load_exercises(pack.path(), &info).unwrap_err();
+        assert!(err.to_string().contains("unknown kind 'quiz'"), "{err}");
+    }
+
+    #[test]
+    fn test_reset_to_solution() {
+        let pack = discover_pack(&["exercises/00_intro/intro1.py"]);
+        let entry = ExerciseEntry {
+            name: "intro1".to_string(),
+            dir: "00_intro".to_string(),
+            ..Default::default()
+        };
+        let exercise = Exercise::from_entry(&entry, pack.path(), None);
+        fs::write(&exercise.path, "mangled").unwrap();
+
+        let err = exercise.reset_to_solution().unwrap_err();
+        assert!(err.to_string().starts_with("No solution file"), "{err}");
+        assert_eq!(fs::read_to_string(&exercise.path).unwrap(), "mangled");
+
+        fs::create_dir_all(exercise.solution_path.parent().unwrap()).unwrap();
+        fs::write(&exercise.solution_path, "working").unwrap();
+        let backup = exercise.reset_to_solution().unwrap();
+        assert_eq!(backup, pack.path().join("exercises/00_intro/intro1.py.bak"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "mangled");
+        assert_eq!(fs::read_to_string(&exercise.path).unwrap(), "working");
+
+        // A second reset keeps the first backup
+        fs::write(&exercise.path, "mangled again").unwrap();
+        let second = exercise.reset_to_solution().unwrap();
+        assert_eq!(second, pack.path().join("exercises/00_intro/intro1.py.bak.2"));
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "mangled");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "mangled again");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_pack_root_follows_symlinks() {
+        let pack = discover_pack(&["exercises/00_intro/intro1.py"]);
+        fs::write(pack.path().join("info.toml"), "").unwrap();
+        let real = fs::canonicalize(pack.path()).unwrap();
+        let links = tempfile::tempdir().unwrap();
+        let link = links.path().join("pack");
+        std::os::unix::fs::symlink(pack.path(), &link).unwrap();
+
+        assert_eq!(resolve_pack_root(Some(&link), Path::new("/")).unwrap(), real);
+        assert_eq!(resolve_pack_root(None, &link.join("exercises")).unwrap(), real);
+        let missing = links.path().join("missing");
+        assert_eq!(resolve_pack_root(Some(&missing), Path::new("/")).unwrap(), missing);
+    }
+
+    #[test]
+    fn test_read_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("big.py");
+        let content: String = (1..=100_000).map(|i| format!("x{} = {}\n", i, i)).collect();
+        fs::write(&path, content + "tail").unwrap();
+
+        let (text, more) = read_head(&path, 3).unwrap();
+        assert_eq!(text, "x1 = 1\nx2 = 2\nx3 = 3\n");
+        assert_eq!(more, 100_000 - 3 + 1);
+
+        fs::write(&path, "a\nb\n").unwrap();
+        assert_eq!(read_head(&path, 2).unwrap(), ("a\nb\n".to_string(), 0));
+        assert_eq!(read_head(&path, 5).unwrap(), ("a\nb\n".to_string(), 0));
+        assert_eq!(read_head(&path, 1).unwrap(), ("a\n".to_string(), 1));
+
+        // A file with no newlines is cut, not read into memory whole
+        let long = "x".repeat(MAX_LINE_BYTES);
+        fs::write(&path, "y".repeat(10 * MAX_LINE_BYTES)).unwrap();
+        let (text, more) = read_head(&path, 3).unwrap();
+        assert_eq!(text, format!("{}…\n", "y".repeat(MAX_LINE_BYTES)));
+        assert_eq!(more, 0);
+
+        // A line of exactly the limit isn't marked as cut
+        fs::write(&path, format!("{}\nb\n", long)).unwrap();
+        assert_eq!(read_head(&path, 5).unwrap(), (format!("{}\nb\n", long), 0));
+    }
+
+    #[test]
+    fn test_read_solution_truncates() {
+        let pack = discover_pack(&["exercises/00_intro/intro1.py"]);
+        let entry = ExerciseEntry {
+            name: "intro1".to_string(),
+            dir: "00_intro".to_string(),
+            ..Default::default()
+        };
+        let exercise = Exercise::from_entry(&entry, pack.path(), None);
+        assert!(exercise.read_solution().is_err());
+
+        fs::create_dir_all(exercise.solution_path.parent().unwrap()).unwrap();
+        fs::write(&exercise.solution_path, "print('hi')\n").unwrap();
+        assert_eq!(exercise.read_solution().unwrap(), "print('hi')\n");
+
+        let long = "pass\n".repeat(SOLUTION_PREVIEW_LINES + 2);
+        fs::write(&exercise.solution_path, long).unwrap();
+        let shown = exercise.read_solution().unwrap();
+        assert_eq!(shown.lines().count(), SOLUTION_PREVIEW_LINES + 1);
+        assert!(shown.ends_with("pass\n… file has 2 more lines"), "{shown}");
+    }
+
+    #[test]
+    fn test_module_exercise_paths() {
+        let entry = ExerciseEntry {
+            name: "dyn2".to_string(),
+            dir: "05_dynamic".to_string(),
+            module: Some("exercises.05_dynamic.dyn2.runner".to_string()),
+            ..Default::default()
+        };
+
+        let exercise = Exercise::from_entry(&entry, Path::new("/pack"), None);
+        assert_eq!(exercise.path, PathBuf::from("/pack/exercises/05_dynamic/dyn2/runner.py"));
+        assert_eq!(exercise.solution_path, PathBuf::from("/pack/solutions/05_dynamic/dyn2/runner.py"));
+        assert_eq!(exercise.display_path(), "05_dynamic/dyn2/runner.py");
+        assert_eq!(exercise.solution_module().as_deref(), Some("solutions.05_dynamic.dyn2.runner"));
+        assert!(exercise.watches(Path::new("/pack/exercises/05_dynamic/dyn2/runner.py")));
+
+        let plain = Exercise::from_entry(&ExerciseEntry { module: None, ..entry }, Path::new("/pack"), None);
+        assert_eq!(plain.solution_module(), None);
+    }
+
+    #[test]
+    fn test_watch_paths_resolve_from_exercise_dir() {
+        let entry = ExerciseEntry {
+            name: "dyn1".to_string(),
+            dir: "05_dynamic".to_string(),
+            watch: vec!["steps.py".to_string(), "../shared/util.py".to_string()],
+            ..Default::default()
+        };
+
+        let exercise = Exercise::from_entry(&entry, Path::new("/tmp/zenlings"), None);
+        assert_eq!(
+            exercise.watch,
+            [
+                PathBuf::from("/tmp/zenlings/exercises/05_dynamic/steps.py"),
+                PathBuf::from("/tmp/zenlings/exercises/shared/util.py"),
+            ]
+        );
+        assert!(exercise.watches(Path::new("/tmp/zenlings/exercises/05_dynamic/dyn1.py")));
+        assert!(exercise.watches(Path::new("/tmp/zenlings/exercises/shared/util.py")));
+        assert!(!exercise.watches(Path::new("/tmp/zenlings/exercises/05_dynamic/other.py")));
+    }
+
+    #[test]
+    fn test_pipeline_name_template() {
+        let info: InfoToml = toml::from_str(
+            r#"
+            format_version = 1
+            pipeline_name_template = "ex_{name}"
+
+            [[exercises]]
+            name = "load1"
+            dir = "01_loading"
+
+            [[exercises]]
+            name = "load2"
+            dir = "01_loading"
+            pipeline_name = "custom_pipeline"
+            "#,
+        )
+        .unwrap();
+        let template = info.pipeline_name_template.as_deref();
+        let templated = Exercise::from_entry(&info.exercises[0], Path::new("/pack"), template);
+        assert_eq!(templated.pipeline_name, "ex_load1");
+        // An explicit pipeline_name still wins
+        let explicit = Exercise::from_entry(&info.exercises[1], Path::new("/pack"), template);
+        assert_eq!(explicit.pipeline_name, "custom_pipeline");
+
+        assert!(check_pipeline_name_template("ex_{name}").is_ok());
+        let err = check_pipeline_name_template("shared_pipeline").unwrap_err().to_string();
+        assert!(err.contains("must contain {name}"), "{err}");
+    }
+
+    #[test]
+    fn test_format_version_errors() {
+        assert!(check_format_version(SUPPORTED_FORMAT_VERSION).is_ok());
+
+        let newer = check_format_version(2).unwrap_err().to_string();
+        assert!(newer.contains("requires a newer zenlings"), "{newer}");
+        assert!(newer.contains("pack format v2"), "{newer}");
+        assert!(newer.contains(env!("CARGO_PKG_VERSION")), "{newer}");
+
+        let older = check_format_version(0).unwrap_err().to_string();
+        assert!(older.contains("too old"), "{older}");
+    }
+
+    #[test]
+    fn test_verify_status_string_or_list() {
+        let info: InfoToml = toml::from_str(
+            r#"
+            format_version = 1
+
+            [[exercises]]
+            name = "a"
+            dir = "00_intro"
+            verify_status = "failed"
+
+            [[exercises]]
+            name = "b"
+            dir = "00_intro"
+            verify_status = ["completed", "cached"]
+
+            [[exercises]]
+            name = "c"
+            dir = "00_intro"
+            "#,
+        )
+        .unwrap();
+
+        let statuses: Vec<Option<Vec<String>>> =
+            info.exercises.iter().map(|e| e.verify_status.clone()).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Some(vec!["failed".to_string()]),
+                Some(vec!["completed".to_string(), "cached".to_string()]),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_exercises_dir() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = check_exercises_dir(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("exercises/ directory not found"), "{}", err);
+
+        fs::create_dir(dir.path().join("exercises")).unwrap();
+        let err = check_exercises_dir(dir.path()).unwrap_err().to_string();
+        assert!(err.contains("is empty"), "{}", err);
+
+        fs::create_dir(dir.path().join("exercises/00_intro")).unwrap();
+        assert!(check_exercises_dir(dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("load1", "load1"), 0);
+        assert_eq!(levenshtein("lod1", "load1"), 1);
+        assert_eq!(levenshtein("laod1", "load1"), 2);
+        assert_eq!(levenshtein("", "map"), 3);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_closest_names() {
+        let names = ["load1", "load2", "map1", "product1"];
+
+        assert_eq!(closest_names("lod1", names), vec!["load1"]);
+        assert_eq!(closest_names("load", names), vec!["load1", "load2"]);
+        assert!(closest_names("capstone", names).is_empty());
     }
 }
@@ -0,0 +1,195 @@
+//! Progress export to a shareable summary file.
+//!
+//! Backs the `zenlings export` subcommand, which reads the progress file
+//! and info.toml without entering the TUI.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::app_state::AppState;
+use crate::hints::hints_used_count;
+use crate::timefmt::{format_duration, format_timestamp};
+
+/// Output format for `zenlings export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    /// Default output file name for this format
+    pub fn default_filename(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "progress.md",
+            ExportFormat::Json => "progress.json",
+        }
+    }
+}
+
+/// Snapshot of a learner's progress through a pack
+#[derive(Debug, Serialize)]
+pub struct ProgressSummary {
+    pub total: usize,
+    pub completed: usize,
+    /// Unix seconds, as stored in the progress file
+    pub started_at: Option<String>,
+    /// Last activity, once every exercise is completed
+    pub finished_at: Option<String>,
+    pub hints_used: u32,
+    pub time_spent_secs: u64,
+    pub exercises: Vec<ExerciseSummary>,
+}
+
+/// Per-exercise line of a `ProgressSummary`
+#[derive(Debug, Serialize)]
+pub struct ExerciseSummary {
+    pub name: String,
+    pub path: String,
+    pub completed: bool,
+    pub hints_used: u32,
+    pub time_spent_secs: u64,
+}
+
+impl ProgressSummary {
+    /// Build a summary from loaded application state
+    pub fn from_state(state: &AppState) -> Self {
+        let exercises: Vec<ExerciseSummary> = state
+            .exercises
+            .iter()
+            .map(|exercise| ExerciseSummary {
+                name: exercise.name.clone(),
+                path: exercise.display_path(),
+                completed: state.is_completed(&exercise.name),
+                hints_used: hints_used_count(&state.progress, &exercise.name),
+                time_spent_secs: state.time_spent_secs(&exercise.name),
+            })
+            .collect();
+
+        let finished_at = if state.all_completed() {
+            state.progress.last_activity.clone()
+        } else {
+            None
+        };
+
+        Self {
+            total: state.total_count(),
+            completed: state.completed_count(),
+            started_at: state.progress.started_at.clone(),
+            finished_at,
+            hints_used: exercises.iter().map(|e| e.hints_used).sum(),
+            time_spent_secs: exercises.iter().map(|e| e.time_spent_secs).sum(),
+            exercises,
+        }
+    }
+}
+
+/// Render a summary as a Markdown checklist
+pub fn render_markdown(summary: &ProgressSummary) -> String {
+    let percent = (summary.completed * 100).checked_div(summary.total).unwrap_or(0);
+    let timestamp = |ts: &Option<String>| {
+        ts.as_deref()
+            .map(format_timestamp)
+            .unwrap_or_else(|| "—".to_string())
+    };
+
+    let mut out = String::new();
+    out.push_str("# Zenlings Progress\n\n");
+    out.push_str(&format!(
+        "**{} / {} exercises completed** ({}%)\n\n",
+        summary.completed, summary.total, percent
+    ));
+    out.push_str(&format!("- Started: {}\n", timestamp(&summary.started_at)));
+    out.push_str(&format!("- Finished: {}\n", timestamp(&summary.finished_at)));
+    out.push_str(&format!("- Time spent: {}\n", format_duration(summary.time_spent_secs)));
+    out.push_str(&format!("- Hints used: {}\n", summary.hints_used));
+    out.push_str("\n## Exercises\n\n");
+
+    for exercise in &summary.exercises {
+        let icon = if exercise.completed { "✅" } else { "⬜" };
+        let mut details = Vec::new();
+        if exercise.time_spent_secs > 0 {
+            details.push(format_duration(exercise.time_spent_secs));
+        }
+        match exercise.hints_used {
+            0 => {}
+            1 => details.push("1 hint".to_string()),
+            n => details.push(format!("{} hints", n)),
+        }
+
+        out.push_str(&format!("- {} `{}`", icon, exercise.path));
+        if !details.is_empty() {
+            out.push_str(&format!(" — {}", details.join(", ")));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Write a progress summary for `state` to `output`
+pub fn write_export(state: &AppState, output: &Path, format: ExportFormat) -> Result<()> {
+    let summary = ProgressSummary::from_state(state);
+    let content = match format {
+        ExportFormat::Markdown => render_markdown(&summary),
+        ExportFormat::Json => serde_json::to_string_pretty(&summary)
+            .context("Failed to serialize progress summary")?,
+    };
+
+    fs::write(output, content)
+        .with_context(|| format!("Failed to write progress summary: {:?}", output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_summary() -> ProgressSummary {
+        ProgressSummary {
+            total: 2,
+            completed: 1,
+            started_at: Some("0".to_string()),
+            finished_at: None,
+            hints_used: 2,
+            time_spent_secs: 200,
+            exercises: vec![
+                ExerciseSummary {
+                    name: "intro1".to_string(),
+                    path: "00_intro/intro1.py".to_string(),
+                    completed: true,
+                    hints_used: 2,
+                    time_spent_secs: 200,
+                },
+                ExerciseSummary {
+                    name: "intro2".to_string(),
+                    path: "00_intro/intro2.py".to_string(),
+                    completed: false,
+                    hints_used: 0,
+                    time_spent_secs: 0,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let md = render_markdown(&sample_summary());
+
+        assert!(md.contains("**1 / 2 exercises completed** (50%)"));
+        assert!(md.contains("- Started: 1970-01-01 00:00 UTC\n"));
+        assert!(md.contains("- Finished: —\n"));
+        assert!(md.contains("- ✅ `00_intro/intro1.py` — 3m 20s, 2 hints\n"));
+        assert!(md.contains("- ⬜ `00_intro/intro2.py`\n"));
+    }
+
+    #[test]
+    fn test_summary_serializes_to_json() {
+        let json = serde_json::to_value(sample_summary()).unwrap();
+
+        assert_eq!(json["completed"], 1);
+        assert_eq!(json["exercises"][0]["name"], "intro1");
+        assert_eq!(json["exercises"][1]["completed"], false);
+    }
+}
@@ -0,0 +1,202 @@
+//! `zenlings fetch`: unpack a pack distributed as a `.tar.gz`.
+//!
+//! Like the clipboard and the `o` action, this hands the work to platform
+//! commands (`tar`, and `curl` for URLs) instead of pulling in archive and
+//! HTTP crates. Downloading is behind the `net` feature.
+
+use anyhow::{Context, Result, bail};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use crate::exercise;
+
+/// Extract the pack at `source` (a `.tar.gz` path, or an http(s) URL to one)
+/// into `pack_dir`, or a directory named after the archive
+///
+/// Returns the unpacked pack's root (the directory holding `info.toml`).
+pub fn fetch_pack(source: &str, pack_dir: Option<&Path>) -> Result<PathBuf> {
+    let dest = match pack_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => PathBuf::from(default_dir_name(source)),
+    };
+    if fs::read_dir(&dest).is_ok_and(|mut entries| entries.next().is_some()) {
+        bail!("{} already exists and isn't empty; pick another with --pack-dir", dest.display());
+    }
+
+    if is_url(source) {
+        let archive = std::env::temp_dir().join(format!("zenlings-fetch-{}.tar.gz", std::process::id()));
+        let result = download(source, &archive).and_then(|()| extract(&archive, &dest));
+        let _ = fs::remove_file(&archive);
+        result?;
+    } else {
+        extract(Path::new(source), &dest)?;
+    }
+
+    find_extracted_root(&dest)
+}
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// Directory to unpack into when none is given, e.g. "zenml-pack" for
+/// ".../zenml-pack.tar.gz?raw=1"
+fn default_dir_name(source: &str) -> String {
+    let path = source.split(['?', '#']).next().unwrap_or(source);
+    let file = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let stem = [".tar.gz", ".tgz"]
+        .iter()
+        .find_map(|ext| file.strip_suffix(ext))
+        .unwrap_or(file);
+    if stem.is_empty() {
+        "zenlings-pack".to_string()
+    } else {
+        stem.to_string()
+    }
+}
+
+#[cfg(feature = "net")]
+fn download(url: &str, archive: &Path) -> Result<()> {
+    let status = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--output"])
+        .arg(archive)
+        .arg(url)
+        .status()
+        .context("Failed to run curl (is it installed?)")?;
+    if !status.success() {
+        bail!("Downloading {} failed", url);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "net"))]
+fn download(url: &str, _archive: &Path) -> Result<()> {
+    bail!("This zenlings was built without the `net` feature; download {} and pass the file instead", url)
+}
+
+/// Check the archive's entries, then unpack it into `dest`
+fn extract(archive: &Path, dest: &Path) -> Result<()> {
+    let names = tar_listing(archive, "-tzf")?;
+    if let Some(bad) = names.iter().find(|name| !is_safe_entry(name)) {
+        bail!("Refusing to extract {}: entry '{}' points outside the pack", archive.display(), bad);
+    }
+    // Links could point anywhere once extracted; packs don't need them
+    if tar_listing(archive, "-tvzf")?.iter().any(|line| line.starts_with(['l', 'h'])) {
+        bail!("Refusing to extract {}: it contains links", archive.display());
+    }
+
+    fs::create_dir_all(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+    let output = Command::new("tar")
+        .arg("-xzf")
+        .arg(archive)
+        .arg("-C")
+        .arg(dest)
+        .output()
+        .context("Failed to run tar (is it installed?)")?;
+    if !output.status.success() {
+        bail!(
+            "Extracting {} failed: {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Lines of `tar <flag> <archive>`
+fn tar_listing(archive: &Path, flag: &str) -> Result<Vec<String>> {
+    let output = Command::new("tar")
+        .arg(flag)
+        .arg(archive)
+        .output()
+        .context("Failed to run tar (is it installed?)")?;
+    if !output.status.success() {
+        bail!(
+            "{} isn't a readable .tar.gz: {}",
+            archive.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).lines().map(str::to_string).collect())
+}
+
+/// Whether an archive entry stays inside the directory it's extracted to
+fn is_safe_entry(name: &str) -> bool {
+    let normalized = name.replace('\\', "/");
+    !normalized.starts_with('/')
+        && !normalized.split('/').next().is_some_and(|first| first.ends_with(':'))
+        && Path::new(&normalized)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// The pack root inside `dest`: `dest` itself, or its only subdirectory
+/// (archives usually wrap the pack in a top-level folder)
+fn find_extracted_root(dest: &Path) -> Result<PathBuf> {
+    if dest.join("info.toml").is_file() {
+        return exercise::find_pack_root(dest);
+    }
+    let dirs: Vec<PathBuf> = fs::read_dir(dest)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    match dirs.as_slice() {
+        [only] if only.join("info.toml").is_file() => exercise::find_pack_root(only),
+        _ => bail!("The archive doesn't contain a zenlings pack (no info.toml found)"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_safe_entry() {
+        assert!(is_safe_entry("pack/info.toml"));
+        assert!(is_safe_entry("./pack/exercises/00_intro/intro1.py"));
+        assert!(is_safe_entry("pack/"));
+        assert!(!is_safe_entry("../evil.py"));
+        assert!(!is_safe_entry("pack/../../evil.py"));
+        assert!(!is_safe_entry("/etc/passwd"));
+        assert!(!is_safe_entry("..\\evil.py"));
+        assert!(!is_safe_entry("C:/Windows/evil.py"));
+    }
+
+    #[test]
+    fn test_default_dir_name() {
+        assert_eq!(default_dir_name("https://example.com/packs/zenml-basics.tar.gz"), "zenml-basics");
+        assert_eq!(default_dir_name("https://example.com/dl/pack.tgz?raw=1"), "pack");
+        assert_eq!(default_dir_name("downloads/pack.tar.gz"), "pack");
+        assert_eq!(default_dir_name("https://example.com/"), "zenlings-pack");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_fetch_local_archive() {
+        let src = tempfile::tempdir().unwrap();
+        let pack = src.path().join("mypack");
+        fs::create_dir_all(pack.join("exercises/00_intro")).unwrap();
+        fs::write(pack.join("info.toml"), "format_version = 1\n").unwrap();
+        fs::write(pack.join("exercises/00_intro/intro1.py"), "").unwrap();
+        let archive = src.path().join("mypack.tar.gz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(src.path())
+            .arg("mypack")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("unpacked");
+        let root = fetch_pack(&archive.to_string_lossy(), Some(&dest)).unwrap();
+        assert_eq!(root, dest.join("mypack"));
+        assert!(root.join("exercises/00_intro/intro1.py").is_file());
+
+        // A second fetch into the same place would mix two packs
+        assert!(fetch_pack(&archive.to_string_lossy(), Some(&dest)).is_err());
+    }
+}
@@ -0,0 +1,343 @@
+//! Non-interactive verification.
+//!
+//! Backs the `zenlings verify` subcommand, which verifies a range of
+//! exercises without entering the TUI (for CI and grading). Progress is
+//! read but never written.
+//!
+//! It also backs `zenlings --dry-run`, which prints the commands a
+//! verification would run without spawning anything.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use crate::app_state::AppState;
+use crate::exercise::Exercise;
+use crate::theme::Status;
+use crate::verify::{self, VerifyOptions, VerifyResult};
+
+/// Results of a headless verification run
+#[derive(Debug, Serialize)]
+pub struct VerifyReport {
+    pub total: usize,
+    pub passed: usize,
+    /// Exercises skipped after the first failure (without `--keep-going`)
+    pub not_run: usize,
+    pub exercises: Vec<ExerciseReport>,
+}
+
+/// Outcome for one exercise in a `VerifyReport`
+#[derive(Debug, Serialize)]
+pub struct ExerciseReport {
+    /// 1-based position in the exercise list
+    pub number: usize,
+    pub name: String,
+    pub path: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+impl VerifyReport {
+    /// Whether every exercise in the range was verified and passed
+    pub fn all_passed(&self) -> bool {
+        self.passed == self.total
+    }
+}
+
+/// Verify each exercise in `range`, in order
+///
+/// Stops at the first failure unless `keep_going`.
+pub fn verify_range(
+    state: &AppState,
+    opts: &VerifyOptions,
+    range: RangeInclusive<usize>,
+    simple: bool,
+    keep_going: bool,
+) -> VerifyReport {
+    let total = range.clone().count();
+    let mut exercises = Vec::new();
+    for idx in range {
+        let report = verify_one(state, &state.exercises[idx], idx + 1, opts, simple);
+        let failed = !report.passed;
+        exercises.push(report);
+        if failed && !keep_going {
+            break;
+        }
+    }
+
+    VerifyReport {
+        total,
+        passed: exercises.iter().filter(|e| e.passed).count(),
+        not_run: total - exercises.len(),
+        exercises,
+    }
+}
+
+fn verify_one(state: &AppState, exercise: &Exercise, number: usize, opts: &VerifyOptions, simple: bool) -> ExerciseReport {
+    // Nothing to run; report what the learner recorded
+    if exercise.manual {
+        let passed = state.is_completed(&exercise.name);
+        return ExerciseReport {
+            number,
+            name: exercise.name.clone(),
+            path: exercise.display_path(),
+            passed,
+            message: if passed {
+                "Manual exercise, marked complete".to_string()
+            } else {
+                "Manual exercise, not marked complete yet".to_string()
+            },
+        };
+    }
+    let result = if simple {
+        verify::verify_exercise_simple(exercise, opts)
+    } else {
+        verify::verify_exercise(exercise, opts)
+    };
+    let (passed, message) = match result {
+        Ok(result) => (result.passed(), describe(&result)),
+        Err(e) => (false, format!("Verification error: {}", e)),
+    };
+    ExerciseReport {
+        number,
+        name: exercise.name.clone(),
+        path: exercise.display_path(),
+        passed,
+        message,
+    }
+}
+
+/// One-line description of a result, with the ZenML summary when there is one
+fn describe(result: &VerifyResult) -> String {
+    if result.zenml_output.is_empty() || result.passed() {
+        result.message.clone()
+    } else {
+        format!("{} ({})", result.message, result.zenml_output)
+    }
+}
+
+/// Print a report as plain text or JSON
+pub fn print_report(report: &VerifyReport, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(report)?);
+    } else {
+        print!("{}", format_report(report));
+    }
+    Ok(())
+}
+
+/// A pass/fail table, one row per verified exercise, then a summary line
+pub fn format_report(report: &VerifyReport) -> String {
+    let path_width = report
+        .exercises
+        .iter()
+        .map(|e| e.path.chars().count())
+        .chain(["Exercise".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = format!("{:>3}  {:<8}  {:<path_width$}  Details\n", "#", "Result", "Exercise");
+    for exercise in &report.exercises {
+        let status = if exercise.passed { Status::Passed } else { Status::Failed };
+        out.push_str(&format!(
+            "{:>3}  {:<8}  {:<path_width$}  {}\n",
+            exercise.number,
+            status.marker(),
+            exercise.path,
+            exercise.message
+        ));
+    }
+
+    let failed = report.exercises.len() - report.passed;
+    out.push_str(&format!("\n{}/{} passed", report.passed, report.total));
+    if failed > 0 {
+        out.push_str(&format!(", {} failed", failed));
+    }
+    if report.not_run > 0 {
+        out.push_str(&format!(
+            ", {} not run (stopped at the first failure; --keep-going runs the rest)",
+            report.not_run
+        ));
+    }
+    out.push('\n');
+    out
+}
+
+/// Describe the commands verifying an exercise would run, without running them
+pub fn dry_run_plan(exercise: &Exercise, opts: &VerifyOptions, simple: bool) -> String {
+    let opts = opts.for_exercise(exercise);
+    let mut lines = vec![
+        format!("{} ({})", exercise.name, exercise.display_path()),
+    ];
+    if exercise.manual {
+        lines.push("  manual:       nothing is run; the learner marks it done".to_string());
+        return lines.join("\n");
+    }
+    lines.push(format!("  working dir:  {}", opts.working_dir.display()));
+
+    if !opts.env.is_empty() {
+        let mut env: Vec<String> = opts
+            .env
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, verify::shell_quote(v)))
+            .collect();
+        env.sort();
+        lines.push(format!("  env:          {}", env.join(" ")));
+    }
+
+    let python = verify::build_python_command(&exercise.path, &opts);
+    lines.push(format!("  python:       {}", verify::describe_command(&python)));
+
+    let check = match &exercise.verify_command {
+        _ if simple => "(none: --simple-verify checks the exit code only)".to_string(),
+        Some(command) => match command.split_first() {
+            Some((program, args)) => {
+                let cmd = verify::build_custom_command(program, args, &opts);
+                verify::describe_command(&cmd)
+            }
+            None => "(empty verify_command)".to_string(),
+        },
+        None => {
+            let cmd = verify::build_zenml_status_command(&exercise.pipeline_name, &opts);
+            verify::describe_command(&cmd)
+        }
+    };
+    lines.push(format!("  status check: {}", check));
+    if !simple && exercise.verify_command.is_none() && !exercise.verify_tags.is_empty() {
+        let cmd = verify::build_zenml_describe_command("<run id>", &opts);
+        lines.push(format!("  tag check:    {} (needs {})", verify::describe_command(&cmd), exercise.verify_tags.join(", ")));
+    }
+    lines.join("\n")
+}
+
+/// Print the dry-run plan for each exercise in `range`
+///
+/// A closed pipe (e.g. `zenlings --dry-run --all | head`) is not an error.
+pub fn print_dry_run(
+    state: &AppState,
+    opts: &VerifyOptions,
+    range: RangeInclusive<usize>,
+    simple: bool,
+) -> Result<()> {
+    let plans: Vec<String> = state.exercises[range]
+        .iter()
+        .map(|exercise| dry_run_plan(exercise, opts, simple))
+        .collect();
+    match writeln!(io::stdout().lock(), "{}", plans.join("\n\n")) {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        other => Ok(other?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(passed: &[bool]) -> VerifyReport {
+        let exercises: Vec<ExerciseReport> = passed
+            .iter()
+            .enumerate()
+            .map(|(i, &passed)| ExerciseReport {
+                number: i + 1,
+                name: format!("intro{}", i + 1),
+                path: format!("00_intro/intro{}.py", i + 1),
+                passed,
+                message: String::new(),
+            })
+            .collect();
+        VerifyReport {
+            total: exercises.len(),
+            passed: exercises.iter().filter(|e| e.passed).count(),
+            not_run: 0,
+            exercises,
+        }
+    }
+
+    #[test]
+    fn test_all_passed() {
+        assert!(report(&[true, true]).all_passed());
+        assert!(!report(&[true, false]).all_passed());
+    }
+
+    #[test]
+    fn test_report_json_shape() {
+        let json = serde_json::to_value(report(&[true, false])).unwrap();
+        assert_eq!(json["total"], 2);
+        assert_eq!(json["passed"], 1);
+        assert_eq!(json["exercises"][1]["name"], "intro2");
+        assert_eq!(json["exercises"][1]["passed"], false);
+    }
+
+    #[test]
+    fn test_format_report() {
+        let mut report = report(&[true, false]);
+        report.exercises[0].message = "Pipeline completed".to_string();
+        report.exercises[1].message = "Python script failed".to_string();
+        report.total = 4;
+        report.not_run = 2;
+
+        assert_eq!(
+            format_report(&report),
+            "  #  Result    Exercise            Details\n  \
+             1  ✓ PASSED  00_intro/intro1.py  Pipeline completed\n  \
+             2  ✗ FAILED  00_intro/intro2.py  Python script failed\n\
+             \n1/4 passed, 1 failed, 2 not run (stopped at the first failure; --keep-going runs the rest)\n"
+        );
+        assert!(!report.all_passed());
+    }
+
+    #[test]
+    fn test_dry_run_plan_for_manual_exercise() {
+        let entry = crate::exercise::ExerciseEntry {
+            name: "read1".to_string(),
+            dir: "00_intro".to_string(),
+            kind: Some("manual".to_string()),
+            ..Default::default()
+        };
+        let exercise = Exercise::from_entry(&entry, std::path::Path::new("/pack"), None);
+
+        let plan = dry_run_plan(&exercise, &VerifyOptions::default(), false);
+        assert_eq!(
+            plan,
+            "read1 (00_intro/read1.py)\n  manual:       nothing is run; the learner marks it done"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_plan() {
+        let entry = crate::exercise::ExerciseEntry {
+            name: "intro1".to_string(),
+            dir: "00_intro".to_string(),
+            ..Default::default()
+        };
+        let mut exercise = Exercise::from_entry(&entry, std::path::Path::new("/pack"), None);
+        exercise.env.insert("B".to_string(), "2".to_string());
+        let opts = VerifyOptions {
+            python_bin: "/pack/.venv/bin/python".to_string(),
+            working_dir: "/pack".into(),
+            python_args: vec!["-X".to_string(), "dev".to_string()],
+            env: [("A".to_string(), "1".to_string())].into(),
+            ..Default::default()
+        };
+
+        let plan = dry_run_plan(&exercise, &opts, false);
+        let lines: Vec<&str> = plan.lines().collect();
+        assert_eq!(lines[0], "intro1 (00_intro/intro1.py)");
+        assert_eq!(lines[1], "  working dir:  /pack");
+        assert_eq!(lines[2], "  env:          A=1 B=2");
+        assert_eq!(
+            lines[3],
+            "  python:       /pack/.venv/bin/python -X dev /pack/exercises/00_intro/intro1.py"
+        );
+        assert!(lines[4].starts_with("  status check: zenml pipeline runs list --pipeline intro1_pipeline"));
+
+        let plan = dry_run_plan(&exercise, &opts, true);
+        assert!(plan.ends_with("(none: --simple-verify checks the exit code only)"));
+
+        exercise.verify_command = Some(vec!["zenml".to_string(), "model".to_string(), "list".to_string()]);
+        let plan = dry_run_plan(&exercise, &opts, false);
+        assert!(plan.ends_with("  status check: zenml model list"));
+    }
+
+}
@@ -1,12 +1,6 @@
 //! Hint display and usage tracking.
 
 use crate::app_state::ProgressFile;
-use crate::exercise::Exercise;
-
-/// Get the hint for an exercise
-pub fn hint_for(exercise: &Exercise) -> Option<&str> {
-    exercise.hint.as_deref()
-}
 
 /// Record that a hint was used for an exercise
 pub fn record_hint_used(progress: &mut ProgressFile, exercise_name: &str) {
@@ -0,0 +1,189 @@
+//! Configurable key bindings.
+//!
+//! Bindings are read from an optional `~/.config/zenlings/keys.toml` that maps
+//! action names to single characters, e.g. `next = "j"`. Any action not
+//! mentioned keeps its default key.
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::term::Action;
+
+/// Action names accepted in keys.toml, with their default keys
+const DEFAULT_BINDINGS: [(&str, Action, char); 24] = [
+    ("quit", Action::Quit, 'q'),
+    ("hint", Action::Hint, 'h'),
+    ("next", Action::Next, 'n'),
+    ("prev", Action::Prev, 'p'),
+    ("list", Action::List, 'l'),
+    ("run", Action::Rerun, 'r'),
+    ("solution", Action::Solution, 's'),
+    ("open", Action::Open, 'o'),
+    ("toggle_raw", Action::ToggleRaw, 'v'),
+    ("cycle_output", Action::CycleOutput, 'e'),
+    ("check_solution", Action::VerifySolution, 'c'),
+    ("toggle_watch", Action::ToggleWatch, 'w'),
+    ("undo", Action::Undo, 'u'),
+    ("copy_path", Action::CopyPath, 'y'),
+    ("save_log", Action::SaveLog, 'S'),
+    ("help", Action::Help, '?'),
+    ("mark_done", Action::MarkDone, 'm'),
+    ("info", Action::Info, 'i'),
+    ("stats", Action::Stats, 't'),
+    ("reset_solution", Action::ResetToSolution, 'R'),
+    ("open_solution", Action::OpenSolution, 'O'),
+    ("open_pack", Action::OpenPack, 'P'),
+    ("auto_advance", Action::ToggleAutoAdvance, 'a'),
+    ("skip", Action::Skip, 'k'),
+];
+
+/// Mapping between key characters and actions
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: Vec<(Action, char)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: DEFAULT_BINDINGS
+                .iter()
+                .map(|&(_, action, key)| (action, key))
+                .collect(),
+        }
+    }
+}
+
+impl KeyMap {
+    /// Load bindings from the user's config file, or defaults if it doesn't exist
+    pub fn load() -> Result<Self> {
+        match config_path() {
+            Some(path) if path.exists() => {
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {:?}", path))?;
+                Self::from_toml(&content).with_context(|| format!("Invalid key bindings in {:?}", path))
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Parse a keys.toml document, layering it over the defaults
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let overrides: HashMap<String, String> =
+            toml::from_str(content).context("Failed to parse keys.toml")?;
+
+        let mut keymap = Self::default();
+        for (name, key) in &overrides {
+            let Some(&(_, action, _)) = DEFAULT_BINDINGS.iter().find(|(n, _, _)| n == name) else {
+                let known: Vec<&str> = DEFAULT_BINDINGS.iter().map(|(n, _, _)| *n).collect();
+                bail!("Unknown action '{}' (expected one of: {})", name, known.join(", "));
+            };
+            let mut chars = key.chars();
+            let (Some(c), None) = (chars.next(), chars.next()) else {
+                bail!("Key for '{}' must be a single character, got '{}'", name, key);
+            };
+            keymap.set(action, c);
+        }
+
+        keymap.check_duplicates()?;
+        Ok(keymap)
+    }
+
+    /// Action bound to a key character
+    pub fn action_for(&self, key: char) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(_, k)| *k == key)
+            .map(|(action, _)| *action)
+    }
+
+    /// Key character bound to an action
+    pub fn key_for(&self, action: Action) -> char {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, key)| *key)
+            .unwrap_or('?')
+    }
+
+    fn set(&mut self, action: Action, key: char) {
+        if let Some(binding) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            binding.1 = key;
+        }
+    }
+
+    fn check_duplicates(&self) -> Result<()> {
+        for (i, (action, key)) in self.bindings.iter().enumerate() {
+            if let Some((other, _)) = self.bindings[i + 1..].iter().find(|(_, k)| k == key) {
+                bail!(
+                    "Key '{}' is bound to both '{}' and '{}'",
+                    key,
+                    action_name(*action),
+                    action_name(*other)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The keys.toml name of an action
+fn action_name(action: Action) -> &'static str {
+    DEFAULT_BINDINGS
+        .iter()
+        .find(|(_, a, _)| *a == action)
+        .map(|(name, _, _)| *name)
+        .unwrap_or("unknown")
+}
+
+/// Location of the key bindings file
+fn config_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/zenlings/keys.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let keys = KeyMap::default();
+        assert_eq!(keys.action_for('n'), Some(Action::Next));
+        assert_eq!(keys.key_for(Action::Rerun), 'r');
+        assert_eq!(keys.action_for('y'), Some(Action::CopyPath));
+        assert_eq!(keys.action_for('S'), Some(Action::SaveLog));
+        assert_eq!(keys.action_for('s'), Some(Action::Solution));
+        assert_eq!(keys.action_for('x'), None);
+    }
+
+    #[test]
+    fn test_overrides_keep_unmapped_defaults() {
+        let keys = KeyMap::from_toml("next = \"j\"\nprev = \"k\"\nskip = \"K\"").unwrap();
+        assert_eq!(keys.action_for('j'), Some(Action::Next));
+        assert_eq!(keys.action_for('k'), Some(Action::Prev));
+        assert_eq!(keys.action_for('n'), None);
+        assert_eq!(keys.key_for(Action::Hint), 'h');
+    }
+
+    #[test]
+    fn test_swapped_keys_are_allowed() {
+        let keys = KeyMap::from_toml("next = \"p\"\nprev = \"n\"").unwrap();
+        assert_eq!(keys.action_for('p'), Some(Action::Next));
+        assert_eq!(keys.action_for('n'), Some(Action::Prev));
+    }
+
+    #[test]
+    fn test_duplicate_binding_is_rejected() {
+        let err = KeyMap::from_toml("next = \"h\"").unwrap_err();
+        assert!(err.to_string().contains("bound to both"));
+    }
+
+    #[test]
+    fn test_invalid_entries_are_rejected() {
+        assert!(KeyMap::from_toml("jump = \"j\"").is_err());
+        assert!(KeyMap::from_toml("next = \"jj\"").is_err());
+        assert!(KeyMap::from_toml("next = \"\"").is_err());
+    }
+}
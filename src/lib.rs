@@ -0,0 +1,39 @@
+//! Zenlings - Interactive ZenML Dynamic Pipelines Learning Tool
+//!
+//! The library side of the `zenlings` binary. Packs can be loaded and
+//! exercises verified programmatically, without the TUI:
+//!
+//! ```no_run
+//! use zenlings::app_state::AppState;
+//! use zenlings::verify::{self, VerifyOptions};
+//!
+//! let state = AppState::load("path/to/pack".into())?;
+//! let opts = VerifyOptions::default();
+//! let result = verify::verify_exercise(state.current_exercise(), &opts)?;
+//! println!("{}: {}", result.exercise_name, result.message);
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod app_state;
+pub mod exercise;
+pub mod hints;
+pub mod verify;
+
+// Frontend modules used by the zenlings binary
+pub mod check_cache;
+pub mod clipboard;
+pub mod completions;
+pub mod export;
+pub mod fetch;
+pub mod headless;
+pub mod keys;
+pub mod list;
+pub mod lock;
+pub mod logging;
+pub mod markdown;
+pub mod opener;
+pub mod plain;
+pub mod term;
+pub mod theme;
+pub mod timefmt;
+pub mod watch;
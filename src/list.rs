@@ -0,0 +1,131 @@
+//! Plain exercise listing.
+//!
+//! Backs the `zenlings list` subcommand, which prints the exercises in a
+//! pack to stdout (for shell completion, grading harnesses and scripts)
+//! without entering the TUI.
+
+use anyhow::Result;
+use crossterm::style::Stylize;
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
+
+use crate::app_state::AppState;
+
+/// When to color `zenlings list` output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolve `Auto` against the actual stdout
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => std::io::stdout().is_terminal(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// One exercise as printed by `zenlings list`
+#[derive(Debug, Serialize)]
+pub struct ListEntry {
+    /// 1-based position, as accepted by `--exercise`
+    pub index: usize,
+    pub name: String,
+    pub dir: String,
+    pub path: String,
+    pub completed: bool,
+    pub pipeline_name: String,
+}
+
+/// Collect list entries, optionally leaving out completed exercises
+pub fn list_entries(state: &AppState, incomplete_only: bool) -> Vec<ListEntry> {
+    state
+        .exercises
+        .iter()
+        .enumerate()
+        .map(|(idx, exercise)| ListEntry {
+            index: idx + 1,
+            name: exercise.name.clone(),
+            dir: exercise.dir.clone(),
+            path: exercise.display_path(),
+            completed: state.is_completed(&exercise.name),
+            pipeline_name: exercise.pipeline_name.clone(),
+        })
+        .filter(|entry| !incomplete_only || !entry.completed)
+        .collect()
+}
+
+/// Format one entry as a line of text
+fn format_entry(entry: &ListEntry, color: bool) -> String {
+    let mark = match (entry.completed, color) {
+        (true, true) => "✓".green().to_string(),
+        (true, false) => "✓".to_string(),
+        (false, _) => " ".to_string(),
+    };
+    let pipeline = format!("pipeline: {}", entry.pipeline_name);
+    let pipeline = if color {
+        pipeline.dark_grey().to_string()
+    } else {
+        pipeline
+    };
+    format!("{:>3}. [{}] {}  {}", entry.index, mark, entry.path, pipeline)
+}
+
+/// Print entries as text or JSON
+///
+/// A closed pipe (e.g. `zenlings list | head`) is not an error.
+pub fn print_list(entries: &[ListEntry], json: bool, color: bool) -> Result<()> {
+    let mut out = io::stdout().lock();
+    let written = if json {
+        writeln!(out, "{}", serde_json::to_string_pretty(entries)?)
+    } else {
+        entries
+            .iter()
+            .try_for_each(|entry| writeln!(out, "{}", format_entry(entry, color)))
+    };
+    match written {
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+        other => Ok(other?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(completed: bool) -> ListEntry {
+        ListEntry {
+            index: 3,
+            name: "load1".to_string(),
+            dir: "01_loading".to_string(),
+            path: "01_loading/load1.py".to_string(),
+            completed,
+            pipeline_name: "load1_pipeline".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_entry_plain() {
+        assert_eq!(
+            format_entry(&entry(true), false),
+            "  3. [✓] 01_loading/load1.py  pipeline: load1_pipeline"
+        );
+        assert_eq!(
+            format_entry(&entry(false), false),
+            "  3. [ ] 01_loading/load1.py  pipeline: load1_pipeline"
+        );
+    }
+
+    #[test]
+    fn test_format_entry_color_adds_escapes() {
+        let line = format_entry(&entry(true), true);
+        assert!(line.contains('\u{1b}'));
+        assert!(line.contains("01_loading/load1.py"));
+    }
+}
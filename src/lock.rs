@@ -0,0 +1,180 @@
+//! Lock file guarding a progress file against two sessions at once.
+//!
+//! `<progress file>.lock` sits next to the progress file and holds the pid
+//! of the session saving to it. A lock whose process has exited is stale
+//! and reclaimed without asking.
+
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A held lock; removed again when dropped
+#[derive(Debug)]
+pub struct ProgressLock {
+    path: PathBuf,
+}
+
+/// Result of trying to take the lock
+#[derive(Debug)]
+pub enum LockStatus {
+    Acquired(ProgressLock),
+    /// Another live process holds it
+    HeldBy(u32),
+}
+
+/// The lock file guarding `progress_path`, named after it so different
+/// progress files in one directory don't share a lock
+pub fn lock_path(progress_path: &Path) -> PathBuf {
+    let mut name = progress_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    progress_path.with_file_name(name)
+}
+
+/// Take the lock at `path` unless a running process other than this one holds it
+///
+/// The file is created exclusively, so of two sessions starting together
+/// only one gets it.
+pub fn acquire(path: &Path) -> Result<LockStatus> {
+    match create(path) {
+        Ok(lock) => return Ok(LockStatus::Acquired(lock)),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e).with_context(|| format!("Failed to create lock file: {:?}", path)),
+    }
+
+    // A session that has only just created the file may not have written its pid yet
+    let pid = read_pid(path).or_else(|| {
+        std::thread::sleep(Duration::from_millis(50));
+        read_pid(path)
+    });
+    if let Some(pid) = pid {
+        if pid != std::process::id() && pid_alive(pid) {
+            return Ok(LockStatus::HeldBy(pid));
+        }
+    }
+    log::info!("reclaiming stale lock {} (pid {:?})", path.display(), pid);
+    take_over(path).map(LockStatus::Acquired)
+}
+
+/// Create the lock file, failing with `AlreadyExists` if it's there
+fn create(path: &Path) -> io::Result<ProgressLock> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    let lock = ProgressLock { path: path.to_path_buf() };
+    writeln!(file, "{}", std::process::id())?;
+    Ok(lock)
+}
+
+/// Take the lock at `path` regardless of who holds it
+pub fn take_over(path: &Path) -> Result<ProgressLock> {
+    fs::write(path, format!("{}\n", std::process::id()))
+        .with_context(|| format!("Failed to write lock file: {:?}", path))?;
+    Ok(ProgressLock { path: path.to_path_buf() })
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+impl Drop for ProgressLock {
+    fn drop(&mut self) {
+        // A session that took over owns the file now
+        if read_pid(&self.path) == Some(std::process::id()) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Whether a process with this pid is running
+#[cfg(unix)]
+fn pid_alive(pid: u32) -> bool {
+    if Path::new("/proc/self").exists() {
+        return Path::new("/proc").join(pid.to_string()).exists();
+    }
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(windows)]
+fn pid_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn pid_alive(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acquire_and_release() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = lock_path(&dir.path().join(".zenlings-progress.json"));
+
+        let lock = match acquire(&path).unwrap() {
+            LockStatus::Acquired(lock) => lock,
+            LockStatus::HeldBy(pid) => panic!("held by {}", pid),
+        };
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+
+        // Our own pid isn't "another session"
+        assert!(matches!(acquire(&path).unwrap(), LockStatus::Acquired(_)));
+
+        drop(lock);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_lock_is_created_exclusively() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = lock_path(&dir.path().join(".zenlings-progress.json"));
+
+        let _lock = create(&path).unwrap();
+        assert_eq!(create(&path).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(read_pid(&path), Some(std::process::id()));
+    }
+
+    #[test]
+    fn test_each_progress_file_has_its_own_lock() {
+        let dir = Path::new("/home/learner");
+        assert_eq!(lock_path(&dir.join(".zenlings-progress.json")), dir.join(".zenlings-progress.json.lock"));
+        assert_ne!(lock_path(&dir.join("a.json")), lock_path(&dir.join("b.json")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_live_and_stale_locks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = lock_path(&dir.path().join(".zenlings-progress.json"));
+
+        // A process that's still running holds the lock
+        let mut child = std::process::Command::new("sleep").arg("5").spawn().unwrap();
+        fs::write(&path, child.id().to_string()).unwrap();
+        assert!(matches!(acquire(&path).unwrap(), LockStatus::HeldBy(pid) if pid == child.id()));
+
+        // Once it exits, the lock is reclaimed
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(matches!(acquire(&path).unwrap(), LockStatus::Acquired(_)));
+    }
+
+    #[test]
+    fn test_taken_over_lock_survives_drop() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = lock_path(&dir.path().join(".zenlings-progress.json"));
+
+        let lock = take_over(&path).unwrap();
+        fs::write(&path, "1").unwrap();
+        drop(lock);
+        assert_eq!(read_pid(&path), Some(1));
+    }
+}
@@ -0,0 +1,82 @@
+//! Debug logging to a file.
+//!
+//! The TUI owns the terminal in raw mode, so log records are never
+//! written to stdout/stderr - only to the configured log file.
+
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+
+/// Default log file name, created in the pack root
+const LOG_FILENAME: &str = ".zenlings.log";
+
+/// Resolve where logs should go, if logging is enabled at all
+///
+/// An explicit `--log-file` always enables logging; `--verbose` alone
+/// logs to `.zenlings.log` in the pack root.
+pub fn resolve_log_path(verbose: bool, log_file: Option<&Path>, pack_root: &Path) -> Option<PathBuf> {
+    match log_file {
+        Some(path) => Some(path.to_path_buf()),
+        None if verbose => Some(pack_root.join(LOG_FILENAME)),
+        None => None,
+    }
+}
+
+/// Log level for a `-v` count: debug (subprocesses, watch events, state
+/// changes) by default, trace (every raw file system event) from `-vv`
+pub fn level_for(verbosity: u8) -> log::LevelFilter {
+    if verbosity >= 2 {
+        log::LevelFilter::Trace
+    } else {
+        log::LevelFilter::Debug
+    }
+}
+
+/// Install a logger that appends records up to `level` to `path`
+///
+/// `RUST_LOG` still overrides the level per module.
+pub fn init_file_logger(path: &Path, level: log::LevelFilter) -> Result<()> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file: {:?}", path))?;
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .target(env_logger::Target::Pipe(Box::new(file)))
+        .try_init()
+        .context("Failed to initialize logger")?;
+
+    log::info!("zenlings {} starting", env!("CARGO_PKG_VERSION"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_log_path() {
+        let root = Path::new("/tmp/pack");
+
+        assert_eq!(resolve_log_path(false, None, root), None);
+        assert_eq!(
+            resolve_log_path(true, None, root),
+            Some(root.join(LOG_FILENAME))
+        );
+        assert_eq!(
+            resolve_log_path(false, Some(Path::new("/tmp/z.log")), root),
+            Some(PathBuf::from("/tmp/z.log"))
+        );
+    }
+
+    #[test]
+    fn test_level_for() {
+        assert_eq!(level_for(0), log::LevelFilter::Debug);
+        assert_eq!(level_for(1), log::LevelFilter::Debug);
+        assert_eq!(level_for(2), log::LevelFilter::Trace);
+        assert_eq!(level_for(3), log::LevelFilter::Trace);
+    }
+}
@@ -3,98 +3,446 @@
 //! A Rustlings-inspired CLI for learning ZenML's dynamic pipelines feature
 //! through hands-on exercises with instant feedback.
 
-mod app_state;
-mod exercise;
-mod hints;
-mod term;
-mod verify;
-mod watch;
-
 use anyhow::{Context, Result, bail};
-use clap::Parser;
-use std::path::PathBuf;
+use clap::{CommandFactory, Parser};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::thread;
-use std::time::Duration;
-
-use app_state::AppState;
-use term::{Action, CursorGuard, StartupCheckItem, StartupCheckStatus};
-use verify::{OutputLine, PythonVersion, VerifyOptions, VerifyResult};
-use watch::WatchEvent;
+use std::time::{Duration, Instant};
+
+use zenlings::app_state::{self, AppState};
+use zenlings::export::ExportFormat;
+use zenlings::keys::KeyMap;
+use zenlings::list::ColorChoice;
+use zenlings::term::{self, Action, CursorGuard, StartupCheckItem, StartupCheckStatus};
+use zenlings::theme::{Theme, ThemeName};
+use zenlings::verify::{self, OutputLine, OutputView, PythonVersion, Stream, VerifyOptions, VerifyResult};
+use zenlings::watch::{self, WatchEvent};
+use zenlings::{check_cache, clipboard, completions, exercise, export, fetch, headless, hints, list, logging, opener, plain};
 
 /// Zenlings - Learn ZenML Dynamic Pipelines
 #[derive(Parser, Debug)]
 #[command(name = "zenlings", version, about)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
     /// Path to zenlings pack (directory containing info.toml)
-    #[arg(long)]
+    #[arg(long, global = true)]
     path: Option<PathBuf>,
 
-    /// Disable file watching
+    /// Print the commands verification would run for the current exercise
+    /// (with `verify`: for the selected range), then exit
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// With --dry-run, show every exercise instead of just the current one
+    #[arg(long, visible_alias = "list", requires = "dry_run")]
+    all: bool,
+
+    /// Start with file watching paused (toggle at runtime with 'w')
     #[arg(long)]
     no_watch: bool,
 
+    /// After a pass, move on to the next incomplete exercise by itself (toggle with 'a')
+    #[arg(long)]
+    auto_advance: bool,
+
+    /// Quiet period after the last save before verification starts
+    #[arg(long, value_name = "MS", default_value_t = 300)]
+    watch_debounce_ms: u64,
+
     /// Python binary to use
-    #[arg(long, default_value = "python")]
+    #[arg(long, global = true, default_value = "python")]
     python: String,
 
+    /// Extra interpreter flags for exercise runs, shell-split (e.g. "-X dev -W error")
+    #[arg(long, global = true, allow_hyphen_values = true, value_name = "ARGS")]
+    python_args: Option<String>,
+
     /// ZenML binary to use
-    #[arg(long, default_value = "zenml")]
+    #[arg(long, global = true, default_value = "zenml")]
     zenml: String,
 
-    /// Jump to a specific exercise by name
-    #[arg(long)]
+    /// Kill ZenML CLI calls (status checks, stack lookups) that take longer; 0 waits forever
+    #[arg(long, global = true, value_name = "SECS", default_value_t = verify::DEFAULT_ZENML_TIMEOUT.as_secs())]
+    zenml_timeout_secs: u64,
+
+    /// Jump to a specific exercise by name (or its number in the list)
+    #[arg(long, value_name = "EXERCISE")]
     exercise: Option<String>,
 
-    /// Use simple verification (exit code only, no ZenML check)
+    /// Only work through exercises with this tag (progress is still shared)
+    #[arg(long, value_name = "TAG")]
+    tag: Option<String>,
+
+    /// Present exercises in a random order (kept when resuming; see --seed)
     #[arg(long)]
+    shuffle: bool,
+
+    /// Seed for --shuffle, to reproduce an order
+    #[arg(long, value_name = "N", requires = "shuffle")]
+    seed: Option<u64>,
+
+    /// Go back to the pack's own order after a --shuffle session
+    #[arg(long, conflicts_with = "shuffle")]
+    no_shuffle: bool,
+
+    /// Skip the welcome screen and resume at the last active exercise
+    #[arg(long, conflicts_with = "exercise")]
+    resume: bool,
+
+    /// Use simple verification (exit code only, no ZenML check)
+    #[arg(long, global = true)]
     simple_verify: bool,
 
     /// Skip startup checks
     #[arg(long)]
     skip_checks: bool,
+
+    /// Run `zenml init` without asking if the pack isn't initialized
+    #[arg(long, conflicts_with = "skip_checks")]
+    auto_init: bool,
+
+    /// Re-probe Python and ZenML instead of using results cached by a previous launch
+    #[arg(long, conflicts_with = "skip_checks")]
+    recheck: bool,
+
+    /// Color theme (remembered for next time; defaults to mono if NO_COLOR is set)
+    #[arg(long, value_enum)]
+    theme: Option<ThemeName>,
+
+    /// Show hints and pack messages as written instead of rendering Markdown
+    #[arg(long)]
+    no_markdown: bool,
+
+    /// Editor for 'o', shell-split (e.g. "code --wait"); defaults to $VISUAL, then $EDITOR
+    #[arg(long, value_name = "CMD")]
+    editor: Option<String>,
+
+    /// Watch solutions/ too, noting when the current solution changes (implied by --author)
+    #[arg(long)]
+    watch_solutions: bool,
+
+    /// Pack author mode: watch solutions/ and allow verifying solution files
+    #[arg(long)]
+    author: bool,
+
+    /// Log subprocess calls, file changes and state changes to .zenlings.log (-vv: every file event)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Write debug logs to this file (implies -v)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Keep progress in this file instead of the pack root (or set ZENLINGS_PROGRESS)
+    #[arg(long, global = true, value_name = "PATH")]
+    progress_file: Option<PathBuf>,
+
+    /// Start with fresh progress and never write it (for demos and sandboxes)
+    #[arg(long, conflicts_with = "progress_file")]
+    no_save: bool,
+
+    /// Draw over the main screen instead of the alternate screen (clears it on exit)
+    #[arg(long)]
+    inline: bool,
+
+    /// Don't capture the mouse (wheel scrolling); for terminals that mishandle it
+    #[arg(long)]
+    no_mouse: bool,
+
+    /// Line-by-line interface without raw mode or screen clearing, for
+    /// screen readers and limited SSH sessions; commands are typed as words
+    #[arg(long, conflicts_with_all = ["inline", "no_mouse"])]
+    plain: bool,
+
+    /// Lines of exercise output kept on screen ('S' saves the full output to a file)
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 100,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    output_lines: u64,
+
+    /// Set an environment variable for exercise runs (repeatable)
+    #[arg(long = "env", global = true, value_name = "KEY=VAL", value_parser = parse_env_pair)]
+    env: Vec<(String, String)>,
+}
+
+/// Parse a `KEY=VAL` pair for `--env`
+fn parse_env_pair(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VAL, got '{}'", s)),
+    }
+}
+
+/// Non-interactive subcommands
+#[derive(clap::Subcommand, Debug)]
+enum Commands {
+    /// Write a shareable summary of your progress
+    Export {
+        /// Output file (default: progress.md, or progress.json for --format json)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Summary format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+    },
+
+    /// Print the pack's exercises and whether each is completed
+    List {
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only show exercises that aren't completed yet
+        #[arg(long)]
+        incomplete_only: bool,
+
+        /// Color the output
+        #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+        color: ColorChoice,
+    },
+
+    /// Verify exercises without the TUI (exits non-zero if any fail)
+    Verify {
+        /// Print results as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// First exercise to verify, by name or number (default: the first)
+        #[arg(long, value_name = "EXERCISE")]
+        from: Option<String>,
+
+        /// Last exercise to verify, by name or number (default: the last)
+        #[arg(long, visible_alias = "until", value_name = "EXERCISE")]
+        to: Option<String>,
+
+        /// Verify the rest of the range after a failure instead of stopping
+        #[arg(long)]
+        keep_going: bool,
+    },
+
+    /// Check Python, ZenML and the active stack, and explain how to fix problems
+    Doctor,
+
+    /// Unpack a pack from a .tar.gz file or URL
+    Fetch {
+        /// Path or http(s) URL of the .tar.gz
+        source: String,
+
+        /// Directory to unpack into (default: named after the archive)
+        #[arg(long, value_name = "DIR")]
+        pack_dir: Option<PathBuf>,
+    },
+
+    /// Print a shell completion script (e.g. `source <(zenlings completions bash)`)
+    #[command(hide = true)]
+    Completions {
+        #[arg(value_enum)]
+        shell: completions::Shell,
+    },
+
+    /// Print the current pack's exercise names, for completion scripts
+    #[command(name = completions::EXERCISES_HELPER, hide = true)]
+    CompleteExercises,
 }
 
 /// Message to the verification worker thread
 enum VerifyRequest {
-    Run(exercise::Exercise),
+    Run(Box<exercise::Exercise>),
+    /// Kill the in-flight run, if any; always answered with `Cancelled`
+    Cancel,
     Stop,
 }
 
+/// Which file a verification run should execute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VerifyTarget {
+    Exercise,
+    Solution,
+}
+
 /// Message from the verification worker
 enum VerifyMessage {
     Output(OutputLine),
     Result(VerifyResult),
+    /// A `Cancel` request was handled; any earlier output or result is stale
+    Cancelled,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
+    // These run before any pack exists, so they can't need one
+    match &args.command {
+        Some(Commands::Fetch { source, pack_dir }) => {
+            let root = fetch::fetch_pack(source, pack_dir.as_deref())?;
+            println!("Unpacked the pack to {}", root.display());
+            println!("Start it with: zenlings --path {}", root.display());
+            return Ok(());
+        }
+        Some(Commands::Completions { shell }) => {
+            print!("{}", completions::generate(*shell, &Args::command()));
+            return Ok(());
+        }
+        Some(Commands::CompleteExercises) => {
+            let start = match &args.path {
+                Some(path) => path.clone(),
+                None => std::env::current_dir()?,
+            };
+            for name in completions::exercise_names(&start) {
+                println!("{}", name);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
     // Load application state
-    let pack_root = match &args.path {
-        Some(path) => path.clone(),
-        None => exercise::find_pack_root(&std::env::current_dir()?)?,
-    };
+    let pack_root = exercise::resolve_pack_root(args.path.as_deref(), &std::env::current_dir()?)?;
 
-    // Startup checks
-    if !args.skip_checks {
-        run_startup_checks(&pack_root, &args)?;
+    // Logging goes to a file only, so it never corrupts the TUI
+    if let Some(log_path) = logging::resolve_log_path(args.verbose > 0, args.log_file.as_deref(), &pack_root) {
+        logging::init_file_logger(&log_path, logging::level_for(args.verbose))?;
     }
 
-    let mut state = AppState::load(pack_root.clone())
+    match &args.command {
+        Some(Commands::Export { output, format }) => {
+            let state = AppState::load_with_progress(pack_root, args.progress_file.clone(), true).context("Failed to load zenlings pack")?;
+            let output = output
+                .clone()
+                .unwrap_or_else(|| PathBuf::from(format.default_filename()));
+            export::write_export(&state, &output, *format)?;
+            println!("Wrote progress summary to {}", output.display());
+            return Ok(());
+        }
+        Some(Commands::List { json, incomplete_only, color }) => {
+            let mut state = AppState::load_with_progress(pack_root, args.progress_file.clone(), true).context("Failed to load zenlings pack")?;
+            apply_tag_filter(&mut state, args.tag.as_deref());
+            let entries = list::list_entries(&state, *incomplete_only);
+            list::print_list(&entries, *json, color.enabled())?;
+            return Ok(());
+        }
+        Some(Commands::Verify { json, from, to, keep_going }) => {
+            let mut state = AppState::load_with_progress(pack_root.clone(), args.progress_file.clone(), true).context("Failed to load zenlings pack")?;
+            apply_tag_filter(&mut state, args.tag.as_deref());
+            let range = state.exercise_range(from.as_deref(), to.as_deref())?;
+            let verify_opts = build_verify_options(&state, &pack_root, &args)?;
+            if args.dry_run {
+                return headless::print_dry_run(&state, &verify_opts, range, args.simple_verify);
+            }
+            let report = headless::verify_range(&state, &verify_opts, range, args.simple_verify, *keep_going);
+            headless::print_report(&report, *json)?;
+            if !report.all_passed() {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Commands::Doctor) => {
+            let state = AppState::load_with_progress(pack_root.clone(), args.progress_file.clone(), true).context("Failed to load zenlings pack")?;
+            let verify_opts = build_verify_options(&state, &pack_root, &args)?;
+            if !run_doctor(&pack_root, &verify_opts) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(Commands::Fetch { .. } | Commands::Completions { .. } | Commands::CompleteExercises) => {
+            unreachable!("handled before loading the pack")
+        }
+        None => {}
+    }
+
+    // Fail early (before startup checks and the TUI) on a broken pack layout
+    exercise::check_exercises_dir(&pack_root)?;
+    let keys = KeyMap::load()?;
+
+    let mut state = AppState::load_with_progress(pack_root.clone(), args.progress_file.clone(), !args.no_save)
         .context("Failed to load zenlings pack")?;
+    apply_tag_filter(&mut state, args.tag.as_deref());
+
+    let verify_opts = build_verify_options(&state, &pack_root, &args)?;
+
+    // Show what would be run, without spawning anything (not even startup checks)
+    if args.dry_run {
+        if let Some(ref name) = args.exercise {
+            state.set_current_by_name(name)?;
+        }
+        let range = if args.all {
+            state.exercise_range(None, None)?
+        } else {
+            state.current_index..=state.current_index
+        };
+        headless::print_dry_run(&state, &verify_opts, range, args.simple_verify)?;
+        return Ok(());
+    }
+
+    // Everything below needs a terminal (except --plain, which only reads
+    // lines); say so plainly instead of failing in raw mode
+    if !args.plain {
+        if !term::is_interactive() {
+            eprintln!("{}", term::NOT_A_TERMINAL);
+            std::process::exit(2);
+        }
+        term::restore_on_signals()?;
+    }
+
+    // Two sessions saving the same progress file would overwrite each other's counts
+    if let Some(pid) = state.lock_holder {
+        println!("⚠️  Another Zenlings session may be running on this pack (pid {}).", pid);
+        println!("   Take over? y: this session saves progress (the other's saves may be lost),");
+        print!("   n: continue read-only ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let take_over = if args.plain { read_line_answer()? } else { term::read_answer()? };
+        println!();
+        if take_over {
+            state.take_over_lock()?;
+        } else {
+            state.continue_read_only();
+        }
+    }
+
+    // An explicit --theme is remembered for later sessions
+    if args.theme.is_some() {
+        state.progress.theme = args.theme;
+    }
+    let theme = Theme::new(ThemeName::resolve(args.theme, state.progress.theme));
+
+    // Startup checks (listed line by line in plain mode)
+    if !args.skip_checks && args.plain {
+        if !run_doctor(&pack_root, &verify_opts) {
+            bail!("Startup checks failed; fix the problems above or pass --skip-checks");
+        }
+        println!();
+    } else if !args.skip_checks {
+        run_startup_checks(&pack_root, &verify_opts, args.recheck, args.auto_init, &theme)?;
+        // Passed checks aren't worth keeping in the user's scrollback
+        term::clear_startup_checklist()?;
+    }
+
+    // A shuffled order sticks (via the seed in progress) until --no-shuffle
+    let seed = if args.no_shuffle {
+        state.progress.shuffle_seed = None;
+        None
+    } else if args.shuffle {
+        Some(args.seed.or(state.progress.shuffle_seed).unwrap_or_else(random_seed))
+    } else {
+        state.progress.shuffle_seed
+    };
+    if let Some(seed) = seed {
+        state.shuffle(seed);
+    }
 
     // Jump to specific exercise if requested
     if let Some(ref name) = args.exercise {
         state.set_current_by_name(name)?;
     }
-
-    // Set up verification options (with smart binary detection)
-    let verify_opts = VerifyOptions {
-        python_bin: verify::find_python_binary(&pack_root, &args.python),
-        zenml_bin: verify::find_zenml_binary(&pack_root, &args.zenml),
-        working_dir: pack_root.clone(),
-    };
+    state.author_mode = args.author;
 
     // Channels for verification
     let (verify_tx, verify_rx) = mpsc::channel::<VerifyRequest>();
@@ -107,40 +455,77 @@ fn main() -> Result<()> {
         verification_worker(verify_rx, result_tx, verify_opts_clone, simple_verify);
     });
 
-    // Set up file watcher (optional)
+    // Set up file watcher; --no-watch only pauses it so it can be resumed at runtime
     let (watch_tx, watch_rx) = mpsc::channel::<WatchEvent>();
-    let _watch_handle = if !args.no_watch {
-        let exercises_dir = pack_root.join("exercises");
-        Some(watch::start_watch(&exercises_dir, watch_tx)?)
-    } else {
-        None
-    };
+    let mut watch_roots = vec![pack_root.join("exercises")];
+    let solutions_root = (args.author || args.watch_solutions).then(|| pack_root.join("solutions"));
+    watch_roots.extend(solutions_root.clone());
+    // Extra watched files may live outside exercises/
+    for extra in state.exercises.iter().flat_map(|e| &e.watch) {
+        if let Some(dir) = extra.parent() {
+            if !watch_roots.iter().any(|root| dir.starts_with(root)) {
+                watch_roots.push(dir.to_path_buf());
+            }
+        }
+    }
+    let _watch_handle = watch::start_watch(&watch_roots, solutions_root, watch_tx)?;
+    state.watch_enabled = !args.no_watch;
+    state.auto_advance = args.auto_advance;
+
+    // The ZenML CLI is slow, so the stack shown in the header is looked up in the background
+    let (stack_tx, stack_rx) = mpsc::channel::<verify::StackSummary>();
+    refresh_stack_summary(&verify_opts, &stack_tx);
+
+    if args.plain {
+        let result = run_plain(&mut state, &args, &verify_tx, &result_rx, &watch_rx, &stack_rx);
+        state.save_progress()?;
+        let _ = verify_tx.send(VerifyRequest::Stop);
+        let _ = verify_handle.join();
+        return result;
+    }
 
     // Enter terminal UI
-    let _terminal = term::Terminal::enter()?;
+    let terminal = term::Terminal::enter(!args.no_mouse, !args.inline)?;
+    let editor = opener::configured_editor(args.editor.as_deref());
+
+    let markdown = !args.no_markdown;
 
     // Show welcome message on first run
-    if state.progress.started_at.is_none() || state.completed_count() == 0 {
+    if !args.resume && state.should_show_welcome() {
         if let Some(msg) = state.welcome_message() {
-            term::render_welcome(msg)?;
-            wait_for_continue()?;
+            term::show_welcome(msg, markdown, &keys, &theme)?;
         }
+        state.progress.welcome_shown = true;
+        state.save_progress()?;
     }
 
-    // Streaming output buffer
-    let mut output_buffer: Vec<String> = Vec::new();
+    // Streaming output of the current run
+    let mut output = RunOutput::new(args.output_lines as usize);
+
+    // File changes queue a verification, which starts once saves settle
+    let mut debouncer = watch::Debouncer::new(args.watch_debounce_ms);
+    let mut pending_verify: Option<VerifyTarget> = None;
+
+    // Cancels sent to the worker but not yet acknowledged; results arriving
+    // before the acknowledgement belong to the cancelled run
+    let mut cancels_pending = 0usize;
 
     // Main event loop
     loop {
+        if state.verifying {
+            state.spinner_tick = state.spinner_tick.wrapping_add(1);
+        }
+
         // Render current state
+        let dropped_lines = output.dropped_lines;
         if state.all_completed() {
             if let Some(msg) = state.final_message() {
-                term::render_complete(msg)?;
+                term::render_complete(msg, &state.completion_stats(), markdown, &theme)?;
             } else {
-                term::render_main(&state, &output_buffer)?;
+                term::render_main(&state, output.lines(), dropped_lines, &keys, &theme)?;
             }
         } else {
-            term::render_main(&state, &output_buffer)?;
+            term::render_main(&state, output.lines(), dropped_lines, &keys, &theme)?;
         }
 
         // Check for verification messages (non-blocking)
@@ -148,39 +533,118 @@ fn main() -> Result<()> {
             match msg {
                 VerifyMessage::Output(line) => {
                     match line {
-                        OutputLine::Stdout(s) | OutputLine::Stderr(s) => {
-                            output_buffer.push(s);
-                            // Keep buffer manageable
-                            if output_buffer.len() > 100 {
-                                output_buffer.remove(0);
-                            }
-                        }
+                        OutputLine::Stdout(s) => output.push(Stream::Stdout, s),
+                        OutputLine::Stderr(s) => output.push(Stream::Stderr, s),
                         OutputLine::Done(_) => {
                             // Process completion will come via Result message
                         }
                     }
                 }
+                VerifyMessage::Cancelled => {
+                    cancels_pending = cancels_pending.saturating_sub(1);
+                    output.clear();
+                    state.output_scroll = 0;
+                }
+                VerifyMessage::Result(_) if cancels_pending > 0 => {}
                 VerifyMessage::Result(result) => {
                     // Only apply result if it matches current exercise
                     if result.exercise_name == state.current_exercise().name {
-                        if result.passed() {
-                            state.mark_completed(&result.exercise_name);
-                            state.save_progress()?;
+                        let passed = result.passed() && !state.solution_check;
+                        record_result(&mut state, result)?;
+                        // The last pass stays put so the completion screen follows it
+                        if passed && state.auto_advance && state.next_incomplete_index().is_some() {
+                            state.auto_advance_at = Some(Instant::now() + app_state::AUTO_ADVANCE_DELAY);
                         }
-                        state.last_verify = Some(result);
-                        state.verifying = false;
                     }
                 }
             }
         }
 
-        // Drain file watcher events (we don't auto-run, but need to keep channel clear)
-        while watch_rx.try_recv().is_ok() {}
+        // Move on once a pass has been on screen long enough (a new run cancels it)
+        if state.verifying {
+            state.auto_advance_at = None;
+        }
+        if state.auto_advance_at.is_some_and(|at| Instant::now() >= at) {
+            if let Some(idx) = state.next_incomplete_index() {
+                cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                state.jump_to(idx);
+                state.save_progress()?;
+                output.clear();
+                state.output_scroll = 0;
+                pending_verify = None;
+                debouncer.reset();
+            }
+            state.auto_advance_at = None;
+        }
+
+        // Pick up the stack summary once the background lookup finishes
+        while let Ok(summary) = stack_rx.try_recv() {
+            state.stack_summary = Some(summary);
+        }
+
+        // Queue verification when the current exercise (or, for authors, its solution) changes
+        while let Ok(event) = watch_rx.try_recv() {
+            if !state.watch_enabled {
+                continue;
+            }
+            let exercise = state.current_exercise();
+            let target = match event {
+                WatchEvent::FileChanged(path) if !exercise.manual && exercise.watches(&path) => {
+                    Some(VerifyTarget::Exercise)
+                }
+                WatchEvent::SolutionChanged(path) if path == exercise.solution_path => {
+                    if state.author_mode && !exercise.manual {
+                        Some(VerifyTarget::Solution)
+                    } else {
+                        // 's' reads the file fresh, so the next look shows the edit
+                        state.set_notice("Solution changed on disk");
+                        None
+                    }
+                }
+                _ => None,
+            };
+
+            if let Some(target) = target {
+                debouncer.record();
+                pending_verify = Some(target);
+            }
+        }
+
+        if let Some(target) = pending_verify {
+            if debouncer.poll() {
+                pending_verify = None;
+                // A run started before this save is checking stale code
+                cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                // Saving without changes doesn't need another full run ('r' always forces one)
+                if target == VerifyTarget::Exercise && state.unchanged_since_pass() {
+                    let name = state.current_exercise().name.clone();
+                    state.solution_check = false;
+                    state.last_verify = Some(VerifyResult::cached_pass(&name));
+                    output.clear();
+                    state.output_scroll = 0;
+                } else {
+                    // Keep the stack-mismatch warning current for exercises that have one
+                    let exercise = state.current_exercise();
+                    if exercise.required_stack.is_some() || exercise.required_orchestrator.is_some() {
+                        refresh_stack_summary(&verify_opts, &stack_tx);
+                    }
+                    start_verification(&mut state, &mut output, &verify_tx, target)?;
+                }
+            }
+        }
 
         // Poll for keyboard input
-        if let Some(action) = term::poll_key(Duration::from_millis(50))? {
+        if let Some(action) = term::poll_key(Duration::from_millis(50), &keys)? {
+            // Any key (or wheel scroll) keeps the learner on a pass they're looking at
+            state.auto_advance_at = None;
             match action {
-                Action::Quit => break,
+                Action::Quit => {
+                    if !state.verifying
+                        || term::confirm_quit("Verification in progress — quit anyway?", &theme)?
+                    {
+                        break;
+                    }
+                }
 
                 Action::Hint => {
                     // Clone values we need to avoid borrow conflicts
@@ -190,85 +654,308 @@ fn main() -> Result<()> {
                     if let Some(hint_text) = hint {
                         hints::record_hint_used(&mut state.progress, &exercise_name);
                         state.save_progress()?;
-                        term::render_modal("Hint", &hint_text)?;
-                        wait_for_continue()?;
+                        term::show_modal("Hint", &hint_text, markdown, &keys, &theme)?;
                     } else {
-                        term::render_modal("Hint", "No hint available for this exercise.")?;
-                        wait_for_continue()?;
+                        term::show_modal("Hint", "No hint available for this exercise.", false, &keys, &theme)?;
+                    }
+                }
+
+                Action::Stats => term::show_stats(&state, &theme)?,
+
+                Action::Info => {
+                    match state.current_exercise().description.clone() {
+                        Some(text) => term::show_modal("About this exercise", &text, markdown, &keys, &theme)?,
+                        None => term::show_modal(
+                            "About this exercise",
+                            "No description for this exercise.",
+                            false,
+                            &keys,
+                            &theme,
+                        )?,
                     }
                 }
 
                 Action::Next => {
+                    cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
                     state.next();
                     state.save_progress()?;
-                    output_buffer.clear();
+                    output.clear();
+                    state.output_scroll = 0;
                     state.last_verify = None;
+                    pending_verify = None;
+                    debouncer.reset();
                 }
 
                 Action::Prev => {
+                    cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
                     state.prev();
                     state.save_progress()?;
-                    output_buffer.clear();
+                    output.clear();
+                    state.output_scroll = 0;
                     state.last_verify = None;
+                    pending_verify = None;
+                    debouncer.reset();
+                }
+
+                Action::Undo => {
+                    let previous = state.current_index;
+                    if state.undo().is_some() {
+                        state.save_progress()?;
+                        if state.current_index != previous {
+                            cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                            output.clear();
+                            state.output_scroll = 0;
+                            pending_verify = None;
+                            debouncer.reset();
+                        }
+                    }
                 }
 
                 Action::List => {
-                    term::render_list(&state)?;
-                    wait_for_continue()?;
+                    if let Some(idx) = term::show_list(&state, &keys, &theme)? {
+                        if idx != state.current_index {
+                            cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                            state.jump_to(idx);
+                            state.save_progress()?;
+                            output.clear();
+                            state.output_scroll = 0;
+                            pending_verify = None;
+                            debouncer.reset();
+                        }
+                    }
+                }
+
+                Action::ScrollUp => {
+                    let len = output.scroll_len(state.output_view);
+                    state.output_scroll = (state.output_scroll + term::SCROLL_STEP)
+                        .min(term::max_output_scroll(len, state.current_exercise()));
+                }
+
+                Action::ScrollDown => {
+                    state.output_scroll = state.output_scroll.saturating_sub(term::SCROLL_STEP);
+                }
+
+                Action::Rerun if state.current_exercise().manual => {
+                    let key = keys.key_for(Action::MarkDone);
+                    state.set_notice(format!("Nothing to run here; press '{}' when you're done", key));
+                }
+
+                Action::MarkDone => {
+                    let name = state.current_exercise().name.clone();
+                    if state.current_exercise().manual && !state.is_completed(&name) {
+                        state.mark_completed(&name);
+                        state.save_progress()?;
+                        state.set_notice("Marked complete");
+                    }
                 }
 
                 Action::Rerun => {
-                    if !state.verifying {
-                        state.verifying = true;
-                        state.last_verify = None;
-                        output_buffer.clear();
-                        verify_tx.send(VerifyRequest::Run(state.current_exercise().clone()))?;
+                    // The learner may have switched stacks since startup
+                    refresh_stack_summary(&verify_opts, &stack_tx);
+
+                    // Pressing 'r' mid-run restarts it
+                    cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                    pending_verify = None;
+                    debouncer.reset();
+                    start_verification(&mut state, &mut output, &verify_tx, VerifyTarget::Exercise)?;
+                }
+
+                Action::VerifySolution => {
+                    if state.author_mode && !state.verifying {
+                        pending_verify = None;
+                        debouncer.reset();
+                        start_verification(&mut state, &mut output, &verify_tx, VerifyTarget::Solution)?;
                     }
                 }
 
                 Action::Solution => {
                     let exercise = state.current_exercise();
-                    match std::fs::read_to_string(&exercise.solution_path) {
+                    match exercise.read_solution() {
                         Ok(content) => {
-                            term::render_modal("Solution", &content)?;
-                            wait_for_continue()?;
+                            let name = exercise.name.clone();
+                            term::show_modal("Solution", &content, false, &keys, &theme)?;
+                            state.record_solution_viewed(&name);
+                            state.save_progress()?;
                         }
                         Err(_) => {
-                            term::render_modal(
+                            term::show_modal(
                                 "Solution",
                                 "Solution file not found. Keep trying!",
+                                false,
+                                &keys,
+                                &theme,
                             )?;
-                            wait_for_continue()?;
+                        }
+                    }
+                }
+
+                Action::ResetToSolution => {
+                    let exercise = state.current_exercise().clone();
+                    if !state.can_reset_to_solution(&exercise.name) {
+                        state.set_notice(format!(
+                            "Reset to solution unlocks after {} attempts (or once solved)",
+                            app_state::RESET_MIN_ATTEMPTS
+                        ));
+                    } else if term::confirm(
+                        &format!(
+                            "Replace {} with the solution? Your version is kept in {}",
+                            exercise.display_path(),
+                            exercise.backup_path().display()
+                        ),
+                        &theme,
+                    )? {
+                        match exercise.reset_to_solution() {
+                            Ok(backup) => {
+                                state.record_solution_viewed(&exercise.name);
+                                state.save_progress()?;
+                                state.set_notice(format!(
+                                    "Reset to the solution; your version is in {}",
+                                    backup.display()
+                                ));
+                            }
+                            Err(e) => {
+                                term::show_modal("Reset to solution", &format!("{:#}", e), false, &keys, &theme)?;
+                            }
                         }
                     }
                 }
 
                 Action::Open => {
-                    let exercise = state.current_exercise();
-                    let path = &exercise.path;
-                    
-                    // Use platform-appropriate open command
-                    #[cfg(target_os = "macos")]
-                    let result = std::process::Command::new("open").arg(path).spawn();
-                    
-                    #[cfg(target_os = "linux")]
-                    let result = std::process::Command::new("xdg-open").arg(path).spawn();
-                    
-                    #[cfg(target_os = "windows")]
-                    let result = std::process::Command::new("cmd")
-                        .args(["/C", "start", "", &path.to_string_lossy()])
-                        .spawn();
-                    
-                    #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-                    let result: Result<std::process::Child, std::io::Error> = Err(std::io::Error::new(
-                        std::io::ErrorKind::Unsupported,
-                        "Platform not supported",
-                    ));
-
-                    if let Err(e) = result {
-                        term::render_modal("Open", &format!("Could not open file: {}", e))?;
-                        wait_for_continue()?;
+                    // A dashboard link for the last run can be opened alongside the file
+                    if let Some(url) = state.last_run_url() {
+                        if term::confirm("Open the run in your browser too?", &theme)? {
+                            if let Err(e) = opener::open_url(&url) {
+                                term::show_modal("Open run", &format!("{:#}", e), false, &keys, &theme)?;
+                            }
+                        }
+                    }
+
+                    let path = state.current_exercise().path.clone();
+                    let manual = state.current_exercise().manual;
+                    let Some(editor) = &editor else {
+                        if let Err(e) = opener::open_path(&path) {
+                            term::show_modal("Open", &format!("{:#}", e), false, &keys, &theme)?;
+                        }
+                        continue;
+                    };
+
+                    // Terminal editors (vim, nano) need the real screen and cooked input
+                    let before = state.current_content_hash();
+                    if let Err(e) = terminal.suspend(|| opener::run_editor(editor, &path))? {
+                        term::show_modal("Open", &format!("{:#}", e), false, &keys, &theme)?;
+                    }
+                    // Editors that return at once (most GUI ones) leave later saves to the watcher
+                    if manual || state.current_content_hash() == before {
+                        continue;
+                    }
+                    // Saves made in the editor already queued file events; verify once now instead
+                    while watch_rx.try_recv().is_ok() {}
+                    cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                    pending_verify = None;
+                    debouncer.reset();
+                    start_verification(&mut state, &mut output, &verify_tx, VerifyTarget::Exercise)?;
+                }
+
+                // Solutions are spoilers, so only pack authors open them from here
+                Action::OpenSolution if !state.author_mode => {
+                    state.set_notice("Opening the solution needs --author");
+                }
+                Action::OpenSolution => {
+                    if let Err(e) = opener::open_path(&state.current_exercise().solution_path) {
+                        term::show_modal("Open solution", &format!("{:#}", e), false, &keys, &theme)?;
+                    }
+                }
+
+                Action::OpenPack => {
+                    if let Err(e) = opener::open_path(&state.pack_root) {
+                        term::show_modal("Open pack", &format!("{:#}", e), false, &keys, &theme)?;
+                    }
+                }
+
+                Action::CopyPath => {
+                    let path = state.current_exercise().path.display().to_string();
+                    match clipboard::copy(&path) {
+                        Ok(()) => state.set_notice("Copied path to clipboard"),
+                        Err(_) => term::show_modal("Exercise path", &path, false, &keys, &theme)?,
+                    }
+                }
+
+                Action::SaveLog => {
+                    let exercise_name = state.current_exercise().name.clone();
+                    if output.full.is_empty() {
+                        term::show_modal("Save log", "No output to save yet.", false, &keys, &theme)?;
+                    } else {
+                        let path = state.pack_root.join(format!("{}.log", exercise_name));
+                        let message = match output.save(&path) {
+                            Ok(()) => format!(
+                                "Saved {} lines of output to {}",
+                                output.full.len(),
+                                path.display()
+                            ),
+                            Err(e) => format!("Could not save the log: {:#}", e),
+                        };
+                        term::show_modal("Save log", &message, false, &keys, &theme)?;
+                    }
+                }
+
+                Action::Help => match state.welcome_message() {
+                    Some(msg) => term::show_welcome(msg, markdown, &keys, &theme)?,
+                    None => term::show_modal(
+                        "Welcome to Zenlings!",
+                        &term::getting_started(&keys),
+                        markdown,
+                        &keys,
+                        &theme,
+                    )?,
+                },
+
+                Action::ToggleRaw => {
+                    state.show_raw_zenml = !state.show_raw_zenml;
+                }
+
+                Action::CycleOutput => {
+                    state.output_view = state.output_view.next();
+                    state.output_scroll = 0;
+                    state.set_notice(format!("Output: {}", state.output_view.label()));
+                }
+
+                Action::ToggleWatch => {
+                    state.watch_enabled = !state.watch_enabled;
+                    log::debug!("state: watching {}", if state.watch_enabled { "on" } else { "off" });
+                    if !state.watch_enabled {
+                        // Drop a save that's still waiting out the debounce
+                        pending_verify = None;
+                        debouncer.reset();
+                    }
+                }
+
+                Action::Skip => {
+                    let name = state.current_exercise().name.clone();
+                    if state.is_completed(&name) {
+                        state.set_notice("Already solved; nothing to skip");
+                        continue;
+                    }
+                    let question = "Skip this exercise? Why (optional, it's kept with your progress):";
+                    let Some(reason) = term::prompt_text(question, &theme)? else {
+                        continue;
+                    };
+                    let reason = Some(reason.trim().to_string()).filter(|r| !r.is_empty());
+                    state.skip(&name, reason);
+                    if let Some(idx) = state.next_incomplete_index() {
+                        cancel_verification(&mut state, &verify_tx, &mut cancels_pending)?;
+                        state.jump_to(idx);
+                        output.clear();
+                        state.output_scroll = 0;
+                        pending_verify = None;
+                        debouncer.reset();
                     }
+                    state.save_progress()?;
+                    state.set_notice(format!("Skipped {}; it stays unsolved until you come back", name));
+                }
+
+                Action::ToggleAutoAdvance => {
+                    state.auto_advance = !state.auto_advance;
+                    state.set_notice(if state.auto_advance { "Auto-advance on" } else { "Auto-advance off" });
                 }
 
                 Action::Continue | Action::None => {}
@@ -276,6 +963,9 @@ fn main() -> Result<()> {
         }
     }
 
+    // Persist the current exercise so the next session (or --resume) lands here
+    state.save_progress()?;
+
     // Clean up
     let _ = verify_tx.send(VerifyRequest::Stop);
     let _ = verify_handle.join();
@@ -283,6 +973,387 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Narrow `state` to `--tag`'s exercises, warning when no exercise has the tag
+fn apply_tag_filter(state: &mut AppState, tag: Option<&str>) {
+    let Some(tag) = tag else { return };
+    if state.filter_by_tag(tag) == 0 {
+        let message = format!("No exercise is tagged '{}'; --tag ignored", tag);
+        log::warn!("{}", message);
+        eprintln!("⚠️  {}", message);
+        state.set_notice(message);
+    }
+}
+
+/// A fresh seed for `--shuffle` without `--seed`
+fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    nanos ^ u64::from(std::process::id()).rotate_left(32)
+}
+
+/// Set up verification options (with smart binary detection)
+///
+/// Environment precedence: info.toml `[env]` < `--env` < per-exercise `env`.
+fn build_verify_options(state: &AppState, pack_root: &Path, args: &Args) -> Result<VerifyOptions> {
+    let mut env = state.info.env.clone();
+    env.extend(args.env.iter().cloned());
+    let python_args = match &args.python_args {
+        Some(s) => verify::split_args(s).context("Invalid --python-args")?,
+        None => Vec::new(),
+    };
+    let zenml_timeout = (args.zenml_timeout_secs > 0).then(|| Duration::from_secs(args.zenml_timeout_secs));
+    Ok(VerifyOptions {
+        python_bin: verify::find_python_binary(pack_root, &args.python),
+        zenml_bin: verify::find_zenml_binary(pack_root, &args.zenml, zenml_timeout),
+        working_dir: pack_root.to_path_buf(),
+        env,
+        python_args,
+        zenml_timeout,
+        ..Default::default()
+    })
+}
+
+/// Look up the active stack on a background thread, sending the result to `tx`
+fn refresh_stack_summary(opts: &VerifyOptions, tx: &mpsc::Sender<verify::StackSummary>) {
+    let opts = opts.clone();
+    let tx = tx.clone();
+    thread::spawn(move || {
+        let _ = tx.send(verify::get_stack_summary(&opts));
+    });
+}
+
+/// Output of the current run: the last lines for display, plus everything
+/// for saving to a log file
+struct RunOutput {
+    shown: VecDeque<(Stream, String)>,
+    full: Vec<String>,
+    cap: usize,
+    /// Lines evicted from `shown` to stay within `cap`
+    dropped_lines: usize,
+}
+
+impl RunOutput {
+    fn new(cap: usize) -> Self {
+        Self {
+            shown: VecDeque::with_capacity(cap),
+            full: Vec::new(),
+            cap,
+            dropped_lines: 0,
+        }
+    }
+
+    fn push(&mut self, stream: Stream, line: String) {
+        self.full.push(line.clone());
+        self.shown.push_back((stream, line));
+        if self.shown.len() > self.cap {
+            self.shown.pop_front();
+            self.dropped_lines += 1;
+        }
+    }
+
+    /// The kept lines with their streams, oldest first
+    fn lines(&mut self) -> &[(Stream, String)] {
+        self.shown.make_contiguous()
+    }
+
+    /// The kept lines' text, oldest first
+    fn texts(&self) -> Vec<&str> {
+        self.shown.iter().map(|(_, line)| line.as_str()).collect()
+    }
+
+    /// Rows the output scrolls through: the kept lines `view` shows, plus
+    /// the notices above them
+    fn scroll_len(&self, view: OutputView) -> usize {
+        let shown = self.shown.iter().filter(|(stream, _)| view.shows(*stream)).count();
+        shown + usize::from(self.dropped_lines > 0) + usize::from(view != OutputView::All)
+    }
+
+    fn clear(&mut self) {
+        self.shown.clear();
+        self.full.clear();
+        self.dropped_lines = 0;
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let mut content = self.full.join("\n");
+        content.push('\n');
+        std::fs::write(path, content).with_context(|| format!("Failed to write {:?}", path))
+    }
+}
+
+/// Send the current exercise (or its solution file) to the verification worker
+fn start_verification(
+    state: &mut AppState,
+    output: &mut RunOutput,
+    verify_tx: &mpsc::Sender<VerifyRequest>,
+    target: VerifyTarget,
+) -> Result<()> {
+    let mut exercise = state.current_exercise().clone();
+    if target == VerifyTarget::Solution {
+        exercise.path = exercise.solution_path.clone();
+        exercise.module = exercise.solution_module();
+    }
+
+    log::debug!("state: verifying {} ({:?})", exercise.name, target);
+    state.verifying = true;
+    state.verify_started = Some(Instant::now());
+    state.solution_check = target == VerifyTarget::Solution;
+    state.last_verify = None;
+    output.clear();
+    state.output_scroll = 0;
+    verify_tx.send(VerifyRequest::Run(Box::new(exercise)))?;
+    Ok(())
+}
+
+/// Stop the run in flight so the exercise can change under it; its output
+/// and result are dropped until the worker confirms
+fn cancel_verification(
+    state: &mut AppState,
+    verify_tx: &mpsc::Sender<VerifyRequest>,
+    cancels_pending: &mut usize,
+) -> Result<()> {
+    if state.verifying {
+        verify_tx.send(VerifyRequest::Cancel)?;
+        *cancels_pending += 1;
+        state.verifying = false;
+        state.verify_started = None;
+        state.solution_check = false;
+    }
+    Ok(())
+}
+
+/// Apply a finished run of the current exercise: count the attempt and
+/// save a pass, then show it as the last result
+fn record_result(state: &mut AppState, result: VerifyResult) -> Result<()> {
+    // A solution check says nothing about the learner's file
+    if !state.solution_check {
+        state.record_attempt(&result.exercise_name);
+        if result.passed() {
+            state.mark_completed(&result.exercise_name);
+            state.record_passed_hash(&result.exercise_name);
+        }
+        state.save_progress()?;
+    }
+    log::debug!(
+        "state: {} {} ({})",
+        result.exercise_name,
+        if result.passed() { "passed" } else { "failed" },
+        result.message
+    );
+    state.last_verify = Some(result);
+    state.verifying = false;
+    state.verify_started = None;
+    Ok(())
+}
+
+/// Read a typed line as a yes/no answer (for `--plain`, which has no raw mode)
+fn read_line_answer() -> Result<bool> {
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).context("Failed to read an answer")?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// The `--plain` front end: output and results are printed in order, and
+/// commands are read a line at a time from stdin
+///
+/// Shares the TUI's verification worker and file watcher. Ends on `quit`,
+/// or at the end of input once the current run has finished.
+fn run_plain(
+    state: &mut AppState,
+    args: &Args,
+    verify_tx: &mpsc::Sender<VerifyRequest>,
+    result_rx: &mpsc::Receiver<VerifyMessage>,
+    watch_rx: &mpsc::Receiver<WatchEvent>,
+    stack_rx: &mpsc::Receiver<verify::StackSummary>,
+) -> Result<()> {
+    // Lines are read on their own thread so run output keeps streaming meanwhile
+    let (line_tx, line_rx) = mpsc::channel::<String>();
+    thread::spawn(move || {
+        for line in std::io::stdin().lines().map_while(Result::ok) {
+            if line_tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    if !args.resume && state.should_show_welcome() {
+        if let Some(msg) = state.welcome_message() {
+            println!("{}\n", msg.trim_end());
+        }
+        state.progress.welcome_shown = true;
+        state.save_progress()?;
+    }
+    println!("{}\n", plain::exercise_summary(state));
+    println!("Type a command and press Enter ('help' lists them).");
+
+    let mut output = RunOutput::new(args.output_lines as usize);
+    let mut debouncer = watch::Debouncer::new(args.watch_debounce_ms);
+    let mut pending_verify = false;
+    let mut cancels_pending = 0usize;
+    let mut input_closed = false;
+
+    loop {
+        while let Ok(msg) = result_rx.try_recv() {
+            match msg {
+                VerifyMessage::Cancelled => {
+                    cancels_pending = cancels_pending.saturating_sub(1);
+                    output.clear();
+                }
+                _ if cancels_pending > 0 => {}
+                VerifyMessage::Output(OutputLine::Stdout(s)) => {
+                    println!("{}", s);
+                    output.push(Stream::Stdout, s);
+                }
+                VerifyMessage::Output(OutputLine::Stderr(s)) => {
+                    println!("{}", s);
+                    output.push(Stream::Stderr, s);
+                }
+                VerifyMessage::Output(OutputLine::Done(_)) => {}
+                VerifyMessage::Result(result) if result.exercise_name == state.current_exercise().name => {
+                    let passed = result.passed();
+                    record_result(state, result)?;
+                    if let Some(result) = &state.last_verify {
+                        for line in plain::result_lines(result, &output.texts(), state.last_run_url().as_deref()) {
+                            println!("{}", line);
+                        }
+                    }
+                    if passed && state.all_completed() {
+                        println!("\n{}", state.final_message().unwrap_or("All exercises complete!").trim_end());
+                    } else if passed {
+                        println!("Type 'next' to continue.");
+                    }
+                }
+                VerifyMessage::Result(_) => {}
+            }
+        }
+
+        while let Ok(summary) = stack_rx.try_recv() {
+            state.stack_summary = Some(summary);
+        }
+
+        while let Ok(event) = watch_rx.try_recv() {
+            let exercise = state.current_exercise();
+            if let WatchEvent::FileChanged(path) = event {
+                if state.watch_enabled && !exercise.manual && exercise.watches(&path) {
+                    debouncer.record();
+                    pending_verify = true;
+                }
+            }
+        }
+
+        if pending_verify && debouncer.poll() {
+            pending_verify = false;
+            cancel_verification(state, verify_tx, &mut cancels_pending)?;
+            println!("Change detected, running {}...", state.current_exercise().display_path());
+            start_verification(state, &mut output, verify_tx, VerifyTarget::Exercise)?;
+        }
+
+        if input_closed {
+            if !state.verifying && !pending_verify {
+                break;
+            }
+            thread::sleep(Duration::from_millis(50));
+            continue;
+        }
+        let line = match line_rx.recv_timeout(Duration::from_millis(50)) {
+            Ok(line) => line,
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                input_closed = true;
+                continue;
+            }
+        };
+        let command = match plain::parse_command(&line) {
+            Ok(Some(command)) => command,
+            Ok(None) => continue,
+            Err(message) => {
+                println!("{}", message);
+                continue;
+            }
+        };
+
+        match command {
+            plain::Command::Quit => {
+                cancel_verification(state, verify_tx, &mut cancels_pending)?;
+                break;
+            }
+
+            plain::Command::Help => println!("{}", plain::help_text()),
+
+            plain::Command::List => list::print_list(&list::list_entries(state, false), false, false)?,
+
+            plain::Command::Hint => {
+                let exercise = state.current_exercise();
+                match exercise.hint.clone() {
+                    Some(hint) => {
+                        let name = exercise.name.clone();
+                        hints::record_hint_used(&mut state.progress, &name);
+                        state.save_progress()?;
+                        println!("Hint:\n{}", hint.trim_end());
+                    }
+                    None => println!("No hint available for this exercise."),
+                }
+            }
+
+            plain::Command::Solution => {
+                let exercise = state.current_exercise();
+                match exercise.read_solution() {
+                    Ok(content) => {
+                        let name = exercise.name.clone();
+                        println!("Solution:\n{}", content.trim_end());
+                        state.record_solution_viewed(&name);
+                        state.save_progress()?;
+                    }
+                    Err(_) => println!("Solution file not found. Keep trying!"),
+                }
+            }
+
+            plain::Command::Next | plain::Command::Prev => {
+                cancel_verification(state, verify_tx, &mut cancels_pending)?;
+                if command == plain::Command::Next {
+                    state.next();
+                } else {
+                    state.prev();
+                }
+                state.save_progress()?;
+                output.clear();
+                state.last_verify = None;
+                pending_verify = false;
+                debouncer.reset();
+                println!("{}", plain::exercise_summary(state));
+            }
+
+            plain::Command::Run if state.current_exercise().manual => {
+                println!("Nothing to run here; type 'done' when you're done.");
+            }
+
+            plain::Command::Run => {
+                cancel_verification(state, verify_tx, &mut cancels_pending)?;
+                pending_verify = false;
+                debouncer.reset();
+                println!("Running {}...", state.current_exercise().display_path());
+                start_verification(state, &mut output, verify_tx, VerifyTarget::Exercise)?;
+            }
+
+            plain::Command::Done => {
+                let exercise = state.current_exercise();
+                let name = exercise.name.clone();
+                if !exercise.manual {
+                    println!("This exercise is checked by running it; type 'run'.");
+                } else if state.is_completed(&name) {
+                    println!("Already marked complete.");
+                } else {
+                    state.mark_completed(&name);
+                    state.save_progress()?;
+                    println!("Marked complete.");
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Outcome of a single startup check
 enum CheckOutcome {
     Pass { details: String },
@@ -291,13 +1362,15 @@ enum CheckOutcome {
 }
 
 /// Run a check with spinner animation
-fn run_check_with_spinner<F>(
+fn run_check_with_spinner<T, F>(
     items: &mut [StartupCheckItem],
     idx: usize,
+    theme: &Theme,
     check_fn: F,
-) -> Result<CheckOutcome>
+) -> Result<T>
 where
-    F: FnOnce() -> Result<CheckOutcome> + Send + 'static,
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
 {
     use std::sync::mpsc;
 
@@ -312,7 +1385,7 @@ where
     let mut frame = 0usize;
     loop {
         items[idx].status = StartupCheckStatus::Running { frame };
-        term::render_startup_checklist("Zenlings - Startup Checks", items, None)?;
+        term::render_startup_checklist("Zenlings - Startup Checks", items, None, theme)?;
 
         // Check if result is ready (non-blocking)
         match rx.try_recv() {
@@ -324,15 +1397,20 @@ where
             }
             Err(mpsc::TryRecvError::Disconnected) => {
                 // Thread panicked or dropped sender
-                return Ok(CheckOutcome::Fail {
-                    error: "Check crashed unexpectedly".to_string(),
-                    help: vec![],
-                });
+                bail!("Check crashed unexpectedly");
             }
         }
     }
 }
 
+/// Outcome for a check whose thread crashed
+fn crashed_outcome(e: anyhow::Error) -> CheckOutcome {
+    CheckOutcome::Fail {
+        error: e.to_string(),
+        help: vec![],
+    }
+}
+
 /// Apply check outcome to the checklist item
 fn apply_outcome(items: &mut [StartupCheckItem], idx: usize, outcome: &CheckOutcome) {
     items[idx].status = match outcome {
@@ -349,182 +1427,316 @@ fn apply_outcome(items: &mut [StartupCheckItem], idx: usize, outcome: &CheckOutc
     };
 }
 
-/// Run startup checks with visual feedback
-fn run_startup_checks(pack_root: &PathBuf, args: &Args) -> Result<()> {
-    // Hide cursor during checks (restored automatically on drop)
-    let _cursor = CursorGuard::new()?;
+/// Checklist rows, in display order
+const PYTHON_CHECK: usize = 0;
+const ZENML_CHECK: usize = 1;
+const INIT_CHECK: usize = 2;
+const ORCHESTRATOR_CHECK: usize = 3;
+
+/// Result of one of the independent startup probes run concurrently
+enum ProbeResult {
+    Python(Result<PythonVersion>),
+    Zenml(verify::ZenmlProbe),
+    Orchestrator(verify::OrchestratorCheckResult),
+}
+
+/// Start a probe on its own thread, reporting to `tx` when done
+fn spawn_probe<F>(tx: &mpsc::Sender<ProbeResult>, probe: F)
+where
+    F: FnOnce() -> ProbeResult + Send + 'static,
+{
+    let tx = tx.clone();
+    thread::spawn(move || {
+        let _ = tx.send(probe());
+    });
+}
 
-    // Smart binary detection: prefer .venv binaries if they exist
-    let python_bin = verify::find_python_binary(pack_root, &args.python);
-    let zenml_bin = verify::find_zenml_binary(pack_root, &args.zenml);
+/// Checklist outcome for the Python version probe (>= 3.9)
+fn python_outcome(version: &Result<PythonVersion>, suffix: &str) -> CheckOutcome {
+    match version {
+        Ok(version) if version.meets_minimum() => CheckOutcome::Pass {
+            details: format!("Python {}{}", version, suffix),
+        },
+        Ok(version) => CheckOutcome::Fail {
+            error: format!("Python {} (need >= {})", version, PythonVersion::MIN_REQUIRED),
+            help: vec![
+                "Install Python 3.9 or newer".to_string(),
+                "Or use --python <path> to specify a different interpreter".to_string(),
+            ],
+        },
+        Err(e) => CheckOutcome::Fail {
+            error: format!("Could not detect Python: {}", e),
+            help: vec![
+                "Ensure Python is installed and in your PATH".to_string(),
+                "Or use --python <path> to specify the interpreter".to_string(),
+            ],
+        },
+    }
+}
 
-    // Build verification options
-    let opts = VerifyOptions {
-        python_bin,
-        zenml_bin,
-        working_dir: pack_root.clone(),
+/// Downgrade a passing Python check to a warning when the pack's `.venv`
+/// interpreter didn't run and `find_python_binary` fell back to PATH
+fn venv_outcome(outcome: CheckOutcome, pack_root: &Path, opts: &VerifyOptions) -> CheckOutcome {
+    let Some(venv_python) = verify::skipped_venv_python(pack_root, &opts.python_bin) else {
+        return outcome;
     };
-
-    // Initialize checklist items
-    let mut items = vec![
-        StartupCheckItem {
-            label: "Python version".to_string(),
-            status: StartupCheckStatus::Pending,
+    let note = format!(
+        "{} doesn't run, using '{}' instead; the venv may be broken",
+        venv_python.display(),
+        opts.python_bin
+    );
+    match outcome {
+        CheckOutcome::Pass { details } => CheckOutcome::Warn {
+            details: format!("{} ({})", details, note),
         },
-        StartupCheckItem {
-            label: "ZenML installed".to_string(),
-            status: StartupCheckStatus::Pending,
+        CheckOutcome::Warn { details } => CheckOutcome::Warn {
+            details: format!("{} ({})", details, note),
         },
-        StartupCheckItem {
-            label: "ZenML initialized".to_string(),
-            status: StartupCheckStatus::Pending,
+        CheckOutcome::Fail { error, mut help } => {
+            help.push(format!("{}; recreate it with: python -m venv --clear .venv", note));
+            CheckOutcome::Fail { error, help }
+        }
+    }
+}
+
+/// Checklist outcome for the ZenML installation probe
+fn zenml_outcome(probe: &verify::ZenmlProbe, suffix: &str) -> CheckOutcome {
+    if !probe.python_import_ok {
+        return CheckOutcome::Fail {
+            error: "ZenML not found in Python environment".to_string(),
+            help: vec![
+                "Install with: pip install \"zenml[local]\"".to_string(),
+                "Make sure to install in the same environment as --python".to_string(),
+            ],
+        };
+    }
+    if !probe.zenml_cli_ok {
+        return CheckOutcome::Fail {
+            error: "ZenML CLI not accessible".to_string(),
+            help: vec![
+                "Ensure 'zenml' command is in your PATH".to_string(),
+                "Or use --zenml <path> to specify the CLI location".to_string(),
+            ],
+        };
+    }
+
+    // Both OK - show versions
+    let version_info = match (&probe.zenml_version, &probe.zenml_cli_version) {
+        (Some(py_ver), _) => format!("v{}", py_ver),
+        (None, Some(cli_ver)) => format!("CLI v{}", cli_ver),
+        (None, None) => "installed".to_string(),
+    };
+    CheckOutcome::Pass {
+        details: format!("{}{}", version_info, suffix),
+    }
+}
+
+/// Checklist outcome for the orchestrator probe ('local' recommended; warn only)
+fn orchestrator_outcome(result: verify::OrchestratorCheckResult) -> CheckOutcome {
+    use verify::OrchestratorCheckResult;
+    match result {
+        OrchestratorCheckResult::Found(flavor) if flavor == "local" => CheckOutcome::Pass {
+            details: "local".to_string(),
         },
-        StartupCheckItem {
-            label: "Orchestrator".to_string(),
-            status: StartupCheckStatus::Pending,
+        OrchestratorCheckResult::Found(flavor) => CheckOutcome::Warn {
+            details: format!("'{}' (recommend 'local' for fast feedback)", flavor),
         },
-    ];
+        OrchestratorCheckResult::NotFound => CheckOutcome::Warn {
+            details: "no active orchestrator found".to_string(),
+        },
+        OrchestratorCheckResult::CommandFailed(err) => CheckOutcome::Warn { details: err },
+    }
+}
+
+/// Checklist outcome for the `.zen` directory check
+fn init_outcome(pack_root: &Path) -> CheckOutcome {
+    if verify::check_zenml_init(pack_root) {
+        CheckOutcome::Pass {
+            details: ".zen directory found".to_string(),
+        }
+    } else {
+        CheckOutcome::Fail {
+            error: "ZenML not initialized".to_string(),
+            help: vec![
+                format!("cd {}", pack_root.display()),
+                "zenml init".to_string(),
+                "Or start with --auto-init to have Zenlings run it".to_string(),
+            ],
+        }
+    }
+}
+
+/// Why startup stops when a fail-fast check fails
+fn fail_fast_message(idx: usize) -> Option<&'static str> {
+    match idx {
+        PYTHON_CHECK => Some("Python check failed"),
+        ZENML_CHECK => Some("ZenML installation check failed"),
+        _ => None,
+    }
+}
+
+/// Run startup checks with visual feedback
+///
+/// The Python, ZenML-install and orchestrator probes are independent, so
+/// they run concurrently and each row updates as its result lands. The
+/// `.zen` init check runs once they're done; if it fails, the learner is
+/// offered `zenml init` (run without asking with `auto_init`).
+fn run_startup_checks(
+    pack_root: &Path,
+    opts: &VerifyOptions,
+    recheck: bool,
+    auto_init: bool,
+    theme: &Theme,
+) -> Result<()> {
+    const TITLE: &str = "Zenlings - Startup Checks";
+
+    // Hide cursor during checks (restored automatically on drop)
+    let _cursor = CursorGuard::new()?;
+
+    // Initialize checklist items
+    let mut items: Vec<StartupCheckItem> =
+        ["Python version", "ZenML installed", "ZenML initialized", "Orchestrator"]
+            .iter()
+            .map(|label| StartupCheckItem {
+                label: label.to_string(),
+                status: StartupCheckStatus::Pending,
+            })
+            .collect();
 
     // Render initial state
-    term::render_startup_checklist("Zenlings - Startup Checks", &items, None)?;
+    term::render_startup_checklist(TITLE, &items, None, theme)?;
+
+    // Python/ZenML probes from a previous launch, if nothing has changed since
+    let cache_key = check_cache::CacheKey::current(pack_root, opts);
+    let cached = if recheck {
+        None
+    } else {
+        check_cache::load(pack_root, &cache_key)
+    };
+    let cached_suffix = if cached.is_some() { " (cached)" } else { "" };
 
     // -------------------------------------------------------------------------
-    // Check 1: Python version >= 3.9
+    // Checks 1, 2 and 4: Python version, ZenML installed, orchestrator
     // -------------------------------------------------------------------------
+    let (tx, rx) = mpsc::channel();
+    let mut pending = vec![ORCHESTRATOR_CHECK];
     let opts_clone = opts.clone();
-    let outcome = run_check_with_spinner(&mut items, 0, move || {
-        match verify::get_python_version(&opts_clone) {
-            Ok(version) => {
-                if version.meets_minimum() {
-                    Ok(CheckOutcome::Pass {
-                        details: format!("Python {}", version),
-                    })
-                } else {
-                    Ok(CheckOutcome::Fail {
-                        error: format!("Python {} (need >= {})", version, PythonVersion::MIN_REQUIRED),
-                        help: vec![
-                            "Install Python 3.9 or newer".to_string(),
-                            format!("Or use --python <path> to specify a different interpreter"),
-                        ],
-                    })
-                }
-            }
-            Err(e) => Ok(CheckOutcome::Fail {
-                error: format!("Could not detect Python: {}", e),
-                help: vec![
-                    "Ensure Python is installed and in your PATH".to_string(),
-                    "Or use --python <path> to specify the interpreter".to_string(),
-                ],
-            }),
-        }
-    })?;
-
-    apply_outcome(&mut items, 0, &outcome);
-    term::render_startup_checklist("Zenlings - Startup Checks", &items, None)?;
+    spawn_probe(&tx, move || {
+        ProbeResult::Orchestrator(verify::get_orchestrator_type(&opts_clone))
+    });
 
-    if matches!(outcome, CheckOutcome::Fail { .. }) {
-        thread::sleep(Duration::from_millis(100)); // Brief pause to show final state
-        bail!("Python check failed");
+    match &cached {
+        // Cached results go through the same path as fresh ones
+        Some(cached) => {
+            tx.send(ProbeResult::Python(Ok(cached.python_version)))?;
+            tx.send(ProbeResult::Zenml(cached.zenml.clone()))?;
+        }
+        None => {
+            let opts_clone = opts.clone();
+            spawn_probe(&tx, move || ProbeResult::Python(verify::get_python_version(&opts_clone)));
+            let opts_clone = opts.clone();
+            spawn_probe(&tx, move || ProbeResult::Zenml(verify::probe_zenml(&opts_clone)));
+        }
     }
+    pending.extend([PYTHON_CHECK, ZENML_CHECK]);
+    drop(tx);
 
-    // -------------------------------------------------------------------------
-    // Check 2: ZenML installed
-    // -------------------------------------------------------------------------
-    let opts_clone = opts.clone();
-    let outcome = run_check_with_spinner(&mut items, 1, move || {
-        let probe = verify::probe_zenml(&opts_clone);
-
-        if !probe.python_import_ok {
-            return Ok(CheckOutcome::Fail {
-                error: "ZenML not found in Python environment".to_string(),
-                help: vec![
-                    "Install with: pip install \"zenml[local]\"".to_string(),
-                    format!("Make sure to install in the same environment as --python"),
-                ],
-            });
-        }
-
-        if !probe.zenml_cli_ok {
-            return Ok(CheckOutcome::Fail {
-                error: "ZenML CLI not accessible".to_string(),
-                help: vec![
-                    "Ensure 'zenml' command is in your PATH".to_string(),
-                    "Or use --zenml <path> to specify the CLI location".to_string(),
-                ],
-            });
-        }
-
-        // Both OK - show versions
-        let version_info = match (&probe.zenml_version, &probe.zenml_cli_version) {
-            (Some(py_ver), Some(_cli_ver)) => format!("v{}", py_ver),
-            (Some(py_ver), None) => format!("v{}", py_ver),
-            (None, Some(cli_ver)) => format!("CLI v{}", cli_ver),
-            (None, None) => "installed".to_string(),
-        };
+    let mut python_version = None;
+    let mut probe = None;
+    let mut frame = 0usize;
+    while !pending.is_empty() {
+        for &idx in &pending {
+            items[idx].status = StartupCheckStatus::Running { frame };
+        }
+        term::render_startup_checklist(TITLE, &items, None, theme)?;
 
-        Ok(CheckOutcome::Pass { details: version_info })
-    })?;
+        let landed = match rx.recv_timeout(Duration::from_millis(80)) {
+            Ok(ProbeResult::Python(version)) => {
+                let outcome = venv_outcome(python_outcome(&version, cached_suffix), pack_root, opts);
+                python_version = version.ok();
+                vec![(PYTHON_CHECK, outcome)]
+            }
+            Ok(ProbeResult::Zenml(result)) => {
+                let outcome = zenml_outcome(&result, cached_suffix);
+                probe = Some(result);
+                vec![(ZENML_CHECK, outcome)]
+            }
+            Ok(ProbeResult::Orchestrator(result)) => {
+                vec![(ORCHESTRATOR_CHECK, orchestrator_outcome(result))]
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                frame = frame.wrapping_add(1);
+                continue;
+            }
+            // Every probe thread has exited; whatever is left crashed
+            Err(mpsc::RecvTimeoutError::Disconnected) => pending
+                .iter()
+                .map(|&idx| (idx, crashed_outcome(anyhow::anyhow!("Check crashed unexpectedly"))))
+                .collect(),
+        };
 
-    apply_outcome(&mut items, 1, &outcome);
-    term::render_startup_checklist("Zenlings - Startup Checks", &items, None)?;
+        for (idx, outcome) in landed {
+            pending.retain(|&i| i != idx);
+            apply_outcome(&mut items, idx, &outcome);
+            if let (CheckOutcome::Fail { .. }, Some(message)) = (&outcome, fail_fast_message(idx)) {
+                term::render_startup_checklist(TITLE, &items, None, theme)?;
+                thread::sleep(Duration::from_millis(100)); // Brief pause to show final state
+                bail!(message);
+            }
+        }
+    }
 
-    if matches!(outcome, CheckOutcome::Fail { .. }) {
-        thread::sleep(Duration::from_millis(100));
-        bail!("ZenML installation check failed");
+    // Both probes passed; remember them for the next launch
+    if cached.is_none() {
+        if let (Some(version), Some(probe)) = (python_version, probe) {
+            check_cache::save(pack_root, cache_key, version, probe);
+        }
     }
 
     // -------------------------------------------------------------------------
-    // Check 3: ZenML initialized (.zen directory)
+    // Check 3: ZenML initialized (.zen directory); a filesystem check, no spinner needed
     // -------------------------------------------------------------------------
-    let pack_root_clone = pack_root.clone();
-    let outcome = run_check_with_spinner(&mut items, 2, move || {
-        if verify::check_zenml_init(&pack_root_clone) {
-            Ok(CheckOutcome::Pass {
-                details: ".zen directory found".to_string(),
-            })
-        } else {
-            Ok(CheckOutcome::Fail {
-                error: "ZenML not initialized".to_string(),
-                help: vec![
-                    format!("cd {}", pack_root_clone.display()),
-                    "zenml init".to_string(),
-                ],
+    let mut outcome = init_outcome(pack_root);
+
+    if matches!(outcome, CheckOutcome::Fail { .. }) {
+        let init = auto_init || {
+            items[INIT_CHECK].status = StartupCheckStatus::Pending;
+            term::render_startup_checklist(
+                TITLE,
+                &items,
+                Some("ZenML is not initialized here — initialize now? (y/n)"),
+                theme,
+            )?;
+            term::read_answer()?
+        };
+        if init {
+            let opts_clone = opts.clone();
+            let pack_root_clone = pack_root.to_path_buf();
+            outcome = run_check_with_spinner(&mut items, INIT_CHECK, theme, move || {
+                Ok(match verify::run_zenml_init(&opts_clone) {
+                    Ok(()) => init_outcome(&pack_root_clone),
+                    Err(e) => CheckOutcome::Fail {
+                        error: e.to_string(),
+                        help: vec![
+                            format!("cd {}", pack_root_clone.display()),
+                            "zenml init".to_string(),
+                        ],
+                    },
+                })
             })
+            .unwrap_or_else(crashed_outcome);
         }
-    })?;
+    }
 
-    apply_outcome(&mut items, 2, &outcome);
-    term::render_startup_checklist("Zenlings - Startup Checks", &items, None)?;
+    apply_outcome(&mut items, INIT_CHECK, &outcome);
 
     if matches!(outcome, CheckOutcome::Fail { .. }) {
+        term::render_startup_checklist(TITLE, &items, None, theme)?;
         thread::sleep(Duration::from_millis(100));
         bail!("ZenML not initialized");
     }
 
-    // -------------------------------------------------------------------------
-    // Check 4: Orchestrator is 'local' (warn only, don't fail)
-    // -------------------------------------------------------------------------
-    let opts_clone = opts.clone();
-    let outcome = run_check_with_spinner(&mut items, 3, move || {
-        use verify::OrchestratorCheckResult;
-        match verify::get_orchestrator_type(&opts_clone) {
-            OrchestratorCheckResult::Found(flavor) if flavor == "local" => Ok(CheckOutcome::Pass {
-                details: "local".to_string(),
-            }),
-            OrchestratorCheckResult::Found(flavor) => Ok(CheckOutcome::Warn {
-                details: format!("'{}' (recommend 'local' for fast feedback)", flavor),
-            }),
-            OrchestratorCheckResult::NotFound => Ok(CheckOutcome::Warn {
-                details: "no active orchestrator found".to_string(),
-            }),
-            OrchestratorCheckResult::CommandFailed(err) => Ok(CheckOutcome::Warn {
-                details: err,
-            }),
-        }
-    })?;
-
-    apply_outcome(&mut items, 3, &outcome);
-    term::render_startup_checklist("Zenlings - Startup Checks", &items, Some("All checks passed! Starting Zenlings..."))?;
+    term::render_startup_checklist(TITLE, &items, Some("All checks passed! Starting Zenlings..."), theme)?;
 
     // Brief pause so user can see the final checklist before TUI clears it
     thread::sleep(Duration::from_millis(800));
@@ -532,17 +1744,84 @@ fn run_startup_checks(pack_root: &PathBuf, args: &Args) -> Result<()> {
     Ok(())
 }
 
-/// Wait for user to press Enter/Esc to continue
-fn wait_for_continue() -> Result<()> {
-    loop {
-        if let Some(action) = term::poll_key(Duration::from_millis(100))? {
-            match action {
-                Action::Continue | Action::Quit => break,
-                _ => {}
+/// Run every startup probe and print what it found, with remediation for
+/// failures; returns false if any check failed
+///
+/// The probes that spawn Python or the ZenML CLI run concurrently.
+fn run_doctor(pack_root: &Path, opts: &VerifyOptions) -> bool {
+    let binary = |bin: &str| match verify::resolve_binary(bin) {
+        Some(path) => CheckOutcome::Pass {
+            details: path.display().to_string(),
+        },
+        None => CheckOutcome::Fail {
+            error: format!("'{}' not found", bin),
+            help: vec![format!("Check that '{}' is installed and in your PATH", bin)],
+        },
+    };
+    let init = if verify::check_zenml_init(pack_root) {
+        CheckOutcome::Pass {
+            details: format!("{} found", pack_root.join(".zen").display()),
+        }
+    } else {
+        CheckOutcome::Fail {
+            error: "ZenML not initialized".to_string(),
+            help: vec![
+                format!("cd {}", pack_root.display()),
+                "zenml init".to_string(),
+            ],
+        }
+    };
+    let stack = || match verify::get_stack_name(opts) {
+        Some(name) => CheckOutcome::Pass { details: name },
+        None => CheckOutcome::Warn {
+            details: "could not read the active stack ('zenml stack describe' failed)".to_string(),
+        },
+    };
+
+    let (python, zenml, orchestrator, stack) = thread::scope(|s| {
+        let python = s.spawn(|| {
+            venv_outcome(python_outcome(&verify::get_python_version(opts), ""), pack_root, opts)
+        });
+        let zenml = s.spawn(|| zenml_outcome(&verify::probe_zenml(opts), ""));
+        let orchestrator = s.spawn(|| orchestrator_outcome(verify::get_orchestrator_type(opts)));
+        let stack = s.spawn(stack);
+        let join = |handle: thread::ScopedJoinHandle<'_, CheckOutcome>| {
+            handle
+                .join()
+                .unwrap_or_else(|_| crashed_outcome(anyhow::anyhow!("Check crashed unexpectedly")))
+        };
+        (join(python), join(zenml), join(orchestrator), join(stack))
+    });
+
+    let checks = [
+        ("Python binary", binary(&opts.python_bin)),
+        ("Python version", python),
+        ("ZenML binary", binary(&opts.zenml_bin)),
+        ("ZenML install", zenml),
+        ("ZenML initialized", init),
+        ("Orchestrator", orchestrator),
+        ("Active stack", stack),
+    ];
+
+    println!("Zenlings {} doctor ({})", env!("CARGO_PKG_VERSION"), pack_root.display());
+    println!();
+    let mut ok = true;
+    for (label, outcome) in &checks {
+        match outcome {
+            CheckOutcome::Pass { details } => println!("✅ {:<18} {}", label, details),
+            CheckOutcome::Warn { details } => println!("⚠️  {:<18} {}", label, details),
+            CheckOutcome::Fail { error, help } => {
+                ok = false;
+                println!("❌ {:<18} {}", label, error);
+                for line in help {
+                    println!("   {:<18} → {}", "", line);
+                }
             }
         }
     }
-    Ok(())
+    println!();
+    println!("{}", if ok { "No problems found." } else { "Some checks failed; see the suggestions above." });
+    ok
 }
 
 /// Verification worker thread with streaming output
@@ -552,9 +1831,28 @@ fn verification_worker(
     opts: VerifyOptions,
     simple_mode: bool,
 ) {
-    for request in rx {
+    // A request that arrived while a run was in progress
+    let mut next_request: Option<VerifyRequest> = None;
+
+    loop {
+        let request = match next_request.take() {
+            Some(request) => request,
+            None => match rx.recv() {
+                Ok(request) => request,
+                Err(_) => break,
+            },
+        };
+
         match request {
             VerifyRequest::Run(exercise) => {
+                if let Some(message) = verify::check_integrations(&exercise, &opts.for_exercise(&exercise)) {
+                    let _ = tx.send(VerifyMessage::Result(VerifyResult::missing_integrations(&exercise, message)));
+                    continue;
+                }
+
+                // Taken before the script runs, so its own run doesn't count as stale
+                let baseline = if simple_mode { None } else { verify::run_baseline(&exercise, &opts) };
+
                 // Create a channel for streaming output
                 let (output_tx, output_rx) = mpsc::channel::<OutputLine>();
 
@@ -570,15 +1868,36 @@ fn verification_worker(
                     }
                 });
 
-                // Run the exercise with streaming
-                let python_ok = verify::run_python_streaming(&exercise.path, &opts, output_tx)
-                    .unwrap_or(false);
+                // Run the exercise with streaming; any new request interrupts it
+                let python_ok = verify::run_python_streaming(
+                    &exercise.path,
+                    &opts.for_exercise(&exercise),
+                    output_tx,
+                    || match rx.try_recv() {
+                        Ok(request) => {
+                            next_request = Some(request);
+                            true
+                        }
+                        Err(_) => false,
+                    },
+                )
+                .unwrap_or(Some((false, String::new())));
 
                 // Wait for output forwarding to complete
                 let _ = output_forwarder.join();
 
+                // Cancelled runs don't report a result
+                let Some((python_ok, stdout)) = python_ok else {
+                    continue;
+                };
+
                 // Build result
-                let result = if simple_mode {
+                let stdout_mismatch = verify::check_expected_stdout(&exercise, &stdout)
+                    .filter(|_| python_ok && simple_mode);
+                let result = if let Some(message) = stdout_mismatch {
+                    // Output was streamed
+                    VerifyResult::stdout_mismatch(&exercise, true, String::new(), message)
+                } else if simple_mode {
                     VerifyResult {
                         exercise_name: exercise.name.clone(),
                         outcome: if python_ok {
@@ -590,25 +1909,18 @@ fn verification_worker(
                         python_output: String::new(), // Output was streamed
                         zenml_checked: false,
                         zenml_output: String::new(),
+                        zenml_raw_output: String::new(),
+                        run_url: None,
+                        run_id: None,
                         message: if python_ok {
                             "Exercise completed successfully".to_string()
                         } else {
                             "Python script failed".to_string()
                         },
                     }
-                } else if !python_ok {
-                    VerifyResult {
-                        exercise_name: exercise.name.clone(),
-                        outcome: verify::VerifyOutcome::Failed,
-                        python_exit_ok: false,
-                        python_output: String::new(),
-                        zenml_checked: false,
-                        zenml_output: String::new(),
-                        message: "Python script failed".to_string(),
-                    }
                 } else {
-                    // Check ZenML status
-                    match verify::verify_exercise(&exercise, &opts) {
+                    // Judge the run that was just streamed (output already shown) without re-running it
+                    match verify::check_finished_run(&exercise, &opts, python_ok, String::new(), &stdout, baseline.as_ref()) {
                         Ok(r) => r,
                         Err(e) => VerifyResult {
                             exercise_name: exercise.name.clone(),
@@ -617,6 +1929,9 @@ fn verification_worker(
                             python_output: String::new(),
                             zenml_checked: false,
                             zenml_output: format!("Error: {}", e),
+                            zenml_raw_output: String::new(),
+                            run_url: None,
+                            run_id: None,
                             message: format!("Verification error: {}", e),
                         },
                     }
@@ -624,7 +1939,45 @@ fn verification_worker(
 
                 let _ = tx.send(VerifyMessage::Result(result));
             }
+            VerifyRequest::Cancel => {
+                let _ = tx.send(VerifyMessage::Cancelled);
+            }
             VerifyRequest::Stop => break,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_verification_resets_run_state() {
+        let pack = tempfile::tempdir().unwrap();
+        std::fs::write(
+            pack.path().join("info.toml"),
+            "format_version = 1\n[[exercises]]\nname = \"intro1\"\ndir = \"00_intro\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(pack.path().join("exercises/00_intro")).unwrap();
+        std::fs::write(pack.path().join("exercises/00_intro/intro1.py"), "").unwrap();
+        let mut state = AppState::load_with_progress(pack.path().to_path_buf(), None, false).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let mut cancels_pending = 0;
+
+        // Nothing running: nothing to send
+        cancel_verification(&mut state, &tx, &mut cancels_pending).unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(cancels_pending, 0);
+
+        state.verifying = true;
+        state.verify_started = Some(Instant::now());
+        state.solution_check = true;
+        cancel_verification(&mut state, &tx, &mut cancels_pending).unwrap();
+        assert!(matches!(rx.try_recv(), Ok(VerifyRequest::Cancel)));
+        assert_eq!(cancels_pending, 1);
+        assert!(!state.verifying);
+        assert!(state.verify_started.is_none());
+        assert!(!state.solution_check);
+    }
+}
@@ -0,0 +1,328 @@
+//! Lightweight Markdown rendering for hints and pack messages.
+//!
+//! Pack authors write hints and welcome/final messages in Markdown. This
+//! handles the common subset — `**bold**`, `` `code` ``, bullet lists,
+//! headings and fenced code blocks — and word-wraps the result into styled
+//! rows for the terminal. Anything else is shown as written.
+
+use crossterm::style::Color;
+
+use crate::term::wrap_line;
+use crate::theme::Theme;
+
+/// How a piece of rendered text is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Plain,
+    Bold,
+    Code,
+    CodeBlock,
+}
+
+impl Style {
+    /// Foreground color for the style (`None` keeps the terminal default)
+    pub fn color(self, theme: &Theme) -> Option<Color> {
+        match self {
+            Style::Plain => None,
+            Style::Bold => Some(theme.emphasis),
+            Style::Code => Some(theme.code),
+            Style::CodeBlock => Some(theme.code_block),
+        }
+    }
+}
+
+/// A run of text in a single style
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub text: String,
+    pub style: Style,
+}
+
+/// One rendered terminal row
+pub type Row = Vec<Span>;
+
+/// Indentation applied to fenced code blocks
+const CODE_INDENT: &str = "    ";
+
+/// Render Markdown into rows at most `width` characters wide
+pub fn render(content: &str, width: usize) -> Vec<Row> {
+    let width = width.max(1);
+    let mut rows = Vec::new();
+    let mut in_code_block = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            let code_width = width.saturating_sub(CODE_INDENT.len());
+            rows.extend(wrap_line(line, code_width).into_iter().map(|text| {
+                if text.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![span(&format!("{}{}", CODE_INDENT, text), Style::CodeBlock)]
+                }
+            }));
+        } else if line.trim().is_empty() {
+            rows.push(Vec::new());
+        } else {
+            rows.extend(render_text_line(line, width));
+        }
+    }
+    rows
+}
+
+/// Rows for plain text that shouldn't be interpreted as Markdown
+pub fn plain(content: &str, width: usize) -> Vec<Row> {
+    content
+        .lines()
+        .flat_map(|line| wrap_line(line, width))
+        .map(|text| {
+            if text.is_empty() {
+                Vec::new()
+            } else {
+                vec![span(&text, Style::Plain)]
+            }
+        })
+        .collect()
+}
+
+/// Render a prose line: headings, list items, then inline styles
+fn render_text_line(line: &str, width: usize) -> Vec<Row> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+
+    // Headings render bold, without their leading #s
+    let heading = trimmed.trim_start_matches('#');
+    if heading.len() < trimmed.len() && heading.starts_with(' ') {
+        let words = words_of(&[span(heading.trim(), Style::Bold)]);
+        return wrap_words(indent, &words, width);
+    }
+
+    // List items get a hanging indent so wrapped rows line up with the text
+    let (marker, text) = match trimmed.split_at_checked(2) {
+        Some(("- " | "* " | "+ ", rest)) => ("• ".to_string(), rest),
+        _ => match ordered_marker(trimmed) {
+            Some(len) => (trimmed[..len].to_string(), &trimmed[len..]),
+            None => (String::new(), trimmed),
+        },
+    };
+
+    let words = words_of(&parse_inline(text));
+    wrap_words(&format!("{}{}", indent, marker), &words, width)
+}
+
+/// Length of a leading `1. ` style marker, if there is one
+fn ordered_marker(line: &str) -> Option<usize> {
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    (digits > 0 && line[digits..].starts_with(". ")).then_some(digits + 2)
+}
+
+/// Split a line into spans for `**bold**` and `` `code` ``
+///
+/// Unclosed markers are kept as literal text.
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut plain_text = String::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let styled = if let Some(after) = rest.strip_prefix('`') {
+            after.find('`').map(|end| (Style::Code, &after[..end], &after[end + 1..]))
+        } else if let Some(after) = rest.strip_prefix("**") {
+            after.find("**").map(|end| (Style::Bold, &after[..end], &after[end + 2..]))
+        } else {
+            None
+        };
+
+        match styled {
+            Some((style, inner, after)) if !inner.is_empty() => {
+                if !plain_text.is_empty() {
+                    spans.push(span(&std::mem::take(&mut plain_text), Style::Plain));
+                }
+                spans.push(span(inner, style));
+                rest = after;
+            }
+            _ => {
+                let c = rest.chars().next().unwrap_or_default();
+                plain_text.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain_text.is_empty() {
+        spans.push(span(&plain_text, Style::Plain));
+    }
+    spans
+}
+
+/// A word of a styled line: one or more styled pieces with no space between
+type Word = Vec<Span>;
+
+/// Break spans into words at spaces, keeping each piece's style
+fn words_of(spans: &[Span]) -> Vec<Word> {
+    let mut words = Vec::new();
+    let mut word: Word = Vec::new();
+
+    for s in spans {
+        for (i, piece) in s.text.split(' ').enumerate() {
+            if i > 0 && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            if !piece.is_empty() {
+                word.push(span(piece, s.style));
+            }
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Lay words out in rows of at most `width` characters
+///
+/// The first row starts with `prefix`; continuation rows are indented to
+/// match it. Words longer than a row are hard-broken.
+fn wrap_words(prefix: &str, words: &[Word], width: usize) -> Vec<Row> {
+    let prefix_len = prefix.chars().count();
+    let hanging = " ".repeat(prefix_len);
+    // Leave at least a few columns for text when the prefix is very deep
+    let text_width = width.saturating_sub(prefix_len).max(4.min(width));
+
+    let mut rows = Vec::new();
+    let mut row: Row = vec![span(prefix, Style::Plain)];
+    let mut row_len = 0;
+
+    for word in words {
+        let word_len: usize = word.iter().map(|s| s.text.chars().count()).sum();
+        if row_len > 0 && row_len + 1 + word_len > text_width {
+            rows.push(std::mem::replace(&mut row, vec![span(&hanging, Style::Plain)]));
+            row_len = 0;
+        }
+        if row_len > 0 {
+            push_text(&mut row, " ", Style::Plain);
+            row_len += 1;
+        }
+
+        for piece in word {
+            let chars: Vec<char> = piece.text.chars().collect();
+            let mut rest = &chars[..];
+            while row_len + rest.len() > text_width {
+                let n = text_width - row_len;
+                push_text(&mut row, &rest[..n].iter().collect::<String>(), piece.style);
+                rows.push(std::mem::replace(&mut row, vec![span(&hanging, Style::Plain)]));
+                row_len = 0;
+                rest = &rest[n..];
+            }
+            push_text(&mut row, &rest.iter().collect::<String>(), piece.style);
+            row_len += rest.len();
+        }
+    }
+
+    rows.push(row);
+    rows.into_iter().map(tidy).collect()
+}
+
+/// Append text to a row, merging it into the last span if the style matches
+fn push_text(row: &mut Row, text: &str, style: Style) {
+    match row.last_mut() {
+        Some(last) if last.style == style => last.text.push_str(text),
+        _ => row.push(span(text, style)),
+    }
+}
+
+/// Drop empty spans left over from prefixes
+fn tidy(row: Row) -> Row {
+    row.into_iter().filter(|s| !s.text.is_empty()).collect()
+}
+
+fn span(text: &str, style: Style) -> Span {
+    Span {
+        text: text.to_string(),
+        style,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rows as plain strings, with styled text marked up for easy comparison
+    fn show(rows: &[Row]) -> Vec<String> {
+        rows.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|s| match s.style {
+                        Style::Plain => s.text.clone(),
+                        Style::Bold => format!("<b>{}</b>", s.text),
+                        Style::Code => format!("<c>{}</c>", s.text),
+                        Style::CodeBlock => format!("<pre>{}</pre>", s.text),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_styles() {
+        assert_eq!(
+            show(&render("Call `.load()` on the `artifact`, **now**.", 80)),
+            ["Call <c>.load()</c> on the <c>artifact</c>, <b>now</b>."]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_markers_are_literal() {
+        assert_eq!(show(&render("2 ** 3 and a ` tick", 80)), ["2 ** 3 and a ` tick"]);
+    }
+
+    #[test]
+    fn test_bullets_and_headings() {
+        let text = "# Tips\n\n- first\n* second\n  + nested\n1. numbered";
+        assert_eq!(
+            show(&render(text, 80)),
+            ["<b>Tips</b>", "", "• first", "• second", "  • nested", "1. numbered"]
+        );
+    }
+
+    #[test]
+    fn test_code_block_is_indented_and_not_parsed() {
+        let text = "Try:\n```python\nfor x in **items**:\n    print(x)\n```\ndone";
+        assert_eq!(
+            show(&render(text, 80)),
+            [
+                "Try:",
+                "<pre>    for x in **items**:</pre>",
+                "<pre>        print(x)</pre>",
+                "done",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrapping_keeps_hanging_indent_and_styles() {
+        let rows = render("- use `step.map()` to fan out over items", 20);
+        assert_eq!(
+            show(&rows),
+            ["• use <c>step.map()</c> to", "  fan out over items"]
+        );
+        for row in &rows {
+            let len: usize = row.iter().map(|s| s.text.chars().count()).sum();
+            assert!(len <= 20);
+        }
+    }
+
+    #[test]
+    fn test_long_word_is_hard_broken() {
+        assert_eq!(show(&render("`abcdefghij`", 4)), ["<c>abcd</c>", "<c>efgh</c>", "<c>ij</c>"]);
+    }
+
+    #[test]
+    fn test_plain_is_not_interpreted() {
+        assert_eq!(show(&plain("- **not bold**\n\nx", 80)), ["- **not bold**", "", "x"]);
+    }
+}
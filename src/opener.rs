@@ -0,0 +1,122 @@
+//! Opening files and directories with the platform's default application,
+//! or with the learner's editor when one is configured.
+//!
+//! Backs the `o` (exercise), `O` (solution, author mode) and `P` (pack
+//! root) actions.
+
+use crate::verify::split_args;
+use anyhow::{Context, Result};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+/// The editor to open exercises with: `--editor`, then `$VISUAL`, then `$EDITOR`
+pub fn configured_editor(flag: Option<&str>) -> Option<String> {
+    pick_editor(flag, std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok())
+}
+
+fn pick_editor(flag: Option<&str>, visual: Option<String>, editor: Option<String>) -> Option<String> {
+    flag.map(str::to_string)
+        .into_iter()
+        .chain(visual)
+        .chain(editor)
+        .find(|e| !e.trim().is_empty())
+}
+
+/// Build the command for `editor` (shell-split, e.g. "code --wait") on `path`
+fn editor_command(editor: &str, path: &Path) -> Result<Command> {
+    let words = split_args(editor).with_context(|| format!("Invalid editor command {:?}", editor))?;
+    let (program, args) = words
+        .split_first()
+        .with_context(|| format!("Invalid editor command {:?}", editor))?;
+    let mut cmd = Command::new(program);
+    cmd.args(args).arg(path);
+    Ok(cmd)
+}
+
+/// Run `editor` on `path` in the current terminal and wait for it to exit
+pub fn run_editor(editor: &str, path: &Path) -> Result<()> {
+    let status = editor_command(editor, path)?
+        .status()
+        .with_context(|| format!("Could not start editor {:?}", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor {:?} exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+/// Open `path` with the platform's opener (`open`, `xdg-open` or `start`)
+///
+/// Returns once the opener has been started, without waiting for it.
+pub fn open_path(path: &Path) -> Result<()> {
+    opener_command(path.as_os_str())?
+        .spawn()
+        .with_context(|| format!("Could not open {}", path.display()))?;
+    Ok(())
+}
+
+/// Open `url` in the default browser
+pub fn open_url(url: &str) -> Result<()> {
+    opener_command(OsStr::new(url))?
+        .spawn()
+        .with_context(|| format!("Could not open {}", url))?;
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn opener_command(target: &OsStr) -> Result<Command> {
+    let mut cmd = Command::new("open");
+    cmd.arg(target);
+    Ok(cmd)
+}
+
+#[cfg(target_os = "windows")]
+fn opener_command(target: &OsStr) -> Result<Command> {
+    let mut cmd = Command::new("cmd");
+    cmd.args(["/C", "start", ""]).arg(target);
+    Ok(cmd)
+}
+
+#[cfg(target_os = "linux")]
+fn opener_command(target: &OsStr) -> Result<Command> {
+    let mut cmd = Command::new("xdg-open");
+    cmd.arg(target);
+    Ok(cmd)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn opener_command(_target: &OsStr) -> Result<Command> {
+    anyhow::bail!("Platform not supported")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_editor() {
+        let some = |s: &str| Some(s.to_string());
+        assert_eq!(pick_editor(Some("nano"), some("code"), some("vim")), some("nano"));
+        assert_eq!(pick_editor(None, some("code"), some("vim")), some("code"));
+        assert_eq!(pick_editor(None, some(""), some("vim")), some("vim"));
+        assert_eq!(pick_editor(None, None, None), None);
+    }
+
+    #[test]
+    fn test_editor_command() {
+        let cmd = editor_command("code --wait", Path::new("intro1.py")).unwrap();
+        assert_eq!(cmd.get_program(), "code");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["--wait", "intro1.py"]);
+        assert!(editor_command("  ", Path::new("intro1.py")).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_opener_command() {
+        let cmd = opener_command(OsStr::new("/pack/exercises/00_intro/intro1.py")).unwrap();
+        assert_eq!(cmd.get_program(), "xdg-open");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["/pack/exercises/00_intro/intro1.py"]);
+    }
+}
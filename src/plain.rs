@@ -0,0 +1,158 @@
+//! Line-oriented front end for `--plain`.
+//!
+//! For screen readers and limited SSH sessions: nothing here enters raw
+//! mode, moves the cursor or clears the screen. Output is printed in order
+//! and commands are typed as whole lines. The event loop itself lives in the
+//! binary, next to the TUI's, and shares its verification worker.
+
+use crate::app_state::AppState;
+use crate::theme::Status;
+use crate::verify::{self, VerifyResult};
+
+/// A command typed at the `--plain` prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    Hint,
+    Next,
+    Prev,
+    Solution,
+    Done,
+    List,
+    Help,
+    Quit,
+}
+
+/// Every command: (name, shortcut, command, what it does)
+const COMMANDS: &[(&str, char, Command, &str)] = &[
+    ("run", 'r', Command::Run, "run the current exercise"),
+    ("hint", 'h', Command::Hint, "show the hint"),
+    ("next", 'n', Command::Next, "go to the next exercise"),
+    ("prev", 'p', Command::Prev, "go to the previous exercise"),
+    ("solution", 's', Command::Solution, "show the solution"),
+    ("done", 'd', Command::Done, "mark a manual exercise complete"),
+    ("list", 'l', Command::List, "list every exercise"),
+    ("help", '?', Command::Help, "show these commands"),
+    ("quit", 'q', Command::Quit, "save and exit"),
+];
+
+/// Parse a typed line, by name or shortcut; `Ok(None)` for a blank line
+pub fn parse_command(line: &str) -> Result<Option<Command>, String> {
+    let word = line.trim().to_lowercase();
+    if word.is_empty() {
+        return Ok(None);
+    }
+    COMMANDS
+        .iter()
+        .find(|(name, short, ..)| word == *name || word == short.to_string())
+        .map(|&(_, _, command, _)| Some(command))
+        .ok_or_else(|| format!("Unknown command '{}'. Type 'help' for the list.", line.trim()))
+}
+
+/// The command list shown by `help`
+pub fn help_text() -> String {
+    COMMANDS
+        .iter()
+        .map(|(name, short, _, about)| format!("  {:<12} {}", format!("{} ({})", name, short), about))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The current exercise, as printed on arriving at it
+pub fn exercise_summary(state: &AppState) -> String {
+    let exercise = state.current_exercise();
+    let mut lines = vec![format!(
+        "Exercise {} of {}: {}",
+        state.current_index + 1,
+        state.total_count(),
+        exercise.display_path()
+    )];
+    if exercise.manual {
+        lines.push("Manual exercise: type 'done' when you've finished it.".to_string());
+    } else {
+        lines.push(format!("Pipeline: {}", exercise.pipeline_name));
+    }
+    lines.push(
+        if state.is_completed(&exercise.name) {
+            "Status: solved"
+        } else {
+            "Status: not solved yet"
+        }
+        .to_string(),
+    );
+    if let Some(description) = &exercise.description {
+        lines.push(String::new());
+        lines.push(description.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+/// A finished verification: the outcome, then whatever explains a failure
+///
+/// `output` is the run's streamed output, searched for a Python exception.
+pub fn result_lines<S: AsRef<str>>(result: &VerifyResult, output: &[S], run_url: Option<&str>) -> Vec<String> {
+    let status = if result.passed() { Status::Passed } else { Status::Failed };
+    let mut lines = vec![format!("{} - {}", status.marker(), result.message)];
+    if !result.passed() {
+        let lines_of_result: Vec<&str> = result.python_output.lines().collect();
+        let exception =
+            verify::traceback_summary(output).or_else(|| verify::traceback_summary(&lines_of_result));
+        if let Some(exception) = exception {
+            lines.push(format!("Exception: {}", exception));
+        }
+        if result.zenml_checked && !result.zenml_output.is_empty() {
+            lines.push(format!("ZenML: {}", result.zenml_output));
+        }
+    }
+    if let Some(url) = run_url {
+        lines.push(format!("View run: {}", url));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("run"), Ok(Some(Command::Run)));
+        assert_eq!(parse_command("  N \n"), Ok(Some(Command::Next)));
+        assert_eq!(parse_command("?"), Ok(Some(Command::Help)));
+        assert_eq!(parse_command("Quit"), Ok(Some(Command::Quit)));
+        assert_eq!(parse_command("   "), Ok(None));
+        assert_eq!(
+            parse_command("jump"),
+            Err("Unknown command 'jump'. Type 'help' for the list.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_help_lists_every_command() {
+        let help = help_text();
+        assert_eq!(help.lines().count(), COMMANDS.len());
+        assert!(help.contains("  solution (s) show the solution"), "{help}");
+    }
+
+    #[test]
+    fn test_result_lines() {
+        let mut result = VerifyResult::cached_pass("intro1");
+        assert_eq!(result_lines::<&str>(&result, &[], None), ["✓ PASSED - Already passed (unchanged)"]);
+
+        result.outcome = verify::VerifyOutcome::Failed;
+        result.message = "Python script failed".to_string();
+        let output = [
+            "Traceback (most recent call last):",
+            "  File \"intro1.py\", line 3, in <module>",
+            "NameError: name 'x' is not defined",
+        ];
+        assert_eq!(
+            result_lines(&result, &output, Some("http://127.0.0.1:8237/runs/3f2a")),
+            [
+                "✗ FAILED - Python script failed",
+                "Exception: NameError: name 'x' is not defined",
+                "View run: http://127.0.0.1:8237/runs/3f2a",
+            ]
+        );
+    }
+}
@@ -1,20 +1,34 @@
 //! Terminal UI using crossterm.
 //!
-//! Simplified terminal handling - just clears screen and prints.
+//! Simplified terminal handling - just clears screen and prints. The TUI
+//! runs on the alternate screen (unless `--inline`), so the shell's own
+//! content is back untouched on exit.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
-    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    cursor::{Hide, MoveTo, MoveToPreviousLine, Show},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        MouseEventKind,
+    },
     execute,
-    style::{Color, Print, ResetColor, SetForegroundColor},
-    terminal::{self, Clear, ClearType, disable_raw_mode, enable_raw_mode},
+    style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor},
+    terminal::{
+        self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
 };
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::Duration;
 
-use crate::app_state::AppState;
-use crate::verify::VerifyOutcome;
+use crate::app_state::{AppState, CompletionStats};
+use crate::exercise::Exercise;
+use crate::keys::KeyMap;
+use crate::markdown::{self, Row};
+use crate::theme::{Status, Theme};
+use crate::timefmt;
+use crate::verify::{self, OutputView, Stream, VerifyOutcome, VerifyResult};
 
 // ============================================================================
 // Startup checklist types and rendering
@@ -64,20 +78,42 @@ fn spinner_frame(i: usize) -> &'static str {
     SPINNER_FRAMES[i % SPINNER_FRAMES.len()]
 }
 
+/// Screen rows the checklist last drew, so it can be redrawn in place
+/// without clearing the user's scrollback
+static CHECKLIST_ROWS: AtomicUsize = AtomicUsize::new(0);
+
+/// Rows a line of `text` takes up on a terminal `width` columns wide
+fn screen_rows(text: &str, width: usize) -> usize {
+    text.chars().count().max(1).div_ceil(width.max(1))
+}
+
+/// Erase the startup checklist from the screen
+pub fn clear_startup_checklist() -> Result<()> {
+    let rows = CHECKLIST_ROWS.swap(0, Ordering::Relaxed);
+    if rows > 0 {
+        let mut stdout = io::stdout();
+        execute!(stdout, MoveToPreviousLine(rows as u16), Clear(ClearType::FromCursorDown))?;
+    }
+    Ok(())
+}
+
 /// Render the startup checklist
 pub fn render_startup_checklist(
     title: &str,
     items: &[StartupCheckItem],
     footer: Option<&str>,
+    theme: &Theme,
 ) -> Result<()> {
-    // Clear screen without entering raw mode
+    // Redraw over the previous render, without entering raw mode
+    clear_startup_checklist()?;
     let mut stdout = io::stdout();
-    execute!(stdout, Clear(ClearType::All), MoveTo(0, 0))?;
+    let width = terminal::size().map(|(w, _)| w as usize).unwrap_or(80);
+    let mut rows = screen_rows(&format!("🎯 {}", title), width) + 1;
 
     // Title
     execute!(
         stdout,
-        SetForegroundColor(Color::Cyan),
+        SetForegroundColor(theme.title),
         Print(format!("🎯 {}\n\n", title)),
         ResetColor
     )?;
@@ -88,66 +124,74 @@ pub fn render_startup_checklist(
             StartupCheckStatus::Pending => {
                 execute!(
                     stdout,
-                    SetForegroundColor(Color::DarkGrey),
+                    SetForegroundColor(theme.muted),
                     Print(format!("  •  {}\n", item.label)),
                     ResetColor
                 )?;
+                rows += screen_rows(&format!("  •  {}", item.label), width);
             }
             StartupCheckStatus::Running { frame } => {
                 execute!(
                     stdout,
-                    SetForegroundColor(Color::Yellow),
+                    SetForegroundColor(theme.warning),
                     Print(format!("  {}  {}", spinner_frame(*frame), item.label)),
                     ResetColor,
                     Print("\n")
                 )?;
+                rows += screen_rows(&format!("  ◐  {}", item.label), width);
             }
             StartupCheckStatus::Passed { details } => {
                 execute!(
                     stdout,
-                    SetForegroundColor(Color::Green),
-                    Print("  ✓  "),
+                    SetForegroundColor(Status::Passed.color(theme)),
+                    Print(format!("  {}  ", Status::Passed.glyph())),
                     ResetColor,
                     Print(&item.label),
-                    SetForegroundColor(Color::DarkGrey),
+                    SetForegroundColor(theme.muted),
                     Print(format!(" — {}", details)),
                     ResetColor,
                     Print("\n")
                 )?;
+                rows += screen_rows(&format!("  {}  {} — {}", Status::Passed.glyph(), item.label, details), width);
             }
             StartupCheckStatus::Warn { details } => {
+                let marker = Status::Warn.marker();
                 execute!(
                     stdout,
-                    SetForegroundColor(Color::Yellow),
-                    Print("  !  "),
+                    SetForegroundColor(Status::Warn.color(theme)),
+                    Print(format!("  {}  ", marker)),
                     ResetColor,
                     Print(&item.label),
-                    SetForegroundColor(Color::Yellow),
+                    SetForegroundColor(Status::Warn.color(theme)),
                     Print(format!(" — {}", details)),
                     ResetColor,
                     Print("\n")
                 )?;
+                rows += screen_rows(&format!("  {}  {} — {}", marker, item.label, details), width);
             }
             StartupCheckStatus::Failed { error, help } => {
+                let marker = Status::Failed.marker();
                 execute!(
                     stdout,
-                    SetForegroundColor(Color::Red),
-                    Print("  ✗  "),
+                    SetForegroundColor(Status::Failed.color(theme)),
+                    Print(format!("  {}  ", marker)),
                     ResetColor,
                     Print(&item.label),
-                    SetForegroundColor(Color::Red),
+                    SetForegroundColor(Status::Failed.color(theme)),
                     Print(format!(" — {}", error)),
                     ResetColor,
                     Print("\n")
                 )?;
+                rows += screen_rows(&format!("  {}  {} — {}", marker, item.label, error), width);
                 // Print help lines
                 for help_line in help {
                     execute!(
                         stdout,
-                        SetForegroundColor(Color::DarkGrey),
+                        SetForegroundColor(theme.muted),
                         Print(format!("       {}\n", help_line)),
                         ResetColor
                     )?;
+                    rows += screen_rows(&format!("       {}", help_line), width);
                 }
             }
         }
@@ -158,37 +202,130 @@ pub fn render_startup_checklist(
         execute!(
             stdout,
             Print("\n"),
-            SetForegroundColor(Color::DarkGrey),
+            SetForegroundColor(theme.muted),
             Print(format!("{}\n", footer_text)),
             ResetColor
         )?;
+        rows += 1 + screen_rows(footer_text, width);
     }
 
+    CHECKLIST_ROWS.store(rows, Ordering::Relaxed);
     stdout.flush()?;
     Ok(())
 }
 
+/// What `Terminal::enter` turned on, for restoring from a signal handler
+static ALTERNATE_ACTIVE: AtomicBool = AtomicBool::new(false);
+static MOUSE_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Put the terminal back the way the shell expects it
+fn restore_terminal() {
+    let mut stdout = io::stdout();
+    if MOUSE_ACTIVE.swap(false, Ordering::Relaxed) {
+        let _ = execute!(stdout, DisableMouseCapture);
+    }
+    if ALTERNATE_ACTIVE.swap(false, Ordering::Relaxed) {
+        let _ = execute!(stdout, LeaveAlternateScreen);
+    }
+    let _ = execute!(stdout, Show, ResetColor);
+    let _ = disable_raw_mode();
+}
+
+/// Restore the terminal and exit when killed (SIGTERM, SIGHUP) or
+/// interrupted outside raw mode (SIGINT during startup checks)
+///
+/// In raw mode Ctrl-C arrives as a key press and quits through the event
+/// loop instead, so normal cleanup runs.
+#[cfg(unix)]
+pub fn restore_on_signals() -> Result<()> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+
+    let mut signals = signal_hook::iterator::Signals::new([SIGINT, SIGTERM, SIGHUP])
+        .context("Failed to install signal handlers")?;
+    std::thread::spawn(move || {
+        if let Some(signal) = signals.forever().next() {
+            restore_terminal();
+            println!();
+            std::process::exit(128 + signal);
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn restore_on_signals() -> Result<()> {
+    Ok(())
+}
+
 /// Terminal wrapper that manages raw mode lifecycle
 pub struct Terminal {
-    _private: (),
+    mouse: bool,
+    alternate: bool,
+}
+
+/// Shown when the TUI is started without a terminal (CI, pipes)
+pub const NOT_A_TERMINAL: &str =
+    "Zenlings needs an interactive terminal; use `zenlings verify` for scripting \
+     (or `zenlings list` to see progress)";
+
+/// Whether stdin and stdout are both attached to a terminal
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
 }
 
 impl Terminal {
-    /// Enter raw mode (no alternate screen - keeps it simple)
-    pub fn enter() -> Result<Self> {
-        enable_raw_mode()?;
-        // Clear screen and hide cursor
+    /// Enter raw mode, on the alternate screen if `alternate`
+    ///
+    /// With `mouse`, wheel events are captured for scrolling.
+    pub fn enter(mouse: bool, alternate: bool) -> Result<Self> {
+        let terminal = Self { mouse, alternate };
+        terminal.resume()?;
+        Ok(terminal)
+    }
+
+    /// Hand the terminal to `f` (e.g. a terminal editor), then take it back
+    pub fn suspend<T>(&self, f: impl FnOnce() -> T) -> Result<T> {
+        self.leave();
+        let result = f();
+        self.resume()?;
+        Ok(result)
+    }
+
+    fn resume(&self) -> Result<()> {
+        enable_raw_mode().context(NOT_A_TERMINAL)?;
         let mut stdout = io::stdout();
+        if self.alternate {
+            execute!(stdout, EnterAlternateScreen)?;
+            ALTERNATE_ACTIVE.store(true, Ordering::Relaxed);
+        }
+        // Clear screen and hide cursor
         execute!(stdout, Hide, Clear(ClearType::All), MoveTo(0, 0))?;
-        Ok(Self { _private: () })
+        if self.mouse {
+            execute!(stdout, EnableMouseCapture)?;
+            MOUSE_ACTIVE.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    fn leave(&self) {
+        let mut stdout = io::stdout();
+        if self.mouse {
+            let _ = execute!(stdout, DisableMouseCapture);
+            MOUSE_ACTIVE.store(false, Ordering::Relaxed);
+        }
+        if self.alternate {
+            ALTERNATE_ACTIVE.store(false, Ordering::Relaxed);
+            let _ = execute!(stdout, Show, LeaveAlternateScreen);
+        } else {
+            let _ = execute!(stdout, Show, Clear(ClearType::All), MoveTo(0, 0));
+        }
+        let _ = disable_raw_mode();
     }
 }
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        let mut stdout = io::stdout();
-        let _ = execute!(stdout, Show, Clear(ClearType::All), MoveTo(0, 0));
-        let _ = disable_raw_mode();
+        self.leave();
     }
 }
 
@@ -203,35 +340,55 @@ pub enum Action {
     Rerun,
     Solution,
     Open,
+    OpenSolution,
+    OpenPack,
+    ToggleRaw,
+    CycleOutput,
+    ToggleWatch,
+    ToggleAutoAdvance,
+    VerifySolution,
+    Undo,
+    CopyPath,
+    SaveLog,
+    Help,
+    MarkDone,
+    Info,
+    Stats,
+    ResetToSolution,
+    Skip,
+    ScrollUp,
+    ScrollDown,
     Continue,
     None,
 }
 
+/// Lines moved per mouse wheel notch
+pub const SCROLL_STEP: usize = 3;
+
 /// Poll for keyboard input with timeout
-pub fn poll_key(timeout: Duration) -> Result<Option<Action>> {
+pub fn poll_key(timeout: Duration, keys: &KeyMap) -> Result<Option<Action>> {
     if event::poll(timeout)? {
-        if let Event::Key(key) = event::read()? {
-            return Ok(Some(key_to_action(key)));
+        match event::read()? {
+            Event::Key(key) => return Ok(Some(key_to_action(key, keys))),
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => return Ok(Some(Action::ScrollUp)),
+                MouseEventKind::ScrollDown => return Ok(Some(Action::ScrollDown)),
+                _ => {}
+            },
+            _ => {}
         }
     }
     Ok(None)
 }
 
 /// Convert a key event to an action
-fn key_to_action(key: KeyEvent) -> Action {
+fn key_to_action(key: KeyEvent, keys: &KeyMap) -> Action {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
         return Action::Quit;
     }
 
     match key.code {
-        KeyCode::Char('q') => Action::Quit,
-        KeyCode::Char('h') => Action::Hint,
-        KeyCode::Char('n') => Action::Next,
-        KeyCode::Char('p') => Action::Prev,
-        KeyCode::Char('l') => Action::List,
-        KeyCode::Char('r') => Action::Rerun,
-        KeyCode::Char('s') => Action::Solution,
-        KeyCode::Char('o') => Action::Open,
+        KeyCode::Char(c) => keys.action_for(c).unwrap_or(Action::None),
         KeyCode::Enter | KeyCode::Esc => Action::Continue,
         _ => Action::None,
     }
@@ -257,211 +414,1467 @@ fn print_colored(text: &str, color: Color) -> Result<()> {
 }
 
 /// Render the main exercise view
-pub fn render_main(state: &AppState, output_buffer: &[String]) -> Result<()> {
+///
+/// `dropped_lines` is how many earlier lines of the run were evicted from
+/// `output_buffer`; a notice above the output says so. Only the streams
+/// `state.output_view` selects are shown.
+pub fn render_main(
+    state: &AppState,
+    output_buffer: &[(Stream, String)],
+    dropped_lines: usize,
+    keys: &KeyMap,
+    theme: &Theme,
+) -> Result<()> {
     clear_screen()?;
     let mut stdout = io::stdout();
     let (width, height) = terminal::size().unwrap_or((80, 24));
     let separator = "─".repeat(width as usize);
 
     // Title
-    print_colored("🎯 Zenlings", Color::Cyan)?;
-    writeln!(stdout, " - Learn ZenML Dynamic Pipelines\r")?;
+    print_colored("🎯 Zenlings", theme.title)?;
+    write!(stdout, " - Learn ZenML Dynamic Pipelines")?;
+    if state.is_read_only() {
+        print_colored(" (read-only: progress isn't saved)", theme.warning)?;
+    }
+    if state.verifying {
+        print_colored(&format!(" {}", spinner_frame(state.spinner_tick)), theme.warning)?;
+    }
+    writeln!(stdout, "\r")?;
+    let stack_line = match &state.stack_summary {
+        Some(summary) => summary.describe(),
+        None => "Stack: checking...".to_string(),
+    };
+    print_colored(&stack_line, theme.muted)?;
+    writeln!(stdout, "\r")?;
+    // Not the stack the exercise is written for: warn in the spacer row, but
+    // still verify (the learner may know what they're doing)
+    let mismatch = state
+        .stack_summary
+        .as_ref()
+        .and_then(|summary| summary.mismatch(state.current_exercise()));
+    if let Some(warning) = mismatch {
+        let line = format!("{} {}", Status::Warn.marker(), warning);
+        print_colored(truncate_line(&line, width as usize), Status::Warn.color(theme))?;
+    }
     writeln!(stdout, "\r")?;
 
     // Progress bar
     let completed = state.completed_count();
     let total = state.total_count();
     let bar_width = 30usize;
-    let filled = if total > 0 { (completed * bar_width) / total } else { 0 };
-    let empty = bar_width - filled;
+    let filled = (completed * bar_width).checked_div(total).unwrap_or(0);
+    let empty = bar_width.saturating_sub(filled);
 
     write!(stdout, "Progress: [")?;
-    print_colored(&"█".repeat(filled), Color::Green)?;
-    print_colored(&"░".repeat(empty), Color::DarkGrey)?;
-    writeln!(stdout, "] {}/{}\r", completed, total)?;
+    print_colored(&"█".repeat(filled), theme.success)?;
+    print_colored(&"░".repeat(empty), theme.muted)?;
+    write!(stdout, "] {}/{} ({}%)", completed, total, percent(completed, total))?;
+    if completed < total {
+        let remaining = remaining_label(state.remaining_count(), total, state.estimated_time_remaining());
+        print_colored(&format!("  {}", remaining), theme.muted)?;
+    }
+    if let Some(seed) = state.progress.shuffle_seed {
+        print_colored(&format!("  shuffled (seed {})", seed), theme.muted)?;
+    }
+    writeln!(stdout, "\r")?;
     writeln!(stdout, "\r")?;
 
     // Current exercise
     let exercise = state.current_exercise();
     write!(stdout, "Current exercise: ")?;
-    print_colored(&exercise.display_path(), Color::Blue)?;
+    print_colored(&exercise.display_path(), theme.highlight)?;
     writeln!(stdout, "\r")?;
+    let description = exercise
+        .description
+        .as_deref()
+        .map(|text| description_lines(text, width as usize, keys.key_for(Action::Info)))
+        .unwrap_or_default();
+    for line in &description {
+        writeln!(stdout, "{}\r", line)?;
+    }
 
     // Separator
     writeln!(stdout, "{}\r", separator)?;
 
-    // Calculate available lines for output
-    let header_lines = 8; // title, progress, exercise, separator, status line
-    let footer_lines = 3; // separator, keys
-    let max_output_lines = (height as usize).saturating_sub(header_lines + footer_lines);
+    let max_output_lines = output_rows(height).saturating_sub(description.len());
+    let output = visible_output(output_buffer, state.output_view);
+
+    // Label runs against the solution file so authors can't mistake them
+    if state.solution_check && (state.verifying || state.last_verify.is_some()) {
+        print_colored("SOLUTION CHECK ", theme.solution)?;
+    }
 
     // Status and output
     if state.verifying {
-        print_colored("⏳ RUNNING", Color::Yellow)?;
+        let elapsed = state.verify_started.map_or(0, |started| started.elapsed().as_secs());
+        print_colored(&running_label(state.spinner_tick, elapsed), theme.warning)?;
         writeln!(stdout, " - Verifying exercise...\r")?;
         writeln!(stdout, "\r")?;
 
         // Show streaming output (last N lines)
-        let start_idx = output_buffer.len().saturating_sub(max_output_lines);
-        for line in &output_buffer[start_idx..] {
-            let display = if line.len() > width as usize - 2 {
-                &line[..width as usize - 5]
-            } else {
-                line.as_str()
-            };
-            writeln!(stdout, "{}\r", display)?;
-        }
+        write_output(&output, dropped_lines, max_output_lines, state, width, keys, theme)?;
     } else if let Some(ref result) = state.last_verify {
         match result.outcome {
             VerifyOutcome::Passed => {
-                print_colored("✅ PASSED", Color::Green)?;
+                print_colored(&Status::Passed.marker(), Status::Passed.color(theme))?;
                 writeln!(stdout, " - {}\r", result.message)?;
+                write_run_url(state, theme)?;
+                let attempts = state.attempts(&result.exercise_name);
+                if !state.solution_check && attempts > 0 {
+                    print_colored(&format!("Solved in {}\r\n", attempts_label(attempts)), theme.muted)?;
+                }
                 writeln!(stdout, "\r")?;
-                writeln!(stdout, "Press 'n' to continue to the next exercise.\r")?;
+                if let Some(at) = state.auto_advance_at {
+                    let secs = at.saturating_duration_since(std::time::Instant::now()).as_millis().div_ceil(1000);
+                    writeln!(
+                        stdout,
+                        "Moving on to the next exercise in {}s (press any key to stay).\r",
+                        secs
+                    )?;
+                } else if !state.solution_check {
+                    writeln!(
+                        stdout,
+                        "Press '{}' to continue to the next exercise.\r",
+                        keys.key_for(Action::Next)
+                    )?;
+                }
 
                 // Show last few lines of output on success too
                 if !output_buffer.is_empty() {
                     writeln!(stdout, "\r")?;
-                    print_colored("Output:\r\n", Color::DarkGrey)?;
-                    let start_idx = output_buffer.len().saturating_sub(10);
-                    for line in &output_buffer[start_idx..] {
-                        let display = if line.len() > width as usize - 2 {
-                            &line[..width as usize - 5]
-                        } else {
-                            line.as_str()
-                        };
-                        writeln!(stdout, "{}\r", display)?;
-                    }
+                    print_colored("Output:\r\n", theme.muted)?;
+                    write_output(&output, dropped_lines, 10, state, width, keys, theme)?;
                 }
             }
             VerifyOutcome::Failed => {
-                print_colored("❌ FAILED", Color::Red)?;
+                print_colored(&Status::Failed.marker(), Status::Failed.color(theme))?;
                 writeln!(stdout, " - {}\r", result.message)?;
+                write_run_url(state, theme)?;
                 writeln!(stdout, "\r")?;
 
-                // Show streaming output buffer (last N lines)
-                let start_idx = output_buffer.len().saturating_sub(max_output_lines);
-                for line in &output_buffer[start_idx..] {
-                    let display = if line.len() > width as usize - 2 {
-                        &line[..width as usize - 5]
+                // Lead with the exception when the exercise raised
+                let texts: Vec<&str> = output_buffer.iter().map(|(_, line)| line.as_str()).collect();
+                let exception = verify::traceback_summary(&texts).or_else(|| {
+                    let lines: Vec<&str> = result.python_output.lines().collect();
+                    verify::traceback_summary(&lines)
+                });
+                if let Some(exception) = exception {
+                    print_colored(&exception, theme.failure)?;
+                    writeln!(stdout, "\r")?;
+                    writeln!(stdout, "\r")?;
+                }
+
+                // ZenML run details (structured summary, or raw CLI output)
+                if result.zenml_checked {
+                    if state.show_raw_zenml {
+                        for line in result.zenml_raw_output.lines() {
+                            writeln!(stdout, "{}\r", line)?;
+                        }
                     } else {
-                        line.as_str()
-                    };
-                    writeln!(stdout, "{}\r", display)?;
+                        print_colored(&result.zenml_output, theme.failure)?;
+                        writeln!(stdout, "\r")?;
+                    }
+                    print_colored(
+                        &format!(
+                            "Press '{}' to toggle raw ZenML output\r\n",
+                            keys.key_for(Action::ToggleRaw)
+                        ),
+                        theme.muted,
+                    )?;
+                    writeln!(stdout, "\r")?;
                 }
+
+                // Show streaming output buffer (last N lines)
+                write_output(&output, dropped_lines, max_output_lines, state, width, keys, theme)?;
             }
         }
+    } else if exercise.manual && state.is_completed(&exercise.name) {
+        print_colored(&format!("{} DONE", Status::Passed.glyph()), Status::Passed.color(theme))?;
+        writeln!(stdout, " - You marked this exercise complete\r")?;
+    } else if exercise.manual {
+        print_colored("Manual", theme.muted)?;
+        writeln!(
+            stdout,
+            " - Press '{}' to mark complete when you're done\r",
+            keys.key_for(Action::MarkDone)
+        )?;
     } else {
-        print_colored("Ready", Color::DarkGrey)?;
-        writeln!(stdout, " - Press 'r' to run the exercise\r")?;
+        print_colored("Ready", theme.muted)?;
+        writeln!(stdout, " - Press '{}' to run the exercise\r", keys.key_for(Action::Rerun))?;
     }
 
+    if let Some(notice) = state.current_notice() {
+        print_colored(notice, theme.success)?;
+    }
     writeln!(stdout, "\r")?;
 
     // Footer
     writeln!(stdout, "{}\r", separator)?;
-    print_colored("h", Color::DarkGrey)?;
-    write!(stdout, " hint  ")?;
-    print_colored("n", Color::DarkGrey)?;
-    write!(stdout, " next  ")?;
-    print_colored("p", Color::DarkGrey)?;
-    write!(stdout, " prev  ")?;
-    print_colored("l", Color::DarkGrey)?;
-    write!(stdout, " list  ")?;
-    print_colored("r", Color::DarkGrey)?;
-    write!(stdout, " run  ")?;
-    print_colored("s", Color::DarkGrey)?;
-    write!(stdout, " solution  ")?;
-    print_colored("o", Color::DarkGrey)?;
-    write!(stdout, " open  ")?;
-    print_colored("q", Color::DarkGrey)?;
+    print_colored(
+        truncate_line(&pipeline_line(exercise, state.last_verify.as_ref()), width as usize),
+        theme.muted,
+    )?;
+    writeln!(stdout, "\r")?;
+    let mut footer = vec![
+        (Action::Hint, "hint"),
+        (Action::Next, "next"),
+        (Action::Prev, "prev"),
+        (Action::Undo, "undo"),
+        (Action::List, "list"),
+        if exercise.manual {
+            (Action::MarkDone, "mark done")
+        } else {
+            (Action::Rerun, "run")
+        },
+        (Action::Solution, "solution"),
+        (Action::Open, "open"),
+        (Action::CopyPath, "copy path"),
+        (Action::Help, "help"),
+    ];
+    if state.author_mode {
+        footer.push((Action::VerifySolution, "check solution"));
+    }
+    for (action, label) in footer {
+        print_colored(&keys.key_for(action).to_string(), theme.muted)?;
+        write!(stdout, " {}  ", label)?;
+    }
+    print_colored(&keys.key_for(Action::ToggleWatch).to_string(), theme.muted)?;
+    write!(stdout, " watch: {}  ", if state.watch_enabled { "on" } else { "off" })?;
+    print_colored(&keys.key_for(Action::ToggleAutoAdvance).to_string(), theme.muted)?;
+    write!(stdout, " auto-next: {}  ", if state.auto_advance { "on" } else { "off" })?;
+    print_colored(&keys.key_for(Action::Quit).to_string(), theme.muted)?;
     writeln!(stdout, " quit\r")?;
 
     stdout.flush()?;
     Ok(())
 }
 
-/// Render the exercise list view
-pub fn render_list(state: &AppState) -> Result<()> {
+/// "View run: <url>" under the status line, when the run has a dashboard link
+fn write_run_url(state: &AppState, theme: &Theme) -> Result<()> {
+    if let Some(url) = state.last_run_url() {
+        print_colored(&format!("View run: {}\r\n", url), theme.muted)?;
+    }
+    Ok(())
+}
+
+/// Cut an output line that wouldn't fit in `width` columns
+///
+/// Counts characters rather than bytes, so multi-byte text (including the
+/// `�` from undecodable output) is never split mid-character.
+fn truncate_line(line: &str, width: usize) -> &str {
+    if line.chars().count() <= width.saturating_sub(2) {
+        return line;
+    }
+    match line.char_indices().nth(width.saturating_sub(5)) {
+        Some((end, _)) => &line[..end],
+        None => line,
+    }
+}
+
+/// Status badge for a run in progress, e.g. "◐ RUNNING (00:23)"
+fn running_label(spinner_tick: usize, elapsed_secs: u64) -> String {
+    format!("{} RUNNING ({})", spinner_frame(spinner_tick), timefmt::format_clock(elapsed_secs))
+}
+
+/// Share of `total` that `done` makes up, rounded down to a whole percent
+fn percent(done: usize, total: usize) -> usize {
+    (done * 100).checked_div(total).unwrap_or(0)
+}
+
+/// e.g. "4 of 12 remaining · ETA: ~20m 00s", or "ETA: —" before any
+/// exercise has a recorded time
+fn remaining_label(remaining: usize, total: usize, eta_secs: Option<u64>) -> String {
+    let eta = match eta_secs {
+        Some(secs) => format!("~{}", timefmt::format_duration(secs)),
+        None => "—".to_string(),
+    };
+    format!("{} of {} remaining · ETA: {}", remaining, total, eta)
+}
+
+/// Inline stats for a list row, e.g. "4 attempts, 3m 20s"
+fn exercise_details(attempts: u32, time_spent_secs: u64) -> String {
+    let mut details = Vec::new();
+    if attempts > 0 {
+        details.push(attempts_label(attempts));
+    }
+    if time_spent_secs > 0 {
+        details.push(timefmt::format_duration(time_spent_secs));
+    }
+    details.join(", ")
+}
+
+/// "Pipeline: <name>", plus the run the last verification checked
+fn pipeline_line(exercise: &Exercise, last_verify: Option<&VerifyResult>) -> String {
+    if exercise.manual {
+        return "Pipeline: none (manual exercise)".to_string();
+    }
+    match last_verify.and_then(|result| result.run_id.as_deref()) {
+        Some(run_id) => format!("Pipeline: {}  ·  Last run: {}", exercise.pipeline_name, run_id),
+        None => format!("Pipeline: {}", exercise.pipeline_name),
+    }
+}
+
+/// "1 attempt" / "4 attempts"
+fn attempts_label(attempts: u32) -> String {
+    if attempts == 1 {
+        "1 attempt".to_string()
+    } else {
+        format!("{} attempts", attempts)
+    }
+}
+
+/// Rows available for exercise output in the main view
+fn output_rows(height: u16) -> usize {
+    let header_lines = 9; // title, stack, progress, exercise, separator, status line
+    let footer_lines = 4; // separator, pipeline, keys
+    (height as usize).saturating_sub(header_lines + footer_lines)
+}
+
+/// Largest useful output scroll offset for the current terminal size
+pub fn max_output_scroll(line_count: usize, exercise: &Exercise) -> usize {
+    let (width, height) = terminal::size().unwrap_or((80, 24));
+    let description_rows = exercise
+        .description
+        .as_deref()
+        .map_or(0, |text| description_lines(text, width as usize, ' ').len());
+    line_count.saturating_sub(output_rows(height).saturating_sub(description_rows))
+}
+
+/// Most rows the exercise description takes up in the main view
+const DESCRIPTION_ROWS: usize = 2;
+
+/// The exercise description wrapped to `width`, cut to `DESCRIPTION_ROWS`
+/// with a pointer to the `info_key` modal if it's longer
+fn description_lines(text: &str, width: usize, info_key: char) -> Vec<String> {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut lines = wrap_line(&text, width);
+    if lines.len() > DESCRIPTION_ROWS {
+        lines.truncate(DESCRIPTION_ROWS);
+        let more = format!("… press '{}' for more", info_key);
+        let keep = width.saturating_sub(more.chars().count());
+        let last = &lines[DESCRIPTION_ROWS - 1];
+        let mut cut: String = last.chars().take(keep).collect();
+        // Don't end on half a word
+        if last.chars().nth(keep).is_some_and(|c| c != ' ') {
+            if let Some(space) = cut.rfind(' ') {
+                cut.truncate(space);
+            }
+        }
+        lines[DESCRIPTION_ROWS - 1] = format!("{}{}", cut.trim_end(), more);
+    }
+    lines
+}
+
+/// The `rows` lines of output ending `scroll` lines above the newest one
+fn output_window<T>(lines: &[T], rows: usize, scroll: usize) -> &[T] {
+    let end = lines.len().saturating_sub(scroll);
+    &lines[end.saturating_sub(rows)..end]
+}
+
+/// The lines of `buffer` from the streams `view` shows
+fn visible_output(buffer: &[(Stream, String)], view: OutputView) -> Vec<(Stream, &str)> {
+    buffer
+        .iter()
+        .filter(|(stream, _)| view.shows(*stream))
+        .map(|(stream, line)| (*stream, line.as_str()))
+        .collect()
+}
+
+/// Notice for output lines that no longer fit in the buffer
+fn hidden_lines_label(dropped: usize) -> String {
+    if dropped == 1 {
+        "… 1 earlier line hidden".to_string()
+    } else {
+        format!("… {} earlier lines hidden", dropped)
+    }
+}
+
+/// Parts of a Python traceback worth coloring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TracebackPart {
+    /// `Traceback (most recent call last):` and `  File "...", line N` frames
+    Location,
+    /// The final `SomeError: message` line
+    Error,
+}
+
+/// Classify each output line as part of a traceback or not
+///
+/// Works on partial output: a frame line starts a traceback even when the
+/// header hasn't arrived (or was dropped), and an exception line only counts
+/// once it follows one.
+fn traceback_parts<S: AsRef<str>>(lines: &[S]) -> Vec<Option<TracebackPart>> {
+    let mut in_traceback = false;
+    lines
+        .iter()
+        .map(|line| {
+            let line = line.as_ref().trim_end();
+            if line == "Traceback (most recent call last):" || line.starts_with("  File \"") {
+                in_traceback = true;
+                Some(TracebackPart::Location)
+            } else if in_traceback && !line.is_empty() && !line.starts_with(char::is_whitespace) {
+                // Frames and source lines are indented; the exception line is not
+                in_traceback = false;
+                Some(TracebackPart::Error)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Print `rows` rows of output, scrolled up by `state.output_scroll`, under
+/// notices of how many earlier lines were dropped and which streams
+/// `state.output_view` hides (each takes one of the rows)
+///
+/// Traceback frames are colored like locations and the exception like a
+/// failure; other stderr lines get the warning color.
+fn write_output(
+    lines: &[(Stream, &str)],
+    dropped: usize,
+    rows: usize,
+    state: &AppState,
+    width: u16,
+    keys: &KeyMap,
+    theme: &Theme,
+) -> Result<()> {
+    let mut stdout = io::stdout();
+    let (view, scroll) = (state.output_view, state.output_scroll);
+    let mut rows = rows;
+    if view != OutputView::All && rows > 0 {
+        let notice = format!("Showing {} ('{}' cycles)", view.label(), keys.key_for(Action::CycleOutput));
+        print_colored(&format!("{}\r\n", notice), theme.muted)?;
+        rows -= 1;
+    }
+    if dropped > 0 && rows > 0 {
+        print_colored(&format!("{}\r\n", hidden_lines_label(dropped)), theme.muted)?;
+        rows -= 1;
+    }
+    let window = output_window(lines, rows, scroll);
+    // Classify from the start so a traceback scrolled partly out of view keeps its colors
+    let end = lines.len().saturating_sub(scroll);
+    let texts: Vec<&str> = lines[..end].iter().map(|(_, line)| *line).collect();
+    let parts = traceback_parts(&texts);
+    for ((stream, line), part) in window.iter().zip(&parts[end - window.len()..]) {
+        let line = truncate_line(line, width as usize);
+        match (part, stream) {
+            (Some(TracebackPart::Location), _) => print_colored(&format!("{}\r\n", line), theme.highlight)?,
+            (Some(TracebackPart::Error), _) => print_colored(&format!("{}\r\n", line), theme.failure)?,
+            (None, Stream::Stderr) => print_colored(&format!("{}\r\n", line), theme.warning)?,
+            (None, Stream::Stdout) => writeln!(stdout, "{}\r", line)?,
+        }
+    }
+    Ok(())
+}
+
+/// Show the exercise list until dismissed; scrolls with the mouse wheel
+///
+/// ↑/↓ move the selection and Enter returns the selected exercise's index so
+/// the caller can jump there; Esc or the list key closes the list without
+/// moving. `/` starts a filter: typing narrows the list to exercises whose
+/// name or directory contains the query, Backspace edits it and Esc clears it.
+pub fn show_list(state: &AppState, keys: &KeyMap, theme: &Theme) -> Result<Option<usize>> {
+    let (_, height) = terminal::size().unwrap_or((80, 24));
+    let rows = (height as usize).saturating_sub(6).max(1);
+    let mut query = String::new();
+    let mut filtering = false;
+    let mut selected = Some(state.current_index);
+    let mut offset = 0;
+    // Scroll the selection into view on the next render
+    let mut follow = true;
+
+    loop {
+        let matches = filter_exercises(&state.exercises, &query);
+        if !selected.is_some_and(|idx| matches.contains(&idx)) {
+            selected = matches.first().copied();
+        }
+        let lines = list_lines(&state.exercises, &matches);
+        if follow {
+            let selected_line = selected.map(ListLine::Exercise);
+            if let Some(line) = lines.iter().position(|l| Some(l) == selected_line.as_ref()) {
+                offset = keep_in_view(line, offset, rows);
+            }
+            follow = false;
+        }
+        let max_offset = lines.len().saturating_sub(rows);
+        offset = offset.min(max_offset);
+        let filter = filtering.then_some(query.as_str());
+        render_list(state, &lines, selected, filter, offset, rows, theme)?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            Event::Mouse(mouse) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollUp => offset = offset.saturating_sub(SCROLL_STEP),
+                    MouseEventKind::ScrollDown => offset = (offset + SCROLL_STEP).min(max_offset),
+                    _ => {}
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        let typing = filtering && !key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Up => {
+                selected = step_selection(&matches, selected, -1);
+                follow = true;
+            }
+            KeyCode::Down => {
+                selected = step_selection(&matches, selected, 1);
+                follow = true;
+            }
+            KeyCode::Enter => {
+                if selected.is_some() {
+                    return Ok(selected);
+                }
+            }
+            KeyCode::Esc if filtering => {
+                query.clear();
+                filtering = false;
+                follow = true;
+            }
+            KeyCode::Char(c) if typing => {
+                query.push(c);
+                // Select the best (first) match as the query changes
+                selected = None;
+                follow = true;
+            }
+            KeyCode::Backspace if typing => {
+                query.pop();
+                selected = None;
+                follow = true;
+            }
+            KeyCode::Char('/') => filtering = true,
+            _ => match key_to_action(key, keys) {
+                Action::Continue | Action::List | Action::Quit => return Ok(None),
+                _ => {}
+            },
+        }
+    }
+}
+
+/// Move the selection `delta` places through `matches`, stopping at the ends
+fn step_selection(matches: &[usize], selected: Option<usize>, delta: isize) -> Option<usize> {
+    let pos = selected.and_then(|idx| matches.iter().position(|&m| m == idx))?;
+    let new_pos = pos.saturating_add_signed(delta).min(matches.len() - 1);
+    Some(matches[new_pos])
+}
+
+/// Scroll offset that keeps row `line` within a window of `rows` rows
+fn keep_in_view(line: usize, offset: usize, rows: usize) -> usize {
+    if line < offset {
+        line
+    } else if line >= offset + rows {
+        line + 1 - rows
+    } else {
+        offset
+    }
+}
+
+/// Indices of exercises whose name or directory contains `query`, ignoring case
+fn filter_exercises(exercises: &[Exercise], query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    exercises
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| {
+            e.name.to_lowercase().contains(&query)
+                || e.dir.to_lowercase().contains(&query)
+                || e.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
+        })
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// A row of the exercise list
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ListLine {
+    /// Start of a run of exercises in this directory
+    Header(String),
+    /// The exercise at this index
+    Exercise(usize),
+}
+
+/// List rows for the exercises in `matches`, with a header before each directory
+fn list_lines(exercises: &[Exercise], matches: &[usize]) -> Vec<ListLine> {
+    let mut lines = Vec::new();
+    let mut dir: Option<&str> = None;
+    for &idx in matches {
+        let exercise = &exercises[idx];
+        if dir != Some(exercise.dir.as_str()) {
+            dir = Some(&exercise.dir);
+            lines.push(ListLine::Header(exercise.dir.clone()));
+        }
+        lines.push(ListLine::Exercise(idx));
+    }
+    lines
+}
+
+/// "#map #io": an exercise's tags as shown in the list
+fn tags_label(tags: &[String]) -> String {
+    tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" ")
+}
+
+/// List marker for an exercise skipped without being solved
+const SKIPPED_GLYPH: &str = "»";
+
+/// "skipped" or "skipped: <reason>", after a skipped exercise in the list
+fn skipped_label(reason: Option<&str>) -> String {
+    match reason {
+        Some(reason) => format!("skipped: {}", reason),
+        None => "skipped".to_string(),
+    }
+}
+
+/// Byte range of the first case-insensitive occurrence of `query` in `text`
+fn match_range(text: &str, query: &str) -> Option<std::ops::Range<usize>> {
+    if query.is_empty() {
+        return None;
+    }
+    // ASCII lowercasing keeps byte offsets lined up with `text`
+    let start = text.to_ascii_lowercase().find(&query.to_ascii_lowercase())?;
+    let end = start + query.len();
+    text.is_char_boundary(end).then_some(start..end)
+}
+
+/// Render the exercise list view, starting at row `offset` of `lines`
+///
+/// `filter` is the query being typed, while filtering.
+fn render_list(
+    state: &AppState,
+    lines: &[ListLine],
+    selected: Option<usize>,
+    filter: Option<&str>,
+    offset: usize,
+    rows: usize,
+    theme: &Theme,
+) -> Result<()> {
     clear_screen()?;
     let mut stdout = io::stdout();
 
-    print_colored("📋 Exercise List\r\n\r\n", Color::Cyan)?;
+    print_colored("📋 Exercise List\r\n", theme.title)?;
+    if let Some(query) = filter {
+        print_colored("/", theme.warning)?;
+        writeln!(stdout, "{}_\r", query)?;
+    } else {
+        writeln!(stdout, "\r")?;
+    }
 
-    for (idx, exercise) in state.exercises.iter().enumerate() {
+    for line in lines.iter().skip(offset).take(rows) {
+        let idx = match line {
+            ListLine::Header(dir) => {
+                let (completed, total) = state.section_progress(dir);
+                print_colored(&format!("── {} ──", state.section_title(dir)), theme.title)?;
+                print_colored(&format!("  {}/{}\r\n", completed, total), theme.muted)?;
+                continue;
+            }
+            ListLine::Exercise(idx) => *idx,
+        };
+        let exercise = &state.exercises[idx];
         let is_current = idx == state.current_index;
         let is_completed = state.is_completed(&exercise.name);
 
-        let icon = if is_completed { "✅" } else { "⬜" };
         let marker = if is_current { "→ " } else { "  " };
 
         if is_current {
-            print_colored(marker, Color::Cyan)?;
-            write!(stdout, "{} {:2}. ", icon, idx + 1)?;
-            print_colored(&exercise.display_path(), Color::Cyan)?;
-            writeln!(stdout, "\r")?;
+            print_colored(marker, theme.title)?;
+        } else {
+            write!(stdout, "{}", marker)?;
+        }
+        write!(stdout, "[")?;
+        if is_completed {
+            print_colored(Status::Passed.glyph(), Status::Passed.color(theme))?;
+        } else if state.is_skipped(&exercise.name) {
+            print_colored(SKIPPED_GLYPH, theme.warning)?;
         } else {
-            write!(stdout, "{}{} {:2}. {}\r\n", marker, icon, idx + 1, exercise.display_path())?;
+            write!(stdout, " ")?;
+        }
+        write!(stdout, "] {:2}. ", idx + 1)?;
+
+        let path = exercise.display_path();
+        let path_color = if is_current { theme.title } else { Color::Reset };
+        let is_selected = selected == Some(idx);
+        if is_selected {
+            execute!(stdout, SetAttribute(Attribute::Reverse))?;
+        }
+        match match_range(&path, filter.unwrap_or_default()) {
+            Some(range) => {
+                print_colored(&path[..range.start], path_color)?;
+                print_colored(&path[range.clone()], theme.highlight)?;
+                print_colored(&path[range.end..], path_color)?;
+            }
+            None => print_colored(&path, path_color)?,
+        }
+        if is_selected {
+            execute!(stdout, SetAttribute(Attribute::NoReverse))?;
+        }
+
+        if !exercise.tags.is_empty() {
+            print_colored(&format!("  {}", tags_label(&exercise.tags)), theme.highlight)?;
+        }
+        let details = exercise_details(
+            state.attempts(&exercise.name),
+            state.time_spent_secs(&exercise.name),
+        );
+        if !details.is_empty() {
+            print_colored(&format!("  ({})", details), theme.muted)?;
+        }
+        if state.is_skipped(&exercise.name) && !is_completed {
+            print_colored(&format!("  {}", skipped_label(state.skip_reason(&exercise.name))), theme.warning)?;
         }
+        writeln!(stdout, "\r")?;
+    }
+    if lines.is_empty() {
+        print_colored("No exercises match.\r\n", theme.muted)?;
     }
 
     writeln!(stdout, "\r")?;
-    print_colored("Press any key to return...\r\n", Color::DarkGrey)?;
+    if filter.is_some() {
+        print_colored("↑/↓ select, Enter to jump, Esc to clear the filter\r\n", theme.muted)?;
+    } else {
+        print_colored("↑/↓ select, Enter to jump, / to filter, Esc to return...\r\n", theme.muted)?;
+    }
 
     stdout.flush()?;
     Ok(())
 }
 
-/// Render a modal with text (for hints/solutions)
-pub fn render_modal(title: &str, content: &str) -> Result<()> {
+/// Maximum number of content rows on one modal page
+const MODAL_PAGE_ROWS: usize = 30;
+
+/// Show a modal with text (for hints/solutions) and wait for it to be dismissed
+///
+/// With `markdown` set the content is rendered as Markdown; otherwise it is
+/// shown as written. Long lines are word-wrapped; content that doesn't fit
+/// on one page can be paged through with Space/→ and ←.
+pub fn show_modal(
+    title: &str,
+    content: &str,
+    markdown: bool,
+    keys: &KeyMap,
+    theme: &Theme,
+) -> Result<()> {
+    let (width, height) = terminal::size().unwrap_or((80, 24));
+    let rows = render_content(content, markdown, (width as usize).saturating_sub(4));
+    let page_rows = MODAL_PAGE_ROWS.min((height as usize).saturating_sub(6)).max(1);
+    let pages: Vec<&[Row]> = if rows.is_empty() {
+        vec![&[]]
+    } else {
+        rows.chunks(page_rows).collect()
+    };
+
+    let mut page = 0;
+    loop {
+        render_modal(title, pages[page], page, pages.len(), theme)?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        match key.code {
+            KeyCode::Char(' ') | KeyCode::Right | KeyCode::PageDown if page + 1 < pages.len() => {
+                page += 1;
+            }
+            KeyCode::Left | KeyCode::PageUp if page > 0 => page -= 1,
+            _ => {
+                if let Action::Continue | Action::Quit = key_to_action(key, keys) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Wrap content into rows, rendering Markdown if enabled
+fn render_content(content: &str, markdown: bool, width: usize) -> Vec<Row> {
+    if markdown {
+        markdown::render(content, width)
+    } else {
+        markdown::plain(content, width)
+    }
+}
+
+/// Print one styled row
+fn print_row(row: &Row, theme: &Theme) -> Result<()> {
+    let mut stdout = io::stdout();
+    for span in row {
+        match span.style.color(theme) {
+            Some(color) => print_colored(&span.text, color)?,
+            None => write!(stdout, "{}", span.text)?,
+        }
+    }
+    writeln!(stdout, "\r")?;
+    Ok(())
+}
+
+/// Render one page of an already-wrapped modal
+fn render_modal(
+    title: &str,
+    rows: &[Row],
+    page: usize,
+    page_count: usize,
+    theme: &Theme,
+) -> Result<()> {
     clear_screen()?;
     let mut stdout = io::stdout();
-    let (width, _) = terminal::size().unwrap_or((80, 24));
 
     // Title
-    print_colored(&format!("💡 {}\r\n\r\n", title), Color::Yellow)?;
+    print_colored(&format!("💡 {}\r\n\r\n", title), theme.warning)?;
 
-    // Content - simple line-by-line with basic wrapping
-    for line in content.lines().take(30) {
-        if line.is_empty() {
-            writeln!(stdout, "\r")?;
-        } else {
-            // Simple truncation for now
-            let display = if line.len() > width as usize - 4 {
-                &line[..width as usize - 7]
-            } else {
-                line
-            };
-            writeln!(stdout, "  {}\r", display)?;
+    for row in rows {
+        if !row.is_empty() {
+            write!(stdout, "  ")?;
         }
+        print_row(row, theme)?;
     }
 
     writeln!(stdout, "\r")?;
-    print_colored("Press Enter or Esc to return...\r\n", Color::DarkGrey)?;
+    if page_count > 1 {
+        print_colored(
+            &format!(
+                "Page {}/{} - Space/→ next, ← back, Enter or Esc to return...\r\n",
+                page + 1,
+                page_count
+            ),
+            theme.muted,
+        )?;
+    } else {
+        print_colored("Press Enter or Esc to return...\r\n", theme.muted)?;
+    }
 
     stdout.flush()?;
     Ok(())
 }
 
-/// Render the welcome message
-pub fn render_welcome(message: &str) -> Result<()> {
-    render_modal("Welcome to Zenlings!", message)
+/// Word-wrap a line to at most `width` characters per row
+///
+/// Breaks at spaces where possible and hard-breaks tokens longer than a
+/// whole row. Leading indentation is kept on the first row.
+pub(crate) fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    if line.chars().count() <= width {
+        return vec![line.to_string()];
+    }
+
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    let mut row_len = 0;
+    let mut row_started = false;
+
+    for word in line.split(' ') {
+        // Don't start a continuation row with the spaces we broke at
+        if !row_started && word.is_empty() && !rows.is_empty() {
+            continue;
+        }
+
+        let word_len = word.chars().count();
+        if row_started && row_len + 1 + word_len > width {
+            rows.push(row.trim_end().to_string());
+            row.clear();
+            row_len = 0;
+            row_started = false;
+        }
+        if row_started {
+            row.push(' ');
+            row_len += 1;
+        }
+
+        let chars: Vec<char> = word.chars().collect();
+        let mut rest = &chars[..];
+        while row_len + rest.len() > width {
+            let n = width - row_len;
+            row.extend(&rest[..n]);
+            rows.push(std::mem::take(&mut row));
+            row_len = 0;
+            rest = &rest[n..];
+        }
+        row.extend(rest);
+        row_len += rest.len();
+        row_started = true;
+    }
+
+    if row_started {
+        rows.push(row.trim_end().to_string());
+    }
+    rows
+}
+
+/// Ask a yes/no question and wait for the answer
+///
+/// `y` answers yes; `n`, Enter, Esc or Ctrl-C answer no.
+pub fn confirm(question: &str, theme: &Theme) -> Result<bool> {
+    Ok(ask(question, theme)?.unwrap_or(false))
+}
+
+/// Ask whether to quit; like `confirm`, except Ctrl-C answers yes, so
+/// pressing it twice force-quits
+pub fn confirm_quit(question: &str, theme: &Theme) -> Result<bool> {
+    Ok(ask(question, theme)?.unwrap_or(true))
 }
 
-/// Render the completion message
-pub fn render_complete(message: &str) -> Result<()> {
+/// Show a y/n question; `None` if it was answered with Ctrl-C
+fn ask(question: &str, theme: &Theme) -> Result<Option<bool>> {
     clear_screen()?;
     let mut stdout = io::stdout();
+    print_colored(&format!("⚠️  {}", question), theme.warning)?;
+    write!(stdout, " (y/n)\r\n")?;
+    stdout.flush()?;
 
-    print_colored("🎉 Congratulations!\r\n\r\n", Color::Green)?;
+    wait_for_answer()
+}
 
-    for line in message.lines() {
-        writeln!(stdout, "{}\r", line)?;
+/// Ask for a line of text (raw mode must be on)
+///
+/// Enter submits what was typed, possibly nothing; Esc or Ctrl-C cancels
+/// with `None`.
+pub fn prompt_text(question: &str, theme: &Theme) -> Result<Option<String>> {
+    let mut text = String::new();
+    loop {
+        clear_screen()?;
+        let mut stdout = io::stdout();
+        print_colored(question, theme.warning)?;
+        write!(stdout, "\r\n\r\n> {}", text)?;
+        print_colored("\r\n\r\nEnter to confirm, Esc to cancel", theme.muted)?;
+        stdout.flush()?;
+
+        let key = loop {
+            if let Event::Key(key) = event::read()? {
+                break key;
+            }
+        };
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(Some(text)),
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Wait for a y/n key press (raw mode must be on); `None` for Ctrl-C
+fn wait_for_answer() -> Result<Option<bool>> {
+    loop {
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(None);
+        }
+        match key.code {
+            KeyCode::Char('y' | 'Y') => return Ok(Some(true)),
+            KeyCode::Char('n' | 'N') | KeyCode::Enter | KeyCode::Esc => return Ok(Some(false)),
+            _ => {}
+        }
+    }
+}
+
+/// Wait for a y/n answer outside the TUI, e.g. to a question in the
+/// startup checklist's footer
+///
+/// Raw mode is only on while waiting, so a single key press answers.
+/// Ctrl-C is an error, stopping startup.
+pub fn read_answer() -> Result<bool> {
+    enable_raw_mode().context(NOT_A_TERMINAL)?;
+    let answer = wait_for_answer();
+    disable_raw_mode()?;
+    answer?.context("Cancelled")
+}
+
+/// Show the welcome message and wait for it to be dismissed
+pub fn show_welcome(message: &str, markdown: bool, keys: &KeyMap, theme: &Theme) -> Result<()> {
+    show_modal("Welcome to Zenlings!", message, markdown, keys, theme)
+}
+
+/// Built-in introduction for packs without a welcome message
+pub fn getting_started(keys: &KeyMap) -> String {
+    let key = |action| keys.key_for(action);
+    format!(
+        "# Getting started\n\
+         \n\
+         Each exercise is a Python file with a pipeline to fix or finish. Open it in \
+         your editor, make a change and save: Zenlings re-runs it and checks the \
+         pipeline's latest run. When it passes, move on to the next one.\n\
+         \n\
+         # Keys\n\
+         \n\
+         - `{}` run the exercise again\n\
+         - `{}` / `{}` next / previous exercise\n\
+         - `{}` hint, `{}` solution\n\
+         - `{}` list all exercises\n\
+         - `{}` open the exercise, `{}` copy its path\n\
+         - `{}` pause or resume re-running on save\n\
+         - `{}` show all output, only stderr or only stdout\n\
+         - `{}` turn moving on by itself after a pass on or off\n\
+         - `{}` skip to the next unsolved exercise (it stays unsolved; the list marks it {})\n\
+         - `{}` undo the last move or completion\n\
+         - `{}` replace the exercise with its solution (once solved, or after {} tries)\n\
+         - `{}` show this again\n\
+         - `{}` quit",
+        key(Action::Rerun),
+        key(Action::Next),
+        key(Action::Prev),
+        key(Action::Hint),
+        key(Action::Solution),
+        key(Action::List),
+        key(Action::Open),
+        key(Action::CopyPath),
+        key(Action::ToggleWatch),
+        key(Action::CycleOutput),
+        key(Action::ToggleAutoAdvance),
+        key(Action::Skip),
+        SKIPPED_GLYPH,
+        key(Action::Undo),
+        key(Action::ResetToSolution),
+        crate::app_state::RESET_MIN_ATTEMPTS,
+        key(Action::Help),
+        key(Action::Quit),
+    )
+}
+
+/// Render the completion message followed by the learner's stats
+pub fn render_complete(
+    message: &str,
+    stats: &CompletionStats,
+    markdown: bool,
+    theme: &Theme,
+) -> Result<()> {
+    clear_screen()?;
+    let mut stdout = io::stdout();
+    let (width, _) = terminal::size().unwrap_or((80, 24));
+
+    print_colored("🎉 Congratulations!\r\n\r\n", theme.success)?;
+
+    for row in render_content(message, markdown, width as usize) {
+        print_row(&row, theme)?;
     }
 
     writeln!(stdout, "\r")?;
-    print_colored("Press 'q' to quit or 'l' to view exercise list...\r\n", Color::DarkGrey)?;
+    print_colored("Your journey\r\n", theme.title)?;
+    for (label, value) in completion_stat_rows(stats) {
+        print_colored(&format!("  {:<18}", label), theme.muted)?;
+        writeln!(stdout, "{}\r", value)?;
+    }
+    if stats.no_solutions_viewed {
+        writeln!(stdout, "\r")?;
+        print_colored("🏅 Honest completion: you never peeked at a solution!\r\n", theme.success)?;
+    }
 
+    writeln!(stdout, "\r")?;
+    print_colored("Press 'q' to quit or 'l' to view exercise list...\r\n", theme.muted)?;
+
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Show the progress dashboard until a key other than ↑/↓ is pressed
+pub fn show_stats(state: &AppState, theme: &Theme) -> Result<()> {
+    let mut offset = 0;
+    loop {
+        let (_, height) = terminal::size().unwrap_or((80, 24));
+        // Title, summary block, table header and footer
+        let rows = (height as usize).saturating_sub(14).max(1);
+        let max_offset = state.exercises.len().saturating_sub(rows);
+        render_stats(state, offset, rows, theme)?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Up => offset = offset.saturating_sub(1),
+                KeyCode::Down => offset = (offset + 1).min(max_offset),
+                _ => return Ok(()),
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => offset = offset.saturating_sub(SCROLL_STEP),
+                MouseEventKind::ScrollDown => offset = (offset + SCROLL_STEP).min(max_offset),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Render the dashboard: overall totals, then one table row per exercise
+/// starting at `offset`
+fn render_stats(state: &AppState, offset: usize, rows: usize, theme: &Theme) -> Result<()> {
+    clear_screen()?;
+    let mut stdout = io::stdout();
+    let (width, _) = terminal::size().unwrap_or((80, 24));
+    let stats = state.completion_stats();
+
+    print_colored("📊 Your progress\r\n\r\n", theme.title)?;
+    let percent_done = percent(stats.solved, stats.total);
+    let mut summary = completion_stat_rows(&stats);
+    summary[0].1 = format!("{}/{} ({}%)", stats.solved, stats.total, percent_done);
+    summary.push(("Total attempts", stats.total_attempts.to_string()));
+    for (label, value) in summary {
+        print_colored(&format!("  {:<18}", label), theme.muted)?;
+        writeln!(stdout, "{}\r", value)?;
+    }
+
+    writeln!(stdout, "\r")?;
+    print_colored(&format!("{}\r\n", stats_header()), theme.title)?;
+    for (i, exercise) in state.exercises.iter().enumerate().skip(offset).take(rows) {
+        let done = state.is_completed(&exercise.name);
+        let mark = if done { Status::Passed.glyph() } else { " " };
+        print_colored(&format!("{} ", mark), Status::Passed.color(theme))?;
+        let row = stats_row(
+            i + 1,
+            &exercise.display_path(),
+            state.attempts(&exercise.name),
+            crate::hints::hints_used_count(&state.progress, &exercise.name),
+            state.time_spent_secs(&exercise.name),
+        );
+        writeln!(stdout, "{}\r", truncate_line(&row, width as usize))?;
+    }
+
+    writeln!(stdout, "\r")?;
+    let scroll = if state.exercises.len() > rows { "↑/↓ scroll, " } else { "" };
+    print_colored(&format!("{}press any other key to return\r\n", scroll), theme.muted)?;
     stdout.flush()?;
     Ok(())
 }
+
+/// Column headings matching `stats_row` (after the 2-column done mark)
+fn stats_header() -> String {
+    format!("  {:>3}  {:<32} {:>8} {:>5} {:>8}", "#", "Exercise", "Attempts", "Hints", "Time")
+}
+
+/// One exercise's line in the stats table
+fn stats_row(number: usize, path: &str, attempts: u32, hints: u32, time_spent_secs: u64) -> String {
+    let time = if time_spent_secs > 0 {
+        timefmt::format_duration(time_spent_secs)
+    } else {
+        "-".to_string()
+    };
+    format!("{:>3}. {:<32} {:>8} {:>5} {:>8}", number, path, attempts, hints, time)
+}
+
+/// Label/value pairs for the completion screen's stats block
+fn completion_stat_rows(stats: &CompletionStats) -> Vec<(&'static str, String)> {
+    vec![
+        ("Exercises solved", format!("{}/{}", stats.solved, stats.total)),
+        ("Time spent", timefmt::format_duration(stats.time_spent_secs)),
+        ("Hints used", stats.hints_used.to_string()),
+        (
+            "Average attempts",
+            stats
+                .average_attempts
+                .map_or_else(|| "-".to_string(), |avg| format!("{:.1}", avg)),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exercises from (dir, name) pairs
+    fn test_exercises(pairs: &[(&str, &str)]) -> Vec<Exercise> {
+        pairs
+            .iter()
+            .map(|(dir, name)| {
+                let entry = crate::exercise::ExerciseEntry {
+                    name: name.to_string(),
+                    dir: dir.to_string(),
+                    ..Default::default()
+                };
+                Exercise::from_entry(&entry, std::path::Path::new("/tmp/zenlings"), None)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_exercises_matches_name_or_dir() {
+        let exercises = test_exercises(&[
+            ("01_basics", "hello_step"),
+            ("02_data", "load_CSV"),
+            ("03_steps", "params"),
+        ]);
+
+        assert_eq!(filter_exercises(&exercises, ""), [0, 1, 2]);
+        assert_eq!(filter_exercises(&exercises, "step"), [0, 2]);
+        assert_eq!(filter_exercises(&exercises, "csv"), [1]);
+        assert!(filter_exercises(&exercises, "nope").is_empty());
+    }
+
+    #[test]
+    fn test_filter_exercises_matches_tags() {
+        let mut exercises = test_exercises(&[("01_basics", "hello_step"), ("02_data", "load_csv")]);
+        exercises[1].tags = vec!["Artifacts".to_string(), "io".to_string()];
+
+        assert_eq!(filter_exercises(&exercises, "artif"), [1]);
+        assert_eq!(tags_label(&exercises[1].tags), "#Artifacts #io");
+    }
+
+    #[test]
+    fn test_list_lines_group_by_dir() {
+        let exercises = test_exercises(&[("01_a", "x"), ("01_a", "y"), ("02_b", "z")]);
+
+        let header = |dir: &str| ListLine::Header(dir.to_string());
+        assert_eq!(
+            list_lines(&exercises, &[0, 1, 2]),
+            [
+                header("01_a"),
+                ListLine::Exercise(0),
+                ListLine::Exercise(1),
+                header("02_b"),
+                ListLine::Exercise(2),
+            ]
+        );
+        assert_eq!(list_lines(&exercises, &[2]), [header("02_b"), ListLine::Exercise(2)]);
+        assert!(list_lines(&exercises, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_step_selection_stops_at_ends() {
+        let matches = [1, 4, 7];
+        assert_eq!(step_selection(&matches, Some(4), 1), Some(7));
+        assert_eq!(step_selection(&matches, Some(7), 1), Some(7));
+        assert_eq!(step_selection(&matches, Some(4), -1), Some(1));
+        assert_eq!(step_selection(&matches, Some(1), -1), Some(1));
+        assert_eq!(step_selection(&matches, None, 1), None);
+    }
+
+    #[test]
+    fn test_keep_in_view() {
+        assert_eq!(keep_in_view(5, 0, 10), 0);
+        assert_eq!(keep_in_view(12, 0, 10), 3);
+        assert_eq!(keep_in_view(2, 5, 10), 2);
+    }
+
+    #[test]
+    fn test_truncate_line_respects_char_boundaries() {
+        assert_eq!(truncate_line("short", 80), "short");
+        assert_eq!(truncate_line("abcdefghij", 10), "abcde");
+        assert_eq!(truncate_line("ééééééééééé", 10), "ééééé");
+        assert_eq!(truncate_line("\u{fffd}".repeat(20).as_str(), 10), "\u{fffd}".repeat(5));
+    }
+
+    #[test]
+    fn test_getting_started_uses_current_bindings() {
+        let text = getting_started(&KeyMap::default());
+        assert!(text.contains("- `r` run the exercise again"));
+        assert!(text.contains("- `?` show this again"));
+
+        let keys = KeyMap::from_toml("next = \"j\"").unwrap();
+        assert!(getting_started(&keys).contains("- `j` / `p` next / previous exercise"));
+    }
+
+    #[test]
+    fn test_screen_rows() {
+        assert_eq!(screen_rows("", 80), 1);
+        assert_eq!(screen_rows("short", 80), 1);
+        assert_eq!(screen_rows(&"x".repeat(80), 80), 1);
+        assert_eq!(screen_rows(&"x".repeat(81), 80), 2);
+        assert_eq!(screen_rows("✓ wide", 0), 6);
+    }
+
+    #[test]
+    fn test_running_label() {
+        assert_eq!(running_label(0, 23), "◐ RUNNING (00:23)");
+        assert_eq!(running_label(1, 3723), "◓ RUNNING (1:02:03)");
+    }
+
+    #[test]
+    fn test_description_lines() {
+        assert_eq!(description_lines("Make it  dynamic.", 40, 'i'), ["Make it dynamic."]);
+        assert_eq!(
+            description_lines("Map a step over every input file", 20, 'i'),
+            ["Map a step over", "every input file"]
+        );
+        let long = "Make this dynamic pipeline generate one step per input file, then combine them";
+        assert_eq!(
+            description_lines(long, 30, 'i'),
+            ["Make this dynamic pipeline", "generate… press 'i' for more"]
+        );
+    }
+
+    #[test]
+    fn test_stats_row_lines_up_with_header() {
+        let row = stats_row(3, "01_loading/load1.py", 4, 1, 200);
+        assert_eq!(row, "  3. 01_loading/load1.py                     4     1   3m 20s");
+        assert_eq!(stats_header().len(), row.len() + 2);
+        assert!(stats_row(12, "00_intro/intro1.py", 0, 0, 0).ends_with("       -"));
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(percent(3, 5), 60);
+        assert_eq!(percent(2, 3), 66);
+        assert_eq!(percent(5, 5), 100);
+        assert_eq!(percent(0, 0), 0);
+    }
+
+    #[test]
+    fn test_remaining_label() {
+        assert_eq!(remaining_label(4, 12, Some(1200)), "4 of 12 remaining · ETA: ~20m 00s");
+        assert_eq!(remaining_label(12, 12, None), "12 of 12 remaining · ETA: —");
+    }
+
+    #[test]
+    fn test_exercise_details() {
+        assert_eq!(exercise_details(0, 0), "");
+        assert_eq!(exercise_details(1, 0), "1 attempt");
+        assert_eq!(exercise_details(4, 200), "4 attempts, 3m 20s");
+        assert_eq!(exercise_details(0, 42), "42s");
+    }
+
+    #[test]
+    fn test_match_range_ignores_case() {
+        assert_eq!(match_range("02_data/load_CSV.py", "csv"), Some(13..16));
+        assert_eq!(match_range("02_data/load_CSV.py", ""), None);
+        assert_eq!(match_range("02_data/load_CSV.py", "xml"), None);
+    }
+
+    #[test]
+    fn test_completion_stat_rows() {
+        let stats = CompletionStats {
+            solved: 12,
+            total: 12,
+            time_spent_secs: 4000,
+            hints_used: 3,
+            average_attempts: Some(7.0 / 3.0),
+            total_attempts: 9,
+            no_solutions_viewed: true,
+        };
+        let rows = completion_stat_rows(&stats);
+        assert_eq!(rows[0], ("Exercises solved", "12/12".to_string()));
+        assert_eq!(rows[1], ("Time spent", "1h 06m".to_string()));
+        assert_eq!(rows[2], ("Hints used", "3".to_string()));
+        assert_eq!(rows[3], ("Average attempts", "2.3".to_string()));
+
+        let stats = CompletionStats {
+            average_attempts: None,
+            ..stats
+        };
+        assert_eq!(completion_stat_rows(&stats)[3].1, "-");
+    }
+
+    #[test]
+    fn test_output_window() {
+        let lines: Vec<String> = (1..=10).map(|i| i.to_string()).collect();
+        assert_eq!(output_window(&lines, 3, 0), ["8", "9", "10"]);
+        assert_eq!(output_window(&lines, 3, 2), ["6", "7", "8"]);
+        assert_eq!(output_window(&lines, 3, 9), ["1"]);
+        assert!(output_window(&lines, 3, 20).is_empty());
+        assert_eq!(output_window(&lines, 20, 0).len(), 10);
+    }
+
+    #[test]
+    fn test_traceback_parts() {
+        use TracebackPart::*;
+        let output = [
+            "Starting pipeline",
+            "Traceback (most recent call last):",
+            "  File \"intro1.py\", line 3, in <module>",
+            "    print(x)",
+            "          ^",
+            "NameError: name 'x' is not defined",
+            "done",
+        ];
+        assert_eq!(
+            traceback_parts(&output),
+            [None, Some(Location), Some(Location), None, None, Some(Error), None]
+        );
+
+        // Still streaming, header already dropped: no exception line yet
+        let partial = ["  File \"intro1.py\", line 3, in <module>", "    print(x)"];
+        assert_eq!(traceback_parts(&partial), [Some(Location), None]);
+
+        // Unindented lines outside a traceback stay plain
+        assert_eq!(traceback_parts(&["Error: not from Python"]), [None]);
+    }
+
+    #[test]
+    fn test_pipeline_line() {
+        let mut exercise = test_exercises(&[("00_intro", "intro1")]).remove(0);
+        exercise.pipeline_name = "intro1_pipeline".to_string();
+        assert_eq!(pipeline_line(&exercise, None), "Pipeline: intro1_pipeline");
+
+        let mut result = VerifyResult::cached_pass("intro1");
+        assert_eq!(pipeline_line(&exercise, Some(&result)), "Pipeline: intro1_pipeline");
+        result.run_id = Some("3f2a".to_string());
+        assert_eq!(
+            pipeline_line(&exercise, Some(&result)),
+            "Pipeline: intro1_pipeline  ·  Last run: 3f2a"
+        );
+
+        exercise.manual = true;
+        assert_eq!(pipeline_line(&exercise, Some(&result)), "Pipeline: none (manual exercise)");
+    }
+
+    #[test]
+    fn test_skipped_label() {
+        assert_eq!(skipped_label(None), "skipped");
+        assert_eq!(skipped_label(Some("come back after map3")), "skipped: come back after map3");
+    }
+
+    #[test]
+    fn test_visible_output() {
+        let buffer = vec![
+            (Stream::Stdout, "step 1".to_string()),
+            (Stream::Stderr, "ValueError: bad".to_string()),
+            (Stream::Stdout, "step 2".to_string()),
+        ];
+        assert_eq!(visible_output(&buffer, OutputView::All).len(), 3);
+        assert_eq!(visible_output(&buffer, OutputView::StderrOnly), [(Stream::Stderr, "ValueError: bad")]);
+        assert_eq!(
+            visible_output(&buffer, OutputView::StdoutOnly),
+            [(Stream::Stdout, "step 1"), (Stream::Stdout, "step 2")]
+        );
+    }
+
+    #[test]
+    fn test_hidden_lines_label() {
+        assert_eq!(hidden_lines_label(1), "… 1 earlier line hidden");
+        assert_eq!(hidden_lines_label(340), "… 340 earlier lines hidden");
+    }
+
+    #[test]
+    fn test_wrap_line_short_line_is_unchanged() {
+        assert_eq!(wrap_line("fits fine", 20), vec!["fits fine"]);
+        assert_eq!(wrap_line("", 20), vec![""]);
+    }
+
+    #[test]
+    fn test_wrap_line_breaks_at_word_boundaries() {
+        assert_eq!(
+            wrap_line("the quick brown fox jumps over", 10),
+            vec!["the quick", "brown fox", "jumps over"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_keeps_indentation() {
+        assert_eq!(
+            wrap_line("    return load(data, batch)", 16),
+            vec!["    return", "load(data,", "batch)"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_hard_breaks_long_tokens() {
+        assert_eq!(wrap_line("abcdefghijkl", 5), vec!["abcde", "fghij", "kl"]);
+        assert_eq!(
+            wrap_line("see https://docs.zenml.io/x", 10),
+            vec!["see", "https://do", "cs.zenml.i", "o/x"]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Color themes for the terminal UI.
+//!
+//! Render functions take a `Theme` and color text by role (title, success,
+//! muted, ...) instead of using fixed `Color`s, so the palette can suit
+//! light terminals or be turned off entirely. Pass/fail/warn states also
+//! carry a `Status` glyph and word, so they read the same without color.
+
+use crossterm::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// Built-in palettes selectable with `--theme`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    /// Bright colors for dark backgrounds
+    #[value(alias = "default")]
+    Dark,
+    /// Darker colors that stay readable on light backgrounds
+    Light,
+    /// Colorblind-safe, high-contrast colors (blue for pass, orange for fail)
+    #[value(name = "high-contrast")]
+    #[serde(rename = "high-contrast")]
+    HighContrast,
+    /// No colors
+    Mono,
+}
+
+impl ThemeName {
+    /// Pick a theme: an explicit choice, then the saved one, then `mono` if
+    /// `NO_COLOR` is set, otherwise `dark`
+    pub fn resolve(chosen: Option<ThemeName>, saved: Option<ThemeName>) -> ThemeName {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        Self::resolve_with(chosen, saved, no_color)
+    }
+
+    fn resolve_with(chosen: Option<ThemeName>, saved: Option<ThemeName>, no_color: bool) -> ThemeName {
+        chosen.or(saved).unwrap_or(if no_color {
+            ThemeName::Mono
+        } else {
+            ThemeName::Dark
+        })
+    }
+}
+
+/// Colors for each role in the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Headings and the selected list entry
+    pub title: Color,
+    pub success: Color,
+    pub failure: Color,
+    /// Spinners, warnings and prompts
+    pub warning: Color,
+    /// Hints about keys, secondary details, empty progress
+    pub muted: Color,
+    /// The current exercise path
+    pub highlight: Color,
+    /// The SOLUTION CHECK label
+    pub solution: Color,
+    /// Markdown `**bold**`
+    pub emphasis: Color,
+    /// Markdown `` `code` ``
+    pub code: Color,
+    /// Markdown fenced code blocks
+    pub code_block: Color,
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Self {
+                title: Color::Cyan,
+                success: Color::Green,
+                failure: Color::Red,
+                warning: Color::Yellow,
+                muted: Color::DarkGrey,
+                highlight: Color::Blue,
+                solution: Color::Magenta,
+                emphasis: Color::White,
+                code: Color::Cyan,
+                code_block: Color::Green,
+            },
+            ThemeName::Light => Self {
+                title: Color::DarkCyan,
+                success: Color::DarkGreen,
+                failure: Color::DarkRed,
+                warning: Color::DarkYellow,
+                muted: Color::DarkBlue,
+                highlight: Color::DarkBlue,
+                solution: Color::DarkMagenta,
+                emphasis: Color::Black,
+                code: Color::DarkCyan,
+                code_block: Color::DarkGreen,
+            },
+            // Okabe-Ito colors, which stay apart for red-green colorblindness
+            ThemeName::HighContrast => Self {
+                title: Color::White,
+                success: Color::Rgb { r: 86, g: 180, b: 233 },
+                failure: Color::Rgb { r: 230, g: 159, b: 0 },
+                warning: Color::Rgb { r: 240, g: 228, b: 66 },
+                muted: Color::Grey,
+                highlight: Color::White,
+                solution: Color::Rgb { r: 204, g: 121, b: 167 },
+                emphasis: Color::White,
+                code: Color::Rgb { r: 86, g: 180, b: 233 },
+                code_block: Color::Rgb { r: 0, g: 158, b: 115 },
+            },
+            // Reset keeps the terminal's own foreground color
+            ThemeName::Mono => Self {
+                title: Color::Reset,
+                success: Color::Reset,
+                failure: Color::Reset,
+                warning: Color::Reset,
+                muted: Color::Reset,
+                highlight: Color::Reset,
+                solution: Color::Reset,
+                emphasis: Color::Reset,
+                code: Color::Reset,
+                code_block: Color::Reset,
+            },
+        }
+    }
+}
+
+/// Outcome states shown to the learner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    Failed,
+    Warn,
+}
+
+impl Status {
+    /// Distinct shape per state, for readers who can't tell the colors apart
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Status::Passed => "✓",
+            Status::Failed => "✗",
+            Status::Warn => "!",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Passed => "PASSED",
+            Status::Failed => "FAILED",
+            Status::Warn => "WARN",
+        }
+    }
+
+    /// Glyph and label together, e.g. "✗ FAILED"
+    pub fn marker(self) -> String {
+        format!("{} {}", self.glyph(), self.label())
+    }
+
+    pub fn color(self, theme: &Theme) -> Color {
+        match self {
+            Status::Passed => theme.success,
+            Status::Failed => theme.failure,
+            Status::Warn => theme.warning,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeName::Dark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_precedence() {
+        use ThemeName::*;
+        assert_eq!(ThemeName::resolve_with(Some(Light), Some(Mono), true), Light);
+        assert_eq!(ThemeName::resolve_with(None, Some(Light), true), Light);
+        assert_eq!(ThemeName::resolve_with(None, None, true), Mono);
+        assert_eq!(ThemeName::resolve_with(None, None, false), Dark);
+    }
+
+    #[test]
+    fn test_theme_name_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&ThemeName::Light).unwrap(), "\"light\"");
+        let name: ThemeName = serde_json::from_str("\"mono\"").unwrap();
+        assert_eq!(name, ThemeName::Mono);
+    }
+
+    #[test]
+    fn test_theme_name_values() {
+        use clap::ValueEnum;
+        assert_eq!(ThemeName::from_str("default", false), Ok(ThemeName::Dark));
+        assert_eq!(ThemeName::from_str("high-contrast", false), Ok(ThemeName::HighContrast));
+        assert_eq!(serde_json::to_string(&ThemeName::HighContrast).unwrap(), "\"high-contrast\"");
+    }
+
+    #[test]
+    fn test_status_markers_differ_without_color() {
+        let markers = [Status::Passed, Status::Failed, Status::Warn].map(Status::marker);
+        assert_eq!(markers, ["✓ PASSED", "✗ FAILED", "! WARN"]);
+        let mono = Theme::new(ThemeName::Mono);
+        assert_eq!(Status::Failed.color(&mono), Status::Passed.color(&mono));
+    }
+
+    #[test]
+    fn test_mono_has_no_colors() {
+        let theme = Theme::new(ThemeName::Mono);
+        assert_eq!(theme.title, Color::Reset);
+        assert_eq!(theme.muted, Color::Reset);
+        assert_ne!(Theme::new(ThemeName::Light).muted, Color::DarkGrey);
+    }
+}
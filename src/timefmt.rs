@@ -0,0 +1,168 @@
+//! Human-readable formatting for durations and progress timestamps.
+//!
+//! Progress timestamps are stored as Unix seconds (see `ProgressFile`).
+
+/// Format a duration in seconds, e.g. "42s", "3m 20s", "1h 05m"
+pub fn format_duration(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    let seconds = secs % 60;
+
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Format elapsed seconds as a clock, e.g. "00:07", "12:34", "1:02:03"
+pub fn format_clock(secs: u64) -> String {
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    let seconds = secs % 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+/// Format a stored Unix-seconds timestamp as "YYYY-MM-DD HH:MM UTC"
+///
+/// Unparseable values are returned unchanged.
+pub fn format_timestamp(stored: &str) -> String {
+    let secs: u64 = match stored.parse() {
+        Ok(secs) => secs,
+        Err(_) => return stored.to_string(),
+    };
+
+    let days = secs / 86_400;
+    let rem = secs % 86_400;
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        rem / 3600,
+        (rem % 3600) / 60
+    )
+}
+
+/// Parse an ISO 8601 timestamp as ZenML prints it (e.g.
+/// "2024-05-01T10:00:03.215Z" or "2024-05-01 10:00:03.215000") into
+/// milliseconds since the Unix epoch
+///
+/// A missing offset is taken as UTC.
+pub fn parse_iso_millis(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (date, time) = text.split_once(['T', ' '])?;
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: u32 = date_parts.next()?.parse().ok()?;
+    let day: u32 = date_parts.next()?.parse().ok()?;
+
+    // Split off a trailing "Z" or "+HH:MM"/"-HH:MM" offset
+    let (clock, offset_secs) = if let Some(clock) = time.strip_suffix('Z') {
+        (clock, 0)
+    } else if let Some(pos) = time.rfind(['+', '-']) {
+        let (clock, offset) = time.split_at(pos);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':').unwrap_or((&offset[1..], "0"));
+        let secs = hours.parse::<i64>().ok()? * 3600 + minutes.parse::<i64>().ok()? * 60;
+        (clock, sign * secs)
+    } else {
+        (time, 0)
+    };
+
+    let mut clock_parts = clock.splitn(3, ':');
+    let hour: i64 = clock_parts.next()?.parse().ok()?;
+    let minute: i64 = clock_parts.next()?.parse().ok()?;
+    let seconds_text = clock_parts.next()?;
+    let (second, fraction) = seconds_text.split_once('.').unwrap_or((seconds_text, ""));
+    let second: i64 = second.parse().ok()?;
+    let millis: i64 = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<3}", &fraction[..fraction.len().min(3)]).parse().ok()?
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let secs = days_from_civil(year, month, day) * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    Some(secs * 1000 + millis)
+}
+
+/// Convert a civil date to days since 1970-01-01
+///
+/// Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date
+///
+/// Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(200), "3m 20s");
+        assert_eq!(format_duration(3900), "1h 05m");
+    }
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(0), "00:00");
+        assert_eq!(format_clock(7), "00:07");
+        assert_eq!(format_clock(754), "12:34");
+        assert_eq!(format_clock(3723), "1:02:03");
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp("0"), "1970-01-01 00:00 UTC");
+        assert_eq!(format_timestamp("951782400"), "2000-02-29 00:00 UTC");
+        assert_eq!(format_timestamp("1769913300"), "2026-02-01 02:35 UTC");
+        assert_eq!(format_timestamp("not-a-time"), "not-a-time");
+    }
+
+    #[test]
+    fn test_parse_iso_millis() {
+        assert_eq!(parse_iso_millis("1970-01-01T00:00:00"), Some(0));
+        assert_eq!(parse_iso_millis("2000-02-29T00:00:00Z"), Some(951_782_400_000));
+        assert_eq!(parse_iso_millis("2026-02-01 02:35:00.5"), Some(1_769_913_300_500));
+        assert_eq!(parse_iso_millis("2026-02-01T02:35:00.123456"), Some(1_769_913_300_123));
+        assert_eq!(parse_iso_millis("2026-02-01T04:35:00+02:00"), Some(1_769_913_300_000));
+        assert_eq!(parse_iso_millis("2026-02-01T00:35:00-02:00"), Some(1_769_913_300_000));
+        assert_eq!(parse_iso_millis("yesterday"), None);
+        assert_eq!(parse_iso_millis("2026-13-01T00:00:00"), None);
+    }
+}
@@ -3,14 +3,18 @@
 //! Runs Python exercises and verifies their success via ZenML CLI.
 
 use anyhow::{Context, Result};
-use std::io::{BufRead, BufReader};
+use std::io::{self, BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, ExitStatus, Output, Stdio};
+use std::sync::LazyLock;
 use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
 
-use crate::exercise::Exercise;
+use crate::exercise::{Exercise, closest_names};
+use std::collections::HashMap;
 use std::fmt;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 /// Outcome of a verification attempt
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -31,7 +35,14 @@ pub struct VerifyResult {
 
     /// Whether ZenML CLI check was performed
     pub zenml_checked: bool,
+    /// Concise summary of the ZenML run (e.g. "Run abc123: FAILED")
     pub zenml_output: String,
+    /// Unprocessed ZenML CLI output, for the raw view
+    pub zenml_raw_output: String,
+    /// Dashboard link for the checked run, when ZenML reports one
+    pub run_url: Option<String>,
+    /// ID of the checked run
+    pub run_id: Option<String>,
 
     /// Human-readable status message
     pub message: String,
@@ -42,12 +53,57 @@ impl VerifyResult {
         self.outcome == VerifyOutcome::Passed
     }
 
-    /// Get the output to display
-    pub fn display_output(&self) -> &str {
-        if !self.python_exit_ok || !self.python_output.is_empty() {
-            &self.python_output
-        } else {
-            &self.zenml_output
+    /// A pass recorded for a file that hasn't changed since it last passed
+    pub fn cached_pass(exercise_name: &str) -> Self {
+        Self {
+            exercise_name: exercise_name.to_string(),
+            outcome: VerifyOutcome::Passed,
+            python_exit_ok: true,
+            python_output: String::new(),
+            zenml_checked: false,
+            zenml_output: String::new(),
+            zenml_raw_output: String::new(),
+            run_url: None,
+            run_id: None,
+            message: "Already passed (unchanged)".to_string(),
+        }
+    }
+
+    /// A failure because the script's stdout didn't match `expect_stdout`
+    pub fn stdout_mismatch(
+        exercise: &Exercise,
+        python_exit_ok: bool,
+        python_output: String,
+        message: String,
+    ) -> Self {
+        Self {
+            exercise_name: exercise.name.clone(),
+            outcome: VerifyOutcome::Failed,
+            python_exit_ok,
+            python_output,
+            zenml_checked: false,
+            zenml_output: String::new(),
+            zenml_raw_output: String::new(),
+            run_url: None,
+            run_id: None,
+            message,
+        }
+    }
+
+    /// A failure before running anything because ZenML integrations the
+    /// exercise needs aren't installed
+    pub fn missing_integrations(exercise: &Exercise, message: String) -> Self {
+        Self {
+            exercise_name: exercise.name.clone(),
+            outcome: VerifyOutcome::Failed,
+            python_exit_ok: false,
+            python_output: String::new(),
+            zenml_checked: false,
+            zenml_output: String::new(),
+            zenml_raw_output: String::new(),
+            run_url: None,
+            run_id: None,
+            message,
         }
     }
 }
@@ -58,6 +114,21 @@ pub struct VerifyOptions {
     pub python_bin: String,
     pub zenml_bin: String,
     pub working_dir: PathBuf,
+    /// Extra environment variables for every spawned process
+    pub env: HashMap<String, String>,
+    /// Interpreter flags placed before the exercise path (e.g. `-X dev`)
+    pub python_args: Vec<String>,
+    /// Total tries for ZenML CLI calls that fail with a transient server error
+    pub zenml_attempts: u32,
+    /// Wait before the first retry; doubled for each one after that
+    pub zenml_backoff: Duration,
+    /// Run `python -m <module>` instead of the exercise path (set per exercise)
+    pub module: Option<String>,
+    /// Require the checked run to be newer than the latest one from before
+    /// the script ran, so an old passing run can't stand in for it
+    pub require_new_run: bool,
+    /// Kill a ZenML CLI call that runs longer than this; `None` waits forever
+    pub zenml_timeout: Option<Duration>,
 }
 
 impl Default for VerifyOptions {
@@ -66,24 +137,452 @@ impl Default for VerifyOptions {
             python_bin: "python".to_string(),
             zenml_bin: "zenml".to_string(),
             working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            env: HashMap::new(),
+            python_args: Vec::new(),
+            zenml_attempts: 3,
+            zenml_backoff: Duration::from_millis(500),
+            module: None,
+            require_new_run: true,
+            zenml_timeout: Some(DEFAULT_ZENML_TIMEOUT),
         }
     }
 }
 
+impl VerifyOptions {
+    /// Options for running a specific exercise, with its own env layered on
+    /// top and its module (if any) to run
+    pub fn for_exercise(&self, exercise: &Exercise) -> VerifyOptions {
+        let mut opts = self.clone();
+        opts.env
+            .extend(exercise.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        opts.module = exercise.module.clone();
+        opts
+    }
+}
+
+/// Split a command-line fragment into arguments, shell-style
+///
+/// Whitespace separates arguments; single quotes, double quotes and
+/// backslashes work as in a POSIX shell.
+pub fn split_args(s: &str) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => anyhow::bail!("Unterminated single quote in {:?}", s),
+                    }
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => arg.push(c),
+                            Some(c) => {
+                                arg.push('\\');
+                                arg.push(c);
+                            }
+                            None => anyhow::bail!("Unterminated double quote in {:?}", s),
+                        },
+                        Some(c) => arg.push(c),
+                        None => anyhow::bail!("Unterminated double quote in {:?}", s),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => current.get_or_insert_with(String::new).push(c),
+                None => anyhow::bail!("Trailing backslash in {:?}", s),
+            },
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    args.extend(current);
+    Ok(args)
+}
+
+/// Command that runs an exercise script: interpreter, its flags, then the
+/// path (or `-m <module>` for module exercises)
+pub fn build_python_command(exercise_path: &Path, opts: &VerifyOptions) -> Command {
+    let mut cmd = Command::new(&opts.python_bin);
+    cmd.args(&opts.python_args);
+    match &opts.module {
+        Some(module) => cmd.arg("-m").arg(module),
+        None => cmd.arg(exercise_path),
+    };
+    cmd.current_dir(&opts.working_dir).envs(&opts.env);
+    cmd
+}
+
+/// What a Python run executes, for error messages
+fn describe_target(exercise_path: &Path, opts: &VerifyOptions) -> String {
+    match &opts.module {
+        Some(module) => format!("module {}", module),
+        None => format!("{:?}", exercise_path),
+    }
+}
+
 /// Message type for streaming output
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OutputLine {
     Stdout(String),
     Stderr(String),
     Done(bool), // exit success
 }
 
+/// Which pipe an output line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+/// Which streams the output view shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputView {
+    #[default]
+    All,
+    StderrOnly,
+    StdoutOnly,
+}
+
+impl OutputView {
+    /// All, then stderr only, then stdout only, then back to all
+    pub fn next(self) -> Self {
+        match self {
+            OutputView::All => OutputView::StderrOnly,
+            OutputView::StderrOnly => OutputView::StdoutOnly,
+            OutputView::StdoutOnly => OutputView::All,
+        }
+    }
+
+    pub fn shows(self, stream: Stream) -> bool {
+        match self {
+            OutputView::All => true,
+            OutputView::StderrOnly => stream == Stream::Stderr,
+            OutputView::StdoutOnly => stream == Stream::Stdout,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OutputView::All => "all output",
+            OutputView::StderrOnly => "stderr only",
+            OutputView::StdoutOnly => "stdout only",
+        }
+    }
+}
+
+/// Decodes subprocess output line by line, collapsing runs of binary lines
+///
+/// Text lines are decoded lossily, so a stray invalid byte shows up as `�`.
+/// Lines that are mostly not text (e.g. a script writing raw bytes) would
+/// only fill the screen with garbage, so consecutive ones are replaced by a
+/// single "[binary/non-UTF8 output suppressed, N bytes]" notice.
+#[derive(Debug, Default)]
+pub struct LineDecoder {
+    suppressed_bytes: usize,
+}
+
+impl LineDecoder {
+    /// Decode one line (without its newline); returns the lines to show
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        let text = String::from_utf8_lossy(bytes);
+        if looks_binary(&text) {
+            self.suppressed_bytes += bytes.len() + 1;
+            return Vec::new();
+        }
+        let mut lines: Vec<String> = self.finish().into_iter().collect();
+        lines.push(text.into_owned());
+        lines
+    }
+
+    /// The notice for a suppressed run still pending at the end of output
+    pub fn finish(&mut self) -> Option<String> {
+        match std::mem::take(&mut self.suppressed_bytes) {
+            0 => None,
+            n => Some(format!("[binary/non-UTF8 output suppressed, {} bytes]", n)),
+        }
+    }
+}
+
+/// Whether a lossily decoded line is mostly replacement or control characters
+fn looks_binary(text: &str) -> bool {
+    let total = text.chars().count();
+    let junk = text
+        .chars()
+        .filter(|&c| {
+            c == char::REPLACEMENT_CHARACTER
+                || (c.is_control() && !matches!(c, '\t' | '\r' | '\x1b'))
+        })
+        .count();
+    text.contains('\0') || (total > 0 && junk * 10 > total * 3)
+}
+
+/// Decode captured output, collapsing binary lines as `LineDecoder` does
+fn decode_output(bytes: &[u8]) -> String {
+    let mut decoder = LineDecoder::default();
+    let body = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    let mut lines: Vec<String> = Vec::new();
+    for line in body.split(|&b| b == b'\n') {
+        lines.extend(decoder.push(line));
+    }
+    lines.extend(decoder.finish());
+
+    let mut text = lines.join("\n");
+    if bytes.ends_with(b"\n") {
+        text.push('\n');
+    }
+    text
+}
+
+// ============================================================================
+// Subprocess logging
+// ============================================================================
+
+/// Logging wrapper around `Command::output`
+trait CommandExt {
+    /// Like `output()`, but logs the command line, working dir, exit code and timing
+    fn output_logged(&mut self) -> io::Result<Output>;
+
+    /// Like `output_logged()`, but kills the process once it has run for
+    /// `timeout`, returning `None`; without a timeout it waits as long as it takes
+    fn output_timeout(&mut self, timeout: Option<Duration>) -> io::Result<Option<Output>>;
+}
+
+impl CommandExt for Command {
+    fn output_logged(&mut self) -> io::Result<Output> {
+        log_spawn(self);
+        let started = Instant::now();
+        let result = self.output();
+        match &result {
+            Ok(out) => log_exit(self, out.status, started.elapsed()),
+            Err(e) => log::warn!("failed to run {:?}: {}", self.get_program(), e),
+        }
+        result
+    }
+
+    fn output_timeout(&mut self, timeout: Option<Duration>) -> io::Result<Option<Output>> {
+        let Some(timeout) = timeout else {
+            return self.output_logged().map(Some);
+        };
+        log_spawn(self);
+        let started = Instant::now();
+        let mut child = self
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .inspect_err(|e| log::warn!("failed to run {:?}: {}", self.get_program(), e))?;
+
+        // Drained on their own threads so a full pipe can't stall the process
+        let read_all = |pipe: Option<Box<dyn io::Read + Send>>| {
+            std::thread::spawn(move || {
+                let mut buf = Vec::new();
+                if let Some(mut pipe) = pipe {
+                    let _ = pipe.read_to_end(&mut buf);
+                }
+                buf
+            })
+        };
+        let stdout = read_all(child.stdout.take().map(|p| Box::new(p) as _));
+        let stderr = read_all(child.stderr.take().map(|p| Box::new(p) as _));
+
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if started.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                log::warn!("{:?} timed out after {:?}; killed it", self.get_program(), timeout);
+                return Ok(None);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        log_exit(self, status, started.elapsed());
+        Ok(Some(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        }))
+    }
+}
+
+/// How long a ZenML CLI call may run before it's killed, unless configured
+pub const DEFAULT_ZENML_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Run a ZenML CLI command under `opts.zenml_timeout`
+///
+/// A call that runs out of time is a `TimedOut` error, so a hung server
+/// fails the check instead of freezing it.
+fn zenml_output(cmd: &mut Command, opts: &VerifyOptions) -> io::Result<Output> {
+    cmd.output_timeout(opts.zenml_timeout)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!(
+                "zenml command timed out after {}s (is the ZenML server reachable? see --zenml-timeout-secs)",
+                opts.zenml_timeout.unwrap_or_default().as_secs()
+            ),
+        )
+    })
+}
+
+/// Run a ZenML CLI command, retrying transient server errors with backoff
+///
+/// Only failures that look like a flaky connection are retried; any other
+/// result (including a successful "no runs found") is returned as is.
+fn zenml_output_with_retry(cmd: &mut Command, opts: &VerifyOptions) -> io::Result<Output> {
+    let mut backoff = opts.zenml_backoff;
+    let mut attempt = 1;
+    loop {
+        let output = zenml_output(cmd, opts)?;
+        if output.status.success() || attempt >= opts.zenml_attempts {
+            return Ok(output);
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !is_transient_zenml_error(&stderr) {
+            return Ok(output);
+        }
+        log::info!(
+            "transient zenml error (attempt {}/{}), retrying in {:?}",
+            attempt,
+            opts.zenml_attempts,
+            backoff
+        );
+        std::thread::sleep(backoff);
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+/// An HTTP 5xx status in CLI error output
+static STATUS_5XX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:http|status|code)\b\D{0,12}\b5\d\d\b").expect("valid regex"));
+
+/// Whether ZenML CLI error output looks like a temporary server problem
+pub fn is_transient_zenml_error(output: &str) -> bool {
+    const TRANSIENT: &[&str] = &[
+        "connection refused",
+        "connection reset",
+        "connection aborted",
+        "timed out",
+        "timeout",
+        "temporarily unavailable",
+        "internal server error",
+        "bad gateway",
+        "service unavailable",
+        "gateway timeout",
+    ];
+    let lower = output.to_lowercase();
+    TRANSIENT.iter().any(|p| lower.contains(p)) || STATUS_5XX.is_match(&lower)
+}
+
+/// Log a command about to be spawned
+fn log_spawn(cmd: &Command) {
+    let cwd = cmd
+        .get_current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|| ".".to_string());
+    log::debug!("exec: {:?} (cwd: {})", cmd, cwd);
+}
+
+/// Render a command the way it would be typed in a shell
+///
+/// Arguments containing spaces or quotes are single-quoted. Environment
+/// overrides and the working directory are not included.
+pub fn describe_command(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|arg| shell_quote(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Quote an argument for a POSIX shell if it needs it
+pub fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Log the exit status and duration of a finished command
+fn log_exit(cmd: &Command, status: ExitStatus, elapsed: Duration) {
+    log::debug!(
+        "exit: {:?} -> {} in {:.2?}",
+        cmd.get_program(),
+        status,
+        elapsed
+    );
+}
+
 /// Verify an exercise by running it and checking the result
 pub fn verify_exercise(exercise: &Exercise, opts: &VerifyOptions) -> Result<VerifyResult> {
+    if let Some(message) = check_integrations(exercise, &opts.for_exercise(exercise)) {
+        return Ok(VerifyResult::missing_integrations(exercise, message));
+    }
+
+    let baseline = run_baseline(exercise, opts);
+
     // Step 1: Run the Python exercise
-    let (python_ok, python_output) = run_python_capture(&exercise.path, opts)?;
+    let output = run_python_output(&exercise.path, &opts.for_exercise(exercise))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    check_finished_run(
+        exercise,
+        opts,
+        output.status.success(),
+        combine_output(&output),
+        &stdout,
+        baseline.as_ref(),
+    )
+}
 
-    if !python_ok {
+/// The latest run of the exercise's pipeline, taken before running it so
+/// `check_finished_run` can tell a fresh run from a stale one; `None`
+/// unless `require_new_run` applies
+pub fn run_baseline(exercise: &Exercise, opts: &VerifyOptions) -> Option<ZenmlRun> {
+    if opts.require_new_run && exercise.verify_command.is_none() {
+        latest_run(&exercise.pipeline_name, &opts.for_exercise(exercise))
+    } else {
+        None
+    }
+}
+
+/// Judge an exercise whose script has already run (e.g. streamed by the
+/// TUI, which must not run it a second time): its exit status, `stdout`,
+/// then the ZenML run or `verify_command`
+pub fn check_finished_run(
+    exercise: &Exercise,
+    opts: &VerifyOptions,
+    python_ok: bool,
+    python_output: String,
+    stdout: &str,
+    baseline: Option<&ZenmlRun>,
+) -> Result<VerifyResult> {
+    let opts = &opts.for_exercise(exercise);
+
+    if !python_ok && !exercise.expects_failed_run() {
         return Ok(VerifyResult {
             exercise_name: exercise.name.clone(),
             outcome: VerifyOutcome::Failed,
@@ -91,184 +590,773 @@ pub fn verify_exercise(exercise: &Exercise, opts: &VerifyOptions) -> Result<Veri
             python_output,
             zenml_checked: false,
             zenml_output: String::new(),
+            zenml_raw_output: String::new(),
+            run_url: None,
+            run_id: None,
             message: "Python script failed".to_string(),
         });
     }
 
+    if let Some(message) = check_expected_stdout(exercise, stdout) {
+        return Ok(VerifyResult::stdout_mismatch(exercise, python_ok, python_output, message));
+    }
+
+    // Step 2: A custom verify command replaces the ZenML status check
+    if let Some(ref command) = exercise.verify_command {
+        return run_custom_verification(exercise, command, python_output, opts);
+    }
+
     // Step 2: Check ZenML pipeline status
-    let (zenml_ok, zenml_output, status) =
-        run_zenml_status_check(&exercise.pipeline_name, opts)?;
+    let check = run_zenml_status_check(&exercise.pipeline_name, opts)?;
+    let no_runs = matches!(check, ZenmlStatusCheck::Retrieved { run: None, .. });
+    let (run_id, stale) = match &check {
+        ZenmlStatusCheck::Retrieved { run: Some(run), .. } => {
+            (run.id.clone(), baseline.is_some_and(|before| !is_new_run(run, before)))
+        }
+        _ => (None, false),
+    };
+    let mut result = judge_status_check(exercise, check, python_ok, python_output);
+    if no_runs {
+        let registered = list_pipeline_names(opts);
+        result.message = missing_run_message(&exercise.pipeline_name, registered.as_deref());
+    }
+    if stale {
+        result.outcome = VerifyOutcome::Failed;
+        result.message = STALE_RUN_MESSAGE.to_string();
+        return Ok(result);
+    }
 
-    if !zenml_ok {
-        return Ok(VerifyResult {
+    // Step 3: Required tags, only once the run itself is accepted
+    if result.passed() && !exercise.verify_tags.is_empty() {
+        if let Some(message) = check_run_tags(&exercise.verify_tags, run_id.as_deref(), opts) {
+            result.outcome = VerifyOutcome::Failed;
+            result.message = message;
+        }
+    }
+    Ok(result)
+}
+
+/// Turn a ZenML status check into a result for an exercise
+fn judge_status_check(
+    exercise: &Exercise,
+    check: ZenmlStatusCheck,
+    python_ok: bool,
+    python_output: String,
+) -> VerifyResult {
+    let (run, zenml_raw_output) = match check {
+        ZenmlStatusCheck::Retrieved { run, raw_output } => (run, raw_output),
+        ZenmlStatusCheck::CliFailed { raw_output } => {
+            let message = classify_zenml_error(&raw_output)
+                .unwrap_or("ZenML status check failed")
+                .to_string();
+            return VerifyResult {
+                exercise_name: exercise.name.clone(),
+                outcome: VerifyOutcome::Failed,
+                python_exit_ok: python_ok,
+                python_output,
+                zenml_checked: true,
+                zenml_output: raw_output.clone(),
+                zenml_raw_output: raw_output,
+                run_url: None,
+                run_id: None,
+                message,
+            };
+        }
+    };
+
+    let status = run.as_ref().and_then(|r| r.status.clone());
+    let run_url = run.as_ref().and_then(|r| r.url.clone());
+    let run_id = run.as_ref().and_then(|r| r.id.clone());
+    let zenml_output = match &run {
+        Some(run) => run.summary(),
+        None => format!("No runs found for pipeline '{}'", exercise.pipeline_name),
+    };
+
+    // Step 3: Verify the status is one of those accepted
+    let status_matches = status
+        .as_ref()
+        .is_some_and(|s| exercise.verify_status.contains(s));
+    // Only checked when the run lists its steps
+    let step_mismatch = exercise
+        .verify_step_count
+        .zip(run.as_ref().and_then(|r| r.step_count))
+        .filter(|&(expected, actual)| actual as u64 != expected);
+
+    if let (true, Some((expected, actual))) = (status_matches, step_mismatch) {
+        VerifyResult {
             exercise_name: exercise.name.clone(),
             outcome: VerifyOutcome::Failed,
-            python_exit_ok: true,
+            python_exit_ok: python_ok,
             python_output,
             zenml_checked: true,
             zenml_output,
-            message: "ZenML status check failed".to_string(),
-        });
-    }
-
-    // Step 3: Verify the status matches expected
-    let status_matches = status
-        .as_ref()
-        .map(|s| s == &exercise.verify_status)
-        .unwrap_or(false);
-
-    if status_matches {
-        Ok(VerifyResult {
+            zenml_raw_output,
+            run_url,
+            run_id,
+            message: format!("Pipeline ran {} steps, expected {}", actual, expected),
+        }
+    } else if status_matches {
+        VerifyResult {
             exercise_name: exercise.name.clone(),
             outcome: VerifyOutcome::Passed,
-            python_exit_ok: true,
+            python_exit_ok: python_ok,
             python_output,
             zenml_checked: true,
             zenml_output,
-            message: format!("Pipeline {}", exercise.verify_status),
-        })
+            zenml_raw_output,
+            run_url,
+            run_id,
+            message: match run.as_ref().and_then(ZenmlRun::details) {
+                Some(details) => format!("Pipeline {} ({})", status.unwrap_or_default(), details),
+                None => format!("Pipeline {}", status.unwrap_or_default()),
+            },
+        }
     } else {
         let actual_status = status.unwrap_or_else(|| "unknown".to_string());
-        Ok(VerifyResult {
+        VerifyResult {
             exercise_name: exercise.name.clone(),
             outcome: VerifyOutcome::Failed,
-            python_exit_ok: true,
+            python_exit_ok: python_ok,
             python_output,
             zenml_checked: true,
             zenml_output,
+            zenml_raw_output,
+            run_url,
+            run_id,
             message: format!(
-                "Pipeline status '{}', expected '{}'",
-                actual_status, exercise.verify_status
+                "Pipeline status '{}', expected {}",
+                actual_status,
+                describe_statuses(&exercise.verify_status)
             ),
-        })
+        }
     }
 }
 
+/// Command for an exercise's `verify_command` (program, then its arguments)
+pub fn build_custom_command(program: &str, args: &[String], opts: &VerifyOptions) -> Command {
+    let mut cmd = Command::new(program);
+    cmd.args(args).current_dir(&opts.working_dir).envs(&opts.env);
+    cmd
+}
+
+/// Run an exercise's `verify_command`, passing if it exits zero and its
+/// stdout matches `verify_expect` (when set)
+fn run_custom_verification(
+    exercise: &Exercise,
+    command: &[String],
+    python_output: String,
+    opts: &VerifyOptions,
+) -> Result<VerifyResult> {
+    let display = command.join(" ");
+    let (program, args) = command
+        .split_first()
+        .context("verify_command is empty")?;
+
+    let output = build_custom_command(program, args, opts)
+        .output_timeout(opts.zenml_timeout)
+        .with_context(|| format!("Failed to run verify command: {}", display))?
+        .with_context(|| {
+            format!(
+                "Verify command `{}` timed out after {}s (see --zenml-timeout-secs)",
+                display,
+                opts.zenml_timeout.unwrap_or_default().as_secs()
+            )
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let combined = if stderr.is_empty() { stdout.clone() } else { format!("{}\n{}", stdout, stderr) };
+
+    let (passed, message) = if !output.status.success() {
+        (false, format!("Verify command `{}` failed ({})", display, output.status))
+    } else {
+        match exercise.verify_expect {
+            Some(ref pattern) if !matches_expectation(pattern, &stdout)? => (
+                false,
+                format!("Verify command output did not match /{}/", pattern),
+            ),
+            _ => (true, format!("Verify command `{}` passed", display)),
+        }
+    };
+
+    Ok(VerifyResult {
+        exercise_name: exercise.name.clone(),
+        outcome: if passed { VerifyOutcome::Passed } else { VerifyOutcome::Failed },
+        python_exit_ok: true,
+        python_output,
+        zenml_checked: true,
+        zenml_output: combined.trim().to_string(),
+        zenml_raw_output: combined,
+        run_url: None,
+        run_id: None,
+        message,
+    })
+}
+
+/// Check a script's stdout against the exercise's `expect_stdout`
+///
+/// Returns the failure message if it doesn't match.
+pub fn check_expected_stdout(exercise: &Exercise, stdout: &str) -> Option<String> {
+    let expected = exercise.expect_stdout.as_ref()?;
+    if exercise.expect_regex {
+        // The pattern was validated when the pack was loaded
+        let found = Regex::new(expected).is_ok_and(|re| re.is_match(stdout));
+        (!found).then(|| format!("Expected output matching /{}/ not found", expected))
+    } else {
+        (!stdout.contains(expected.as_str()))
+            .then(|| format!("Expected output containing '{}' not found", expected))
+    }
+}
+
+/// Check a verify command's stdout against a `verify_expect` regex
+fn matches_expectation(pattern: &str, stdout: &str) -> Result<bool> {
+    let re = Regex::new(pattern)
+        .with_context(|| format!("Invalid verify_expect regex: {}", pattern))?;
+    Ok(re.is_match(stdout))
+}
+
+/// How often a streaming run checks whether it has been cancelled
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Run a Python exercise with streaming output
+///
+/// `should_cancel` is polled while the process runs; once it returns true
+/// the child is killed and `Ok(None)` is returned. Otherwise returns
+/// whether the script exited successfully, and its stdout (which is also
+/// streamed, for `expect_stdout` checks).
 pub fn run_python_streaming(
     exercise_path: &Path,
     opts: &VerifyOptions,
     output_tx: Sender<OutputLine>,
-) -> Result<bool> {
-    let mut child = Command::new(&opts.python_bin)
-        .arg(exercise_path)
-        .current_dir(&opts.working_dir)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
+    mut should_cancel: impl FnMut() -> bool,
+) -> Result<Option<(bool, String)>> {
+    let mut cmd = build_python_command(exercise_path, opts);
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    log_spawn(&cmd);
+    let started = Instant::now();
+    let mut child = cmd
         .spawn()
-        .with_context(|| format!("Failed to run Python: {:?}", exercise_path))?;
+        .with_context(|| format!("Failed to run Python: {}", describe_target(exercise_path, opts)))?;
 
     // Read stdout in a thread
     let stdout = child.stdout.take().expect("stdout piped");
     let tx_out = output_tx.clone();
     let stdout_handle = std::thread::spawn(move || {
-        let reader = BufReader::new(stdout);
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                let _ = tx_out.send(OutputLine::Stdout(line));
-            }
-        }
+        let mut captured = String::new();
+        for_each_line(stdout, |line| {
+            captured.push_str(&line);
+            captured.push('\n');
+            let _ = tx_out.send(OutputLine::Stdout(line));
+        });
+        captured
     });
 
     // Read stderr in a thread
     let stderr = child.stderr.take().expect("stderr piped");
     let tx_err = output_tx.clone();
     let stderr_handle = std::thread::spawn(move || {
-        let reader = BufReader::new(stderr);
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                let _ = tx_err.send(OutputLine::Stderr(line));
-            }
-        }
+        for_each_line(stderr, |line| {
+            let _ = tx_err.send(OutputLine::Stderr(line));
+        });
     });
 
-    // Wait for process to complete
-    let status = child.wait()?;
+    // Wait for process to complete (or be cancelled)
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if should_cancel() {
+            let _ = child.kill();
+            let _ = child.wait();
+            log::debug!("cancelled: {:?} after {:?}", cmd, started.elapsed());
+            // Don't join the readers: anything the script spawned may still
+            // hold the pipes open
+            let _ = output_tx.send(OutputLine::Done(false));
+            return Ok(None);
+        }
+        std::thread::sleep(CANCEL_POLL_INTERVAL);
+    };
+    log_exit(&cmd, status, started.elapsed());
 
     // Wait for readers to finish
-    let _ = stdout_handle.join();
+    let stdout = stdout_handle.join().unwrap_or_default();
     let _ = stderr_handle.join();
 
     let success = status.success();
     let _ = output_tx.send(OutputLine::Done(success));
 
-    Ok(success)
+    Ok(Some((success, stdout)))
+}
+
+/// Call `f` with each decoded line read from `source` until it closes
+///
+/// Lines are read as bytes, so output that isn't valid UTF-8 can't stop
+/// the reader early.
+fn for_each_line(source: impl io::Read, mut f: impl FnMut(String)) {
+    let mut reader = BufReader::new(source);
+    let mut decoder = LineDecoder::default();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                decoder.push(line).into_iter().for_each(&mut f);
+            }
+        }
+    }
+    decoder.finish().into_iter().for_each(f);
 }
 
 /// Run Python and capture all output (non-streaming)
-fn run_python_capture(exercise_path: &Path, opts: &VerifyOptions) -> Result<(bool, String)> {
-    let output = Command::new(&opts.python_bin)
-        .arg(exercise_path)
-        .current_dir(&opts.working_dir)
-        .output()
-        .with_context(|| format!("Failed to run Python: {:?}", exercise_path))?;
+pub fn run_python_capture(exercise_path: &Path, opts: &VerifyOptions) -> Result<(bool, String)> {
+    let output = run_python_output(exercise_path, opts)?;
+    Ok((output.status.success(), combine_output(&output)))
+}
 
-    let mut combined = String::new();
-    combined.push_str(&String::from_utf8_lossy(&output.stdout));
+fn run_python_output(exercise_path: &Path, opts: &VerifyOptions) -> Result<Output> {
+    build_python_command(exercise_path, opts)
+        .output_logged()
+        .with_context(|| format!("Failed to run Python: {}", describe_target(exercise_path, opts)))
+}
+
+/// A script's stdout followed by its stderr
+fn combine_output(output: &Output) -> String {
+    let mut combined = decode_output(&output.stdout);
     if !output.stderr.is_empty() {
         if !combined.is_empty() {
-            combined.push_str("\n");
+            combined.push('\n');
         }
-        combined.push_str(&String::from_utf8_lossy(&output.stderr));
+        combined.push_str(&decode_output(&output.stderr));
     }
+    combined
+}
 
-    Ok((output.status.success(), combined))
+/// Extract the final exception line (e.g. `NameError: name 'x' is not
+/// defined`) from the last Python traceback in some output
+pub fn traceback_summary<S: AsRef<str>>(lines: &[S]) -> Option<String> {
+    let start = lines
+        .iter()
+        .rposition(|l| l.as_ref().trim_end() == "Traceback (most recent call last):")?;
+
+    // Frames and source lines are indented; the exception line is not
+    lines[start + 1..]
+        .iter()
+        .map(|l| l.as_ref().trim_end())
+        .find(|l| !l.is_empty() && !l.starts_with(char::is_whitespace))
+        .map(str::to_string)
 }
 
-/// Check ZenML pipeline run status
-fn run_zenml_status_check(
-    pipeline_name: &str,
-    opts: &VerifyOptions,
-) -> Result<(bool, String, Option<String>)> {
-    let output = Command::new(&opts.zenml_bin)
-        .args([
-            "pipeline",
-            "runs",
-            "list",
-            "--pipeline",
-            pipeline_name,
-            "--size",
-            "1",
-            "--sort_by",
-            "desc:created",
-            "--output",
-            "json",
-        ])
+/// "'completed'" or "one of 'completed', 'cached'"
+fn describe_statuses(statuses: &[String]) -> String {
+    let quoted: Vec<String> = statuses.iter().map(|s| format!("'{}'", s)).collect();
+    match quoted.as_slice() {
+        [single] => single.clone(),
+        _ => format!("one of {}", quoted.join(", ")),
+    }
+}
+
+/// Outcome of asking the ZenML CLI for a pipeline's latest run
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ZenmlStatusCheck {
+    /// The CLI answered; `run` is the latest run, if there is one (whatever its status)
+    Retrieved { run: Option<ZenmlRun>, raw_output: String },
+    /// The CLI itself failed (not logged in, bad pipeline name, crash...)
+    CliFailed { raw_output: String },
+}
+
+/// Command that looks up a pipeline's latest run
+pub fn build_zenml_status_command(pipeline_name: &str, opts: &VerifyOptions) -> Command {
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.args([
+        "pipeline",
+        "runs",
+        "list",
+        "--pipeline",
+        pipeline_name,
+        "--size",
+        "1",
+        "--sort_by",
+        "desc:created",
+        "--output",
+        "json",
+    ])
+    .current_dir(&opts.working_dir)
+    .envs(&opts.env);
+    cmd
+}
+
+const STALE_RUN_MESSAGE: &str = "No new pipeline run was created — did your script call the pipeline?";
+
+/// The pipeline's latest run, or `None` if it has none or ZenML can't be asked
+fn latest_run(pipeline_name: &str, opts: &VerifyOptions) -> Option<ZenmlRun> {
+    match run_zenml_status_check(pipeline_name, opts).ok()? {
+        ZenmlStatusCheck::Retrieved { run, .. } => run,
+        ZenmlStatusCheck::CliFailed { .. } => None,
+    }
+}
+
+/// Whether `run` was created after `baseline`
+///
+/// Compares ids when both have one (a different run is new unless it's
+/// older), otherwise creation times. If neither tells, the run counts as new.
+fn is_new_run(run: &ZenmlRun, baseline: &ZenmlRun) -> bool {
+    let created = run.created_ms.zip(baseline.created_ms);
+    match (&run.id, &baseline.id) {
+        (Some(id), Some(before)) => id != before && created.is_none_or(|(now, then)| now >= then),
+        _ => created.is_none_or(|(now, then)| now > then),
+    }
+}
+
+/// Command that fetches one run's full details (including its tags)
+pub fn build_zenml_describe_command(run_id: &str, opts: &VerifyOptions) -> Command {
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.args(["pipeline", "runs", "describe", run_id, "--output", "json"])
         .current_dir(&opts.working_dir)
-        .output()
+        .envs(&opts.env);
+    cmd
+}
+
+/// Check that the run has every tag in `required`
+///
+/// Returns the failure message if it doesn't, or if its tags can't be read.
+fn check_run_tags(required: &[String], run_id: Option<&str>, opts: &VerifyOptions) -> Option<String> {
+    let Some(run_id) = run_id else {
+        return Some("Can't check tags: ZenML didn't report the run's id".to_string());
+    };
+    let output = zenml_output_with_retry(&mut build_zenml_describe_command(run_id, opts), opts);
+    let tags = match output {
+        Ok(output) if output.status.success() => parse_run_tags(&String::from_utf8_lossy(&output.stdout)),
+        _ => None,
+    };
+    let Some(tags) = tags else {
+        return Some(format!("Couldn't read the tags of run {}", run_id));
+    };
+    let missing: Vec<&str> = required
+        .iter()
+        .filter(|tag| !tags.contains(tag))
+        .map(String::as_str)
+        .collect();
+    match missing.as_slice() {
+        [] => None,
+        [tag] => Some(format!("Run is missing the tag '{}'", tag)),
+        tags => Some(format!("Run is missing the tags {}", tags.join(", "))),
+    }
+}
+
+/// Tag names from `zenml pipeline runs describe --output json`
+///
+/// Accepts the run itself or an `items` list, with `tags` on the run or
+/// under its `body`, `metadata` or `resources`, as plain strings or as
+/// objects with a `name`. A run without tags has none; `None` means the
+/// output isn't a run at all.
+fn parse_run_tags(json_str: &str) -> Option<Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(json_str).ok()?;
+    let run = match value.get("items") {
+        Some(items) => items.get(0)?,
+        None => &value,
+    };
+    if !run.is_object() {
+        return None;
+    }
+
+    let tags = [Some(run), run.get("body"), run.get("metadata"), run.get("resources")]
+        .into_iter()
+        .flatten()
+        .find_map(|v| v.get("tags").and_then(|t| t.as_array()));
+    Some(
+        tags.into_iter()
+            .flatten()
+            .filter_map(|tag| tag.as_str().or_else(|| tag.get("name").and_then(|n| n.as_str())))
+            .map(|tag| tag.to_string())
+            .collect(),
+    )
+}
+
+/// Check ZenML pipeline run status
+fn run_zenml_status_check(pipeline_name: &str, opts: &VerifyOptions) -> Result<ZenmlStatusCheck> {
+    let output = zenml_output_with_retry(&mut build_zenml_status_command(pipeline_name, opts), opts)
         .with_context(|| "Failed to run zenml CLI")?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let combined = if stderr.is_empty() { stdout.clone() } else { format!("{}\n{}", stdout, stderr) };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Ok(classify_status_output(output.status.success(), &stdout, &stderr))
+}
 
+/// Names of the pipelines registered with ZenML, or `None` if the CLI fails
+fn list_pipeline_names(opts: &VerifyOptions) -> Option<Vec<String>> {
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.args(["pipeline", "list", "--output", "json"])
+        .current_dir(&opts.working_dir)
+        .envs(&opts.env);
+    let output = zenml_output_with_retry(&mut cmd, opts).ok()?;
     if !output.status.success() {
-        return Ok((false, combined, None));
+        return None;
     }
+    parse_pipeline_names(&String::from_utf8_lossy(&output.stdout))
+}
 
-    // Parse JSON to extract status
-    let status = parse_zenml_status(&stdout);
+fn parse_pipeline_names(json_str: &str) -> Option<Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(json_str).ok()?;
+    let names = value
+        .get("items")?
+        .as_array()?
+        .iter()
+        .filter_map(|item| item.get("name").and_then(|n| n.as_str()))
+        .map(|s| s.to_string())
+        .collect();
+    Some(names)
+}
 
-    Ok((true, combined, status))
+/// Why a pipeline has no runs: it never ran, or no pipeline has that name
+///
+/// `registered` is `None` when the pipeline list couldn't be fetched.
+fn missing_run_message(pipeline_name: &str, registered: Option<&[String]>) -> String {
+    let Some(registered) = registered else {
+        return format!("No runs found for pipeline '{}'", pipeline_name);
+    };
+    if registered.iter().any(|name| name == pipeline_name) {
+        return format!("Pipeline '{}' has never run", pipeline_name);
+    }
+
+    let message = format!("No pipeline named '{}' is registered in ZenML", pipeline_name);
+    match closest_names(pipeline_name, registered.iter().map(|s| s.as_str())).as_slice() {
+        [] => format!("{} — check the name passed to @pipeline", message),
+        [only] => format!("{} — did you mean '{}'?", message, only),
+        many => format!("{} — did you mean one of: {}?", message, many.join(", ")),
+    }
+}
+
+/// Separate "the CLI failed" from "the CLI reported a run"
+///
+/// A run's own status (even `failed`) never makes this a CLI failure.
+fn classify_status_output(exit_ok: bool, stdout: &str, stderr: &str) -> ZenmlStatusCheck {
+    let raw_output = if stderr.is_empty() {
+        stdout.to_string()
+    } else {
+        format!("{}\n{}", stdout, stderr)
+    };
+
+    if !exit_ok {
+        return ZenmlStatusCheck::CliFailed { raw_output };
+    }
+
+    // Parse JSON to extract the latest run
+    let run = parse_zenml_run(stdout);
+    ZenmlStatusCheck::Retrieved { run, raw_output }
+}
+
+/// An HTTP 401 status, not just "401" inside a run ID, port or timestamp
+static STATUS_401: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:http|status|code)\b\D{0,12}\b401\b").expect("valid regex"));
+
+/// Recognize ZenML CLI failures caused by the server or store rather than
+/// the exercise, returning a message that says what to do about it
+pub fn classify_zenml_error(output: &str) -> Option<&'static str> {
+    const AUTH: &[&str] = &[
+        "authentication",
+        "not authorized",
+        "unauthorized",
+        "credentials",
+        "token has expired",
+    ];
+    const UNREACHABLE: &[&str] = &[
+        "could not connect",
+        "connectionerror",
+        "connection refused",
+        "max retries exceeded",
+        "failed to establish a new connection",
+        "name or service not known",
+    ];
+    const STORE: &[&str] = &[
+        "unable to open database file",
+        "database is locked",
+        "sqlite3.operationalerror",
+    ];
+
+    let lower = output.to_lowercase();
+    let mentions = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+
+    if mentions(AUTH) || STATUS_401.is_match(&lower) {
+        Some("ZenML authentication failed — run `zenml login` to refresh your credentials")
+    } else if mentions(UNREACHABLE) {
+        Some("ZenML server unreachable — run `zenml login` or check your store with `zenml status`")
+    } else if mentions(STORE) {
+        Some("ZenML local store unavailable — check the SQLite database in your ZenML config (`zenml status`)")
+    } else {
+        None
+    }
+}
+
+/// The latest pipeline run, as reported by `zenml pipeline runs list`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZenmlRun {
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub status: Option<String>,
+    /// Names of steps that reported a `failed` status
+    pub failed_steps: Vec<String>,
+    /// Number of steps in the run, when the JSON lists them
+    pub step_count: Option<usize>,
+    /// Wall-clock run time from its start and end timestamps
+    pub duration_ms: Option<u64>,
+    /// Link to the run in the ZenML dashboard
+    pub url: Option<String>,
+    /// When the run was created, in Unix milliseconds
+    pub created_ms: Option<i64>,
+}
+
+impl ZenmlRun {
+    /// One-line summary, e.g. "Run abc123: FAILED — step 'train_model' errored"
+    pub fn summary(&self) -> String {
+        let label = self
+            .name
+            .clone()
+            .or_else(|| self.id.as_ref().map(|id| id.chars().take(8).collect()))
+            .unwrap_or_else(|| "latest".to_string());
+        let status = self
+            .status
+            .as_deref()
+            .unwrap_or("unknown")
+            .to_uppercase();
+
+        let mut summary = format!("Run {}: {}", label, status);
+        match self.failed_steps.as_slice() {
+            [] => {}
+            [step] => summary.push_str(&format!(" — step '{}' errored", step)),
+            steps => {
+                let names: Vec<String> = steps.iter().map(|s| format!("'{}'", s)).collect();
+                summary.push_str(&format!(" — steps {} errored", names.join(", ")));
+            }
+        }
+        summary
+    }
+
+    /// Step count and run time for the PASSED message, e.g. "5 steps, 3.2s"
+    pub fn details(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        match self.step_count {
+            Some(1) => parts.push("1 step".to_string()),
+            Some(count) if count > 0 => parts.push(format!("{} steps", count)),
+            _ => {}
+        }
+        if let Some(ms) = self.duration_ms {
+            parts.push(if ms < 60_000 {
+                format!("{:.1}s", ms as f64 / 1000.0)
+            } else {
+                crate::timefmt::format_duration(ms / 1000)
+            });
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
 }
 
-/// Parse the status from ZenML JSON output
-fn parse_zenml_status(json_str: &str) -> Option<String> {
+/// Parse the latest run from ZenML JSON output
+fn parse_zenml_run(json_str: &str) -> Option<ZenmlRun> {
     let value: serde_json::Value = serde_json::from_str(json_str).ok()?;
 
     // Status is directly on the item (not nested in "body")
     // JSON structure: { "items": [{ "status": "completed", ... }] }
-    value
-        .get("items")?
-        .get(0)?
-        .get("status")?
-        .as_str()
-        .map(|s| s.to_string())
+    let item = value.get("items")?.get(0)?;
+    let str_field = |key: &str| item.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    let steps = item.get("steps");
+    let step_count = match steps {
+        Some(serde_json::Value::Object(map)) => Some(map.len()),
+        Some(serde_json::Value::Array(items)) => Some(items.len()),
+        _ => None,
+    };
+
+    Some(ZenmlRun {
+        id: str_field("id"),
+        name: str_field("name"),
+        status: str_field("status"),
+        failed_steps: parse_failed_steps(steps),
+        step_count,
+        duration_ms: parse_run_duration(item),
+        url: parse_run_url(item),
+        created_ms: parse_run_timestamp(item, "created"),
+    })
+}
+
+/// A timestamp field that ZenML puts on the item itself or under its
+/// `body` or `metadata`, in Unix milliseconds
+fn parse_run_timestamp(item: &serde_json::Value, key: &str) -> Option<i64> {
+    [Some(item), item.get("body"), item.get("metadata")]
+        .into_iter()
+        .flatten()
+        .find_map(|v| v.get(key).and_then(|t| t.as_str()))
+        .and_then(crate::timefmt::parse_iso_millis)
+}
+
+/// Dashboard link for a run, which ZenML servers put on the item itself or
+/// under its `body`, `metadata` or `resources`
+fn parse_run_url(item: &serde_json::Value) -> Option<String> {
+    [Some(item), item.get("body"), item.get("metadata"), item.get("resources")]
+        .into_iter()
+        .flatten()
+        .flat_map(|v| ["dashboard_url", "run_url", "url"].map(|key| v.get(key)))
+        .flatten()
+        .filter_map(|url| url.as_str())
+        .find(|url| url.starts_with("https://") || url.starts_with("http://"))
+        .map(|url| url.to_string())
+}
+
+/// Run time from `start_time`/`end_time`, which ZenML puts on the item
+/// itself or under its `body` or `metadata`
+fn parse_run_duration(item: &serde_json::Value) -> Option<u64> {
+    let elapsed = parse_run_timestamp(item, "end_time")? - parse_run_timestamp(item, "start_time")?;
+    u64::try_from(elapsed).ok()
+}
+
+/// Extract failed step names from a run's `steps` field.
+///
+/// ZenML reports steps either as a map of name -> step (with a `status`)
+/// or as a list of steps carrying their own `name`.
+fn parse_failed_steps(steps: Option<&serde_json::Value>) -> Vec<String> {
+    let is_failed = |step: &serde_json::Value| {
+        let status = step
+            .get("status")
+            .or_else(|| step.get("body").and_then(|b| b.get("status")))
+            .and_then(|s| s.as_str())
+            .or_else(|| step.as_str());
+        status == Some("failed")
+    };
+
+    match steps {
+        Some(serde_json::Value::Object(map)) => map
+            .iter()
+            .filter(|(_, step)| is_failed(step))
+            .map(|(name, _)| name.clone())
+            .collect(),
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter(|step| is_failed(step))
+            .filter_map(|step| step.get("name").and_then(|n| n.as_str()))
+            .map(|s| s.to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
 }
 
 /// Simple verification that just checks Python exit code (no ZenML check)
 pub fn verify_exercise_simple(exercise: &Exercise, opts: &VerifyOptions) -> Result<VerifyResult> {
-    let (python_ok, python_output) = run_python_capture(&exercise.path, opts)?;
+    let opts = &opts.for_exercise(exercise);
+    if let Some(message) = check_integrations(exercise, opts) {
+        return Ok(VerifyResult::missing_integrations(exercise, message));
+    }
+    let output = run_python_output(&exercise.path, opts)?;
+    let python_ok = output.status.success();
+    let python_output = combine_output(&output);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Some(message) = check_expected_stdout(exercise, &stdout).filter(|_| python_ok) {
+        return Ok(VerifyResult::stdout_mismatch(exercise, python_ok, python_output, message));
+    }
 
     let outcome = if python_ok {
         VerifyOutcome::Passed
@@ -289,6 +1377,9 @@ pub fn verify_exercise_simple(exercise: &Exercise, opts: &VerifyOptions) -> Resu
         python_output,
         zenml_checked: false,
         zenml_output: String::new(),
+        zenml_raw_output: String::new(),
+        run_url: None,
+        run_id: None,
         message,
     })
 }
@@ -298,12 +1389,25 @@ pub fn check_zenml_init(dir: &Path) -> bool {
     dir.join(".zen").exists()
 }
 
+/// Run `zenml init` in the working dir, failing with its error output
+pub fn run_zenml_init(opts: &VerifyOptions) -> Result<()> {
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.arg("init").current_dir(&opts.working_dir).envs(&opts.env);
+    let output = zenml_output(&mut cmd, opts).with_context(|| format!("Failed to run {} init", opts.zenml_bin))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() { stdout } else { stderr };
+        anyhow::bail!("zenml init failed: {}", message.trim());
+    }
+    Ok(())
+}
+
 /// Get current ZenML stack info
 pub fn get_zenml_stack_info(opts: &VerifyOptions) -> Result<Option<String>> {
-    let output = Command::new(&opts.zenml_bin)
-        .args(["stack", "describe"])
-        .current_dir(&opts.working_dir)
-        .output();
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.args(["stack", "describe"]).current_dir(&opts.working_dir).envs(&opts.env);
+    let output = zenml_output(&mut cmd, opts);
 
     match output {
         Ok(out) if out.status.success() => {
@@ -313,6 +1417,106 @@ pub fn get_zenml_stack_info(opts: &VerifyOptions) -> Result<Option<String>> {
     }
 }
 
+/// The active stack's name, if `zenml stack describe` reports one
+pub fn get_stack_name(opts: &VerifyOptions) -> Option<String> {
+    get_zenml_stack_info(opts)
+        .ok()
+        .flatten()
+        .and_then(|output| parse_stack_name(&output))
+}
+
+/// Pull the active stack's name out of `zenml stack describe` output
+///
+/// Matches both "Stack 'default' with id ..." and "'default' stack (ACTIVE)".
+fn parse_stack_name(describe_output: &str) -> Option<String> {
+    let re = Regex::new(r"(?:Stack '([^']+)'|'([^']+)' stack)").expect("valid regex");
+    re.captures(describe_output)
+        .and_then(|caps| caps.get(1).or_else(|| caps.get(2)))
+        .map(|m| m.as_str().to_string())
+}
+
+/// The active stack and its orchestrator, as shown in the main view
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StackSummary {
+    pub stack: Option<String>,
+    pub orchestrator: Option<String>,
+    /// The connected server's dashboard, from `zenml status`
+    pub dashboard_url: Option<String>,
+}
+
+impl StackSummary {
+    /// e.g. "Stack: default (orchestrator: local)"
+    pub fn describe(&self) -> String {
+        format!(
+            "Stack: {} (orchestrator: {})",
+            self.stack.as_deref().unwrap_or("unknown"),
+            self.orchestrator.as_deref().unwrap_or("unknown")
+        )
+    }
+
+    /// Warning when this isn't the stack (or orchestrator) `exercise` is
+    /// written for, with the command that switches
+    ///
+    /// Nothing is said while the active stack is unknown.
+    pub fn mismatch(&self, exercise: &Exercise) -> Option<String> {
+        if let (Some(required), Some(active)) = (&exercise.required_stack, &self.stack) {
+            if required != active {
+                return Some(format!(
+                    "This exercise expects the '{}' stack (active: '{}'); run: zenml stack set {}",
+                    required, active, required
+                ));
+            }
+        }
+        match (&exercise.required_orchestrator, &self.orchestrator) {
+            (Some(required), Some(active)) if required != active => Some(format!(
+                "This exercise expects a '{}' orchestrator (active: '{}'); switch to a stack with one, \
+                 e.g. run: zenml stack set default",
+                required, active
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Query the active stack name and orchestrator flavor
+pub fn get_stack_summary(opts: &VerifyOptions) -> StackSummary {
+    let stack = get_stack_name(opts);
+    let orchestrator = match get_orchestrator_type(opts) {
+        OrchestratorCheckResult::Found(flavor) => Some(flavor),
+        _ => None,
+    };
+    StackSummary {
+        stack,
+        orchestrator,
+        dashboard_url: get_dashboard_url(opts),
+    }
+}
+
+/// The dashboard URL `zenml status` reports, if any
+fn get_dashboard_url(opts: &VerifyOptions) -> Option<String> {
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.arg("status").current_dir(&opts.working_dir).envs(&opts.env);
+    let output = zenml_output(&mut cmd, opts)
+        .ok()
+        .filter(|out| out.status.success())?;
+    parse_dashboard_url(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Pull the dashboard (or, failing that, server) URL out of `zenml status` output
+///
+/// Matches "Dashboard URL: http://..." as well as "Connected to a ZenML server: [http://...]".
+fn parse_dashboard_url(status_output: &str) -> Option<String> {
+    let re = Regex::new(r"https?://[^\s\]\)'\x22]+").expect("valid regex");
+    let url_on = |keyword: &str| {
+        status_output
+            .lines()
+            .filter(|line| line.to_lowercase().contains(keyword))
+            .find_map(|line| re.find(line))
+            .map(|m| m.as_str().trim_end_matches(['.', ',', '/']).to_string())
+    };
+    url_on("dashboard").or_else(|| url_on("server"))
+}
+
 /// Result of checking the orchestrator type
 #[derive(Debug, Clone)]
 pub enum OrchestratorCheckResult {
@@ -326,10 +1530,11 @@ pub enum OrchestratorCheckResult {
 
 /// Get the current orchestrator type (flavor) from the active orchestrator
 pub fn get_orchestrator_type(opts: &VerifyOptions) -> OrchestratorCheckResult {
-    let output = Command::new(&opts.zenml_bin)
-        .args(["orchestrator", "list", "--output", "json"])
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.args(["orchestrator", "list", "--output", "json"])
         .current_dir(&opts.working_dir)
-        .output();
+        .envs(&opts.env);
+    let output = zenml_output_with_retry(&mut cmd, opts);
 
     match output {
         Ok(out) if out.status.success() => {
@@ -365,6 +1570,8 @@ pub fn get_orchestrator_type(opts: &VerifyOptions) -> OrchestratorCheckResult {
             // Couldn't even run the command
             if e.kind() == std::io::ErrorKind::NotFound {
                 OrchestratorCheckResult::CommandFailed(format!("'{}' not found in PATH", opts.zenml_bin))
+            } else if e.kind() == std::io::ErrorKind::TimedOut {
+                OrchestratorCheckResult::CommandFailed(e.to_string())
             } else {
                 OrchestratorCheckResult::CommandFailed(format!("failed to run zenml: {}", e))
             }
@@ -377,7 +1584,7 @@ pub fn get_orchestrator_type(opts: &VerifyOptions) -> OrchestratorCheckResult {
 // ============================================================================
 
 /// Python version parsed from the interpreter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PythonVersion {
     pub major: u32,
     pub minor: u32,
@@ -404,7 +1611,8 @@ impl fmt::Display for PythonVersion {
 pub fn get_python_version(opts: &VerifyOptions) -> Result<PythonVersion> {
     let output = Command::new(&opts.python_bin)
         .args(["-c", "import sys; print(f'{sys.version_info.major}.{sys.version_info.minor}.{sys.version_info.micro}')"])
-        .output()
+        .envs(&opts.env)
+        .output_logged()
         .with_context(|| format!("Failed to run Python binary: {}", opts.python_bin))?;
 
     if !output.status.success() {
@@ -428,7 +1636,7 @@ pub fn get_python_version(opts: &VerifyOptions) -> Result<PythonVersion> {
 }
 
 /// Result of probing for ZenML installation
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZenmlProbe {
     /// ZenML Python package version (if importable)
     pub zenml_version: Option<String>,
@@ -471,7 +1679,8 @@ except Exception:
 
     let output = Command::new(&opts.python_bin)
         .args(["-c", script])
-        .output();
+        .envs(&opts.env)
+        .output_logged();
 
     match output {
         Ok(out) if out.status.success() => {
@@ -482,11 +1691,76 @@ except Exception:
     }
 }
 
+/// Names of the ZenML integrations installed in the Python environment, or
+/// `None` if ZenML's integration registry couldn't be queried
+pub fn probe_integrations(opts: &VerifyOptions) -> Option<Vec<String>> {
+    let script = r#"
+import json
+from zenml.integrations.registry import integration_registry
+names = [n for n in integration_registry.integrations if integration_registry.is_installed(n)]
+print(json.dumps(names))
+"#;
+
+    let output = Command::new(&opts.python_bin)
+        .args(["-c", script])
+        .current_dir(&opts.working_dir)
+        .envs(&opts.env)
+        .output_logged();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            parse_installed_integrations(&String::from_utf8_lossy(&out.stdout))
+        }
+        _ => None,
+    }
+}
+
+/// Parse the probe's output: a JSON list of integration names on its last line
+/// (ZenML may log other lines first)
+fn parse_installed_integrations(stdout: &str) -> Option<Vec<String>> {
+    let last = stdout.lines().rev().find(|line| !line.trim().is_empty())?;
+    let names: Vec<String> = serde_json::from_str(last.trim()).ok()?;
+    Some(names.into_iter().map(|n| n.to_lowercase()).collect())
+}
+
+/// Explain which of `required` are missing from `installed`, with the
+/// command that installs them
+fn missing_integrations_message(required: &[String], installed: &[String]) -> Option<String> {
+    let missing: Vec<&str> = required
+        .iter()
+        .map(|name| name.as_str())
+        .filter(|name| !installed.iter().any(|i| i.eq_ignore_ascii_case(name)))
+        .collect();
+    let quoted: Vec<String> = missing.iter().map(|name| format!("'{}'", name)).collect();
+    let needs = match quoted.as_slice() {
+        [] => return None,
+        [one] => format!("the {} integration", one),
+        [rest @ .., last] => format!("the {} and {} integrations", rest.join(", "), last),
+    };
+    Some(format!(
+        "This exercise needs {}: run `zenml integration install {}`",
+        needs,
+        missing.join(" ")
+    ))
+}
+
+/// Check the exercise's `requires_integrations` before running it
+///
+/// Returns the failure message if any are missing. If the registry can't be
+/// queried, the run goes ahead and reports whatever error it hits.
+pub fn check_integrations(exercise: &Exercise, opts: &VerifyOptions) -> Option<String> {
+    if exercise.requires_integrations.is_empty() {
+        return None;
+    }
+    let installed = probe_integrations(opts)?;
+    missing_integrations_message(&exercise.requires_integrations, &installed)
+}
+
 /// Check if zenml CLI is accessible and get its version
 fn check_zenml_cli(opts: &VerifyOptions) -> (bool, Option<String>) {
-    let output = Command::new(&opts.zenml_bin)
-        .args(["--version"])
-        .output();
+    let mut cmd = Command::new(&opts.zenml_bin);
+    cmd.args(["--version"]).envs(&opts.env);
+    let output = zenml_output(&mut cmd, opts);
 
     match output {
         Ok(out) if out.status.success() => {
@@ -495,11 +1769,11 @@ fn check_zenml_cli(opts: &VerifyOptions) -> (bool, Option<String>) {
             let version = version_output
                 .lines()
                 .next()
-                .and_then(|line| {
+                .map(|line| {
                     if let Some(idx) = line.find("version") {
-                        Some(line[idx + 7..].trim().to_string())
+                        line[idx + 7..].trim().to_string()
                     } else {
-                        Some(line.trim().to_string())
+                        line.trim().to_string()
                     }
                 });
             (true, version)
@@ -509,12 +1783,14 @@ fn check_zenml_cli(opts: &VerifyOptions) -> (bool, Option<String>) {
 }
 
 /// Try to find a working zenml binary, checking common locations
-pub fn find_zenml_binary(working_dir: &Path, default_bin: &str) -> String {
+///
+/// A venv binary that doesn't answer `--version` within `timeout` is passed over.
+pub fn find_zenml_binary(working_dir: &Path, default_bin: &str, timeout: Option<Duration>) -> String {
     // First, check if there's a local .venv with zenml
     let venv_zenml = working_dir.join(".venv/bin/zenml");
     if venv_zenml.exists() {
         // Verify it actually works
-        if let Ok(output) = Command::new(&venv_zenml).args(["--version"]).output() {
+        if let Ok(Some(output)) = Command::new(&venv_zenml).args(["--version"]).output_timeout(timeout) {
             if output.status.success() {
                 return venv_zenml.to_string_lossy().to_string();
             }
@@ -528,27 +1804,500 @@ pub fn find_zenml_binary(working_dir: &Path, default_bin: &str) -> String {
 /// Try to find a working python binary, checking common locations
 pub fn find_python_binary(working_dir: &Path, default_bin: &str) -> String {
     // First, check if there's a local .venv with python
-    let venv_python = working_dir.join(".venv/bin/python");
+    let venv_python = venv_python(working_dir);
     if venv_python.exists() {
-        return venv_python.to_string_lossy().to_string();
+        // A half-deleted venv (or one whose base Python is gone) still has the file
+        match Command::new(&venv_python).args(["-c", "pass"]).output_logged() {
+            Ok(output) if output.status.success() => return venv_python.to_string_lossy().to_string(),
+            _ => log::warn!("{} doesn't run; falling back to {}", venv_python.display(), default_bin),
+        }
     }
 
     // Fall back to whatever is in PATH
     default_bin.to_string()
 }
 
+/// The pack's virtualenv interpreter, whether or not it exists
+fn venv_python(working_dir: &Path) -> PathBuf {
+    working_dir.join(".venv/bin/python")
+}
+
+/// The `.venv` interpreter `find_python_binary` passed over because it
+/// didn't run, if `python_bin` is what it fell back to
+pub fn skipped_venv_python(working_dir: &Path, python_bin: &str) -> Option<PathBuf> {
+    let venv_python = venv_python(working_dir);
+    (venv_python.exists() && Path::new(python_bin) != venv_python).then_some(venv_python)
+}
+
+/// Where `bin` would be run from: itself if it's a path, otherwise the first
+/// match in `PATH`
+pub fn resolve_binary(bin: &str) -> Option<PathBuf> {
+    let path = Path::new(bin);
+    if path.components().count() > 1 {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    let search = std::env::var_os("PATH")?;
+    std::env::split_paths(&search).find_map(|dir| {
+        let candidate = dir.join(bin);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let exe = dir.join(format!("{}.exe", bin));
+        (cfg!(windows) && exe.is_file()).then_some(exe)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn test_exercise(verify_status: &[&str]) -> Exercise {
+        let entry = crate::exercise::ExerciseEntry {
+            name: "intro1".to_string(),
+            dir: "00_intro".to_string(),
+            verify_status: Some(verify_status.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        };
+        Exercise::from_entry(&entry, Path::new("/tmp/zenlings"), None)
+    }
+
+    #[test]
+    fn test_resolve_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("tool");
+        std::fs::write(&bin, "").unwrap();
+
+        assert_eq!(resolve_binary(&bin.to_string_lossy()), Some(bin.clone()));
+        assert_eq!(resolve_binary(&dir.path().join("missing").to_string_lossy()), None);
+        assert_eq!(resolve_binary("zenlings-no-such-binary"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_python_binary_skips_broken_venv() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(find_python_binary(dir.path(), "python3"), "python3");
+        assert_eq!(skipped_venv_python(dir.path(), "python3"), None);
+
+        // Present but not runnable, as after a half-finished `rm -rf .venv`
+        let venv_python = dir.path().join(".venv/bin/python");
+        std::fs::create_dir_all(venv_python.parent().unwrap()).unwrap();
+        std::fs::write(&venv_python, "").unwrap();
+        assert_eq!(find_python_binary(dir.path(), "python3"), "python3");
+        assert_eq!(skipped_venv_python(dir.path(), "python3"), Some(venv_python.clone()));
+
+        std::fs::remove_file(&venv_python).unwrap();
+        std::os::unix::fs::symlink(resolve_binary("true").unwrap(), &venv_python).unwrap();
+        let found = find_python_binary(dir.path(), "python3");
+        assert_eq!(found, venv_python.to_string_lossy());
+        assert_eq!(skipped_venv_python(dir.path(), &found), None);
+    }
+
+    #[test]
+    fn test_expected_stdout_substring() {
+        let mut exercise = test_exercise(&["completed"]);
+        assert_eq!(check_expected_stdout(&exercise, "anything"), None);
+
+        exercise.expect_stdout = Some("accuracy: 0.9".to_string());
+        assert_eq!(check_expected_stdout(&exercise, "step done\naccuracy: 0.95\n"), None);
+        assert_eq!(
+            check_expected_stdout(&exercise, "accuracy: 0.8").as_deref(),
+            Some("Expected output containing 'accuracy: 0.9' not found")
+        );
+    }
+
+    #[test]
+    fn test_expected_stdout_regex() {
+        let mut exercise = test_exercise(&["completed"]);
+        exercise.expect_stdout = Some(r"^total: \d+$".to_string());
+        exercise.expect_regex = true;
+
+        assert_eq!(check_expected_stdout(&exercise, "total: 42"), None);
+        assert_eq!(
+            check_expected_stdout(&exercise, "total: many").as_deref(),
+            Some(r"Expected output matching /^total: \d+$/ not found")
+        );
+    }
+
+    #[test]
+    fn test_parse_installed_integrations() {
+        assert_eq!(
+            parse_installed_integrations("Some ZenML log line\n[\"sklearn\", \"MLflow\"]\n"),
+            Some(vec!["sklearn".to_string(), "mlflow".to_string()])
+        );
+        assert_eq!(parse_installed_integrations("[]"), Some(vec![]));
+        assert_eq!(parse_installed_integrations("ModuleNotFoundError: zenml"), None);
+        assert_eq!(parse_installed_integrations(""), None);
+    }
+
+    #[test]
+    fn test_missing_integrations_message() {
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let installed = names(&["sklearn"]);
+
+        assert_eq!(missing_integrations_message(&names(&["sklearn"]), &installed), None);
+        assert_eq!(
+            missing_integrations_message(&names(&["sklearn", "mlflow"]), &installed).as_deref(),
+            Some("This exercise needs the 'mlflow' integration: run `zenml integration install mlflow`")
+        );
+        assert_eq!(
+            missing_integrations_message(&names(&["mlflow", "wandb", "s3"]), &installed).as_deref(),
+            Some(
+                "This exercise needs the 'mlflow', 'wandb' and 's3' integrations: \
+                 run `zenml integration install mlflow wandb s3`"
+            )
+        );
+    }
+
+    #[test]
+    fn test_missing_run_message() {
+        let registered = vec!["load_pipeline".to_string(), "train_pipeline".to_string()];
+        assert_eq!(
+            missing_run_message("load_pipeline", Some(&registered)),
+            "Pipeline 'load_pipeline' has never run"
+        );
+        assert_eq!(
+            missing_run_message("laod_pipeline", Some(&registered)),
+            "No pipeline named 'laod_pipeline' is registered in ZenML — did you mean 'load_pipeline'?"
+        );
+        assert!(missing_run_message("other", Some(&registered))
+            .ends_with("check the name passed to @pipeline"));
+        assert_eq!(
+            missing_run_message("load_pipeline", None),
+            "No runs found for pipeline 'load_pipeline'"
+        );
+    }
+
+    #[test]
+    fn test_parse_pipeline_names() {
+        let json = r#"{"items":[{"id":"1","name":"a_pipeline"},{"id":"2","name":"b_pipeline"}]}"#;
+        assert_eq!(parse_pipeline_names(json).unwrap(), ["a_pipeline", "b_pipeline"]);
+        assert_eq!(parse_pipeline_names("not json"), None);
+    }
+
+    #[test]
+    fn test_binary_lines_are_collapsed() {
+        let mut bytes = b"before\n".to_vec();
+        bytes.extend([0xff, 0xfe, 0x00, 0x01, b'\n', 0x00, 0xff, 0x10, 0x11, b'\n']);
+        bytes.extend(b"after\n");
+        assert_eq!(
+            decode_output(&bytes),
+            "before\n[binary/non-UTF8 output suppressed, 10 bytes]\nafter\n"
+        );
+    }
+
+    #[test]
+    fn test_mostly_text_lines_are_decoded_lossily() {
+        assert_eq!(decode_output(b"caf\xe9 au lait"), "caf\u{fffd} au lait");
+        assert_eq!(decode_output(b"\x1b[32mok\x1b[0m\n"), "\x1b[32mok\x1b[0m\n");
+        assert_eq!(decode_output(b""), "");
+    }
+
+    #[test]
+    fn test_streamed_lines_survive_invalid_utf8() {
+        let input: &[u8] = b"one\r\n\xff\xfe\xfd\nthree";
+        let mut lines = Vec::new();
+        for_each_line(input, |line| lines.push(line));
+        assert_eq!(
+            lines,
+            ["one", "[binary/non-UTF8 output suppressed, 4 bytes]", "three"]
+        );
+    }
+
+    #[test]
+    fn test_cli_failure_is_not_a_failed_run() {
+        let check = classify_status_output(false, "", "Error: not logged in");
+        assert!(matches!(check, ZenmlStatusCheck::CliFailed { .. }));
+
+        // Even an exercise expecting a failed run must not pass on a CLI error
+        let result = judge_status_check(&test_exercise(&["failed"]), check, false, String::new());
+        assert!(!result.passed());
+        assert_eq!(result.message, "ZenML status check failed");
+    }
+
+    #[test]
+    fn test_expected_failed_run_passes() {
+        let json = r#"{"items":[{"name": "intro1_pipeline-1", "status": "failed"}]}"#;
+        let check = classify_status_output(true, json, "");
+        assert!(matches!(check, ZenmlStatusCheck::Retrieved { run: Some(_), .. }));
+
+        let result = judge_status_check(&test_exercise(&["failed"]), check.clone(), false, String::new());
+        assert!(result.passed());
+        assert_eq!(result.message, "Pipeline failed");
+
+        let result = judge_status_check(&test_exercise(&["completed"]), check, true, String::new());
+        assert!(!result.passed());
+        assert_eq!(result.message, "Pipeline status 'failed', expected 'completed'");
+    }
+
+    #[test]
+    fn test_step_count_is_checked() {
+        let mut exercise = test_exercise(&["completed"]);
+        exercise.verify_step_count = Some(3);
+        let check = |steps: &str| {
+            let json = format!(r#"{{"items":[{{"status": "completed", "steps": {}}}]}}"#, steps);
+            classify_status_output(true, &json, "")
+        };
+
+        assert!(judge_status_check(&exercise, check(r#"{"a": {}, "b": {}, "c": {}}"#), true, String::new()).passed());
+        let result = judge_status_check(&exercise, check(r#"{"a": {}, "b": {}}"#), true, String::new());
+        assert!(!result.passed());
+        assert_eq!(result.message, "Pipeline ran 2 steps, expected 3");
+        // A run that doesn't list its steps can't be checked
+        assert!(judge_status_check(&exercise, check("null"), true, String::new()).passed());
+    }
+
+    #[test]
+    fn test_any_accepted_status_passes() {
+        let exercise = test_exercise(&["completed", "cached"]);
+        let check = |status: &str| {
+            let json = format!(r#"{{"items":[{{"status": "{}"}}]}}"#, status);
+            classify_status_output(true, &json, "")
+        };
+
+        let result = judge_status_check(&exercise, check("cached"), true, String::new());
+        assert!(result.passed());
+        assert_eq!(result.message, "Pipeline cached");
+
+        let result = judge_status_check(&exercise, check("running"), true, String::new());
+        assert!(!result.passed());
+        assert_eq!(
+            result.message,
+            "Pipeline status 'running', expected one of 'completed', 'cached'"
+        );
+    }
+
     #[test]
     fn test_parse_zenml_status() {
         // Status is directly on the item (not nested in "body")
         let json = r#"{"items":[{"status":"completed"}]}"#;
-        assert_eq!(parse_zenml_status(json), Some("completed".to_string()));
+        assert_eq!(parse_zenml_run(json).and_then(|r| r.status), Some("completed".to_string()));
 
         let json_empty = r#"{"items":[]}"#;
-        assert_eq!(parse_zenml_status(json_empty), None);
+        assert_eq!(parse_zenml_run(json_empty), None);
+    }
+
+    #[test]
+    fn test_parse_zenml_run_failed_steps_map() {
+        let json = r#"{"items":[{
+            "id": "4f1c2d3e-aaaa-bbbb-cccc-000000000000",
+            "name": "train_pipeline-2024_05_01-10_00_00",
+            "status": "failed",
+            "steps": {
+                "load_data": {"status": "completed"},
+                "train_model": {"status": "failed"}
+            }
+        }]}"#;
+        let run = parse_zenml_run(json).unwrap();
+        assert_eq!(run.failed_steps, vec!["train_model".to_string()]);
+        assert_eq!(
+            run.summary(),
+            "Run train_pipeline-2024_05_01-10_00_00: FAILED — step 'train_model' errored"
+        );
+    }
+
+    #[test]
+    fn test_parse_zenml_run_failed_steps_list() {
+        let json = r#"{"items":[{
+            "id": "4f1c2d3e-aaaa-bbbb-cccc-000000000000",
+            "status": "failed",
+            "steps": [
+                {"name": "a", "body": {"status": "failed"}},
+                {"name": "b", "status": "completed"},
+                {"name": "c", "status": "failed"}
+            ]
+        }]}"#;
+        let run = parse_zenml_run(json).unwrap();
+        assert_eq!(run.summary(), "Run 4f1c2d3e: FAILED — steps 'a', 'c' errored");
+    }
+
+    #[test]
+    fn test_zenml_run_summary_without_steps() {
+        let json = r#"{"items":[{"name": "hello_pipeline-1", "status": "completed"}]}"#;
+        let run = parse_zenml_run(json).unwrap();
+        assert!(run.failed_steps.is_empty());
+        assert_eq!(run.summary(), "Run hello_pipeline-1: COMPLETED");
+
+        assert_eq!(ZenmlRun::default().summary(), "Run latest: UNKNOWN");
+        assert_eq!(parse_zenml_run("not json"), None);
+    }
+
+    #[test]
+    fn test_parse_zenml_run_details() {
+        let json = r#"{"items":[{
+            "name": "train_pipeline-1",
+            "status": "completed",
+            "metadata": {
+                "start_time": "2024-05-01T10:00:00.000000",
+                "end_time": "2024-05-01T10:00:03.215000"
+            },
+            "steps": {"a": {}, "b": {}, "c": {}, "d": {}, "e": {}}
+        }]}"#;
+        let run = parse_zenml_run(json).unwrap();
+        assert_eq!(run.step_count, Some(5));
+        assert_eq!(run.duration_ms, Some(3215));
+        assert_eq!(run.details(), Some("5 steps, 3.2s".to_string()));
+
+        // Timestamps straight on the item, a step list, and a long run
+        let json = r#"{"items":[{
+            "status": "completed",
+            "start_time": "2024-05-01T10:00:00Z",
+            "end_time": "2024-05-01T10:03:20Z",
+            "steps": [{"name": "only"}]
+        }]}"#;
+        assert_eq!(parse_zenml_run(json).unwrap().details(), Some("1 step, 3m 20s".to_string()));
+
+        // Still running (no end time yet), no steps listed
+        let json = r#"{"items":[{"status": "completed", "body": {"start_time": "2024-05-01T10:00:00"}}]}"#;
+        let run = parse_zenml_run(json).unwrap();
+        assert_eq!(run.duration_ms, None);
+        assert_eq!(run.details(), None);
+    }
+
+    #[test]
+    fn test_parse_run_tags() {
+        // Plain strings on the run
+        let json = r#"{"id": "abc", "tags": ["baseline", "v2"]}"#;
+        assert_eq!(parse_run_tags(json), Some(vec!["baseline".to_string(), "v2".to_string()]));
+
+        // Tag objects under resources, inside an items list
+        let json = r#"{"items": [{"id": "abc", "resources": {"tags": [{"id": "1", "name": "baseline"}]}}]}"#;
+        assert_eq!(parse_run_tags(json), Some(vec!["baseline".to_string()]));
+
+        // No tags at all
+        assert_eq!(parse_run_tags(r#"{"id": "abc", "body": {}}"#), Some(vec![]));
+
+        assert_eq!(parse_run_tags(r#"{"items": []}"#), None);
+        assert_eq!(parse_run_tags("[]"), None);
+        assert_eq!(parse_run_tags("not json"), None);
+    }
+
+    #[test]
+    fn test_parse_run_created() {
+        let json = r#"{"items":[{"id": "a", "body": {"created": "2024-05-01T10:00:00.250000"}}]}"#;
+        assert_eq!(parse_zenml_run(json).unwrap().created_ms, Some(1_714_557_600_250));
+        let json = r#"{"items":[{"id": "a", "created": "yesterday"}]}"#;
+        assert_eq!(parse_zenml_run(json).unwrap().created_ms, None);
+    }
+
+    #[test]
+    fn test_is_new_run() {
+        let run = |id: Option<&str>, created_ms: Option<i64>| ZenmlRun {
+            id: id.map(str::to_string),
+            created_ms,
+            ..Default::default()
+        };
+
+        // Same run as before the attempt
+        assert!(!is_new_run(&run(Some("a"), Some(100)), &run(Some("a"), Some(100))));
+        // A different run, created later or within the same millisecond
+        assert!(is_new_run(&run(Some("b"), Some(200)), &run(Some("a"), Some(100))));
+        assert!(is_new_run(&run(Some("b"), Some(100)), &run(Some("a"), Some(100))));
+        // A different but older run (e.g. the newest was deleted)
+        assert!(!is_new_run(&run(Some("b"), Some(50)), &run(Some("a"), Some(100))));
+        // Without ids, only the timestamps tell
+        assert!(is_new_run(&run(None, Some(200)), &run(None, Some(100))));
+        assert!(!is_new_run(&run(None, Some(100)), &run(None, Some(100))));
+        assert!(is_new_run(&run(None, None), &run(None, Some(100))));
+    }
+
+    #[test]
+    fn test_parse_zenml_run_url() {
+        let json = r#"{"items":[{"status": "completed", "body": {"dashboard_url": "https://zenml.example.com/runs/abc"}}]}"#;
+        assert_eq!(
+            parse_zenml_run(json).unwrap().url.as_deref(),
+            Some("https://zenml.example.com/runs/abc")
+        );
+
+        // Local stores have no dashboard; non-links are ignored
+        let json = r#"{"items":[{"status": "completed", "url": "/runs/abc"}]}"#;
+        assert_eq!(parse_zenml_run(json).unwrap().url, None);
+        let json = r#"{"items":[{"status": "completed"}]}"#;
+        assert_eq!(parse_zenml_run(json).unwrap().url, None);
+    }
+
+    #[test]
+    fn test_matches_expectation() {
+        let stdout = "Model: iris_classifier\nVersions: 3\n";
+
+        assert!(matches_expectation("iris_classifier", stdout).unwrap());
+        assert!(matches_expectation(r"(?m)^Versions: \d+$", stdout).unwrap());
+        assert!(!matches_expectation(r"Versions: [4-9]", stdout).unwrap());
+        assert!(!matches_expectation("^iris", "").unwrap());
+        assert!(matches_expectation("(unclosed", stdout).is_err());
+    }
+
+    #[test]
+    fn test_parse_stack_name() {
+        let output = "Stack 'default' with id '8d0acbc3-...' is owned by user default.\n";
+        assert_eq!(parse_stack_name(output), Some("default".to_string()));
+
+        let output = "        Stack Configuration\n...\n      'local-dev' stack (ACTIVE)\n";
+        assert_eq!(parse_stack_name(output), Some("local-dev".to_string()));
+
+        assert_eq!(parse_stack_name("No active stack"), None);
+    }
+
+    #[test]
+    fn test_parse_dashboard_url() {
+        let status = "Using configuration from: '/home/me/.config/zenml'\n\
+                      Connected to a ZenML server: 'http://127.0.0.1:8237'\n\
+                      The dashboard is available at: http://127.0.0.1:8237/.\n";
+        assert_eq!(parse_dashboard_url(status).as_deref(), Some("http://127.0.0.1:8237"));
+
+        let server_only = "Connected to a ZenML Pro server: [https://cloud.example.com]";
+        assert_eq!(parse_dashboard_url(server_only).as_deref(), Some("https://cloud.example.com"));
+
+        assert_eq!(parse_dashboard_url("Running without an active repository root."), None);
+    }
+
+    #[test]
+    fn test_stack_summary_describe() {
+        let summary = StackSummary {
+            stack: Some("default".to_string()),
+            orchestrator: Some("local".to_string()),
+            dashboard_url: None,
+        };
+        assert_eq!(summary.describe(), "Stack: default (orchestrator: local)");
+        assert_eq!(
+            StackSummary::default().describe(),
+            "Stack: unknown (orchestrator: unknown)"
+        );
+    }
+
+    #[test]
+    fn test_stack_summary_mismatch() {
+        let summary = StackSummary {
+            stack: Some("prod".to_string()),
+            orchestrator: Some("kubernetes".to_string()),
+            dashboard_url: None,
+        };
+        let mut exercise = test_exercise(&["completed"]);
+        assert_eq!(summary.mismatch(&exercise), None);
+
+        exercise.required_orchestrator = Some("local".to_string());
+        assert_eq!(
+            summary.mismatch(&exercise).as_deref(),
+            Some(
+                "This exercise expects a 'local' orchestrator (active: 'kubernetes'); \
+                 switch to a stack with one, e.g. run: zenml stack set default"
+            )
+        );
+
+        exercise.required_stack = Some("local".to_string());
+        assert_eq!(
+            summary.mismatch(&exercise).as_deref(),
+            Some("This exercise expects the 'local' stack (active: 'prod'); run: zenml stack set local")
+        );
+
+        // Matching, or not known yet
+        let local = StackSummary {
+            stack: Some("local".to_string()),
+            orchestrator: Some("local".to_string()),
+            dashboard_url: None,
+        };
+        assert_eq!(local.mismatch(&exercise), None);
+        assert_eq!(StackSummary::default().mismatch(&exercise), None);
     }
 
     #[test]
@@ -570,4 +2319,257 @@ mod tests {
         let v = PythonVersion { major: 3, minor: 11, patch: 5 };
         assert_eq!(format!("{}", v), "3.11.5");
     }
+
+    #[test]
+    fn test_traceback_summary() {
+        let output = [
+            "Running pipeline...",
+            "Traceback (most recent call last):",
+            "  File \"exercises/00_intro/intro1.py\", line 12, in <module>",
+            "    print(x)",
+            "          ^",
+            "NameError: name 'x' is not defined",
+        ];
+        assert_eq!(
+            traceback_summary(&output),
+            Some("NameError: name 'x' is not defined".to_string())
+        );
+
+        assert_eq!(traceback_summary(&["all good"]), None);
+    }
+
+    #[test]
+    fn test_traceback_summary_uses_last_chained_exception() {
+        let output = [
+            "Traceback (most recent call last):",
+            "  File \"a.py\", line 1, in <module>",
+            "KeyError: 'a'",
+            "",
+            "During handling of the above exception, another exception occurred:",
+            "",
+            "Traceback (most recent call last):",
+            "  File \"a.py\", line 3, in <module>",
+            "ValueError: bad value",
+        ];
+        assert_eq!(traceback_summary(&output), Some("ValueError: bad value".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_is_visible_to_spawned_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("print_env.sh");
+        std::fs::write(&script, "echo \"value=$ZENLINGS_TEST_VAR\"\n").unwrap();
+
+        let opts = VerifyOptions {
+            python_bin: "sh".to_string(),
+            working_dir: dir.path().to_path_buf(),
+            env: HashMap::from([("ZENLINGS_TEST_VAR".to_string(), "pack".to_string())]),
+            ..Default::default()
+        };
+        let (ok, output) = run_python_capture(&script, &opts).unwrap();
+        assert!(ok);
+        assert!(output.contains("value=pack"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_streaming_run_can_be_cancelled() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("slow.sh");
+        std::fs::write(&script, "sleep 30\n").unwrap();
+
+        let opts = VerifyOptions {
+            python_bin: "sh".to_string(),
+            working_dir: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let started = Instant::now();
+        let result = run_python_streaming(&script, &opts, tx, || true).unwrap();
+
+        assert_eq!(result, None);
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(matches!(rx.recv().unwrap(), OutputLine::Done(false)));
+    }
+
+    #[test]
+    fn test_output_view_cycles() {
+        let mut view = OutputView::default();
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            seen.push((view.shows(Stream::Stdout), view.shows(Stream::Stderr)));
+            view = view.next();
+        }
+        assert_eq!(view, OutputView::All);
+        assert_eq!(seen, [(true, true), (false, true), (true, false)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hung_zenml_call_times_out() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let zenml = dir.path().join("zenml");
+        std::fs::write(&zenml, "#!/bin/sh\nif [ \"$1\" = \"--version\" ]; then echo 'zenml, version 0.80.0'; exit 0; fi\nsleep 30\n").unwrap();
+        std::fs::set_permissions(&zenml, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let opts = VerifyOptions {
+            zenml_bin: zenml.display().to_string(),
+            working_dir: dir.path().to_path_buf(),
+            zenml_timeout: Some(Duration::from_millis(300)),
+            ..Default::default()
+        };
+        let started = Instant::now();
+        match get_orchestrator_type(&opts) {
+            OrchestratorCheckResult::CommandFailed(message) => {
+                assert!(message.starts_with("zenml command timed out"), "{message}")
+            }
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        // Calls that finish in time are unaffected, output and all
+        assert_eq!(check_zenml_cli(&opts), (true, Some("0.80.0".to_string())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hung_venv_zenml_is_passed_over() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join(".venv/bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("zenml"), "#!/bin/sh\nsleep 30\n").unwrap();
+        std::fs::set_permissions(bin.join("zenml"), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let started = Instant::now();
+        assert_eq!(find_zenml_binary(dir.path(), "zenml", Some(Duration::from_millis(300))), "zenml");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_exercise_env_overrides_pack_env() {
+        let opts = VerifyOptions {
+            env: HashMap::from([
+                ("A".to_string(), "pack".to_string()),
+                ("B".to_string(), "pack".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let mut exercise = test_exercise(&["completed"]);
+        exercise.env = HashMap::from([("B".to_string(), "exercise".to_string())]);
+        let merged = opts.for_exercise(&exercise);
+        assert_eq!(merged.env["A"], "pack");
+        assert_eq!(merged.env["B"], "exercise");
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("-X dev  -W error").unwrap(), vec!["-X", "dev", "-W", "error"]);
+        assert_eq!(
+            split_args(r#"-W 'ignore::DeprecationWarning' -c "print(\"hi\")""#).unwrap(),
+            vec!["-W", "ignore::DeprecationWarning", "-c", "print(\"hi\")"]
+        );
+        assert_eq!(split_args(r"a\ b ''").unwrap(), vec!["a b", ""]);
+        assert!(split_args("").unwrap().is_empty());
+        assert!(split_args("-W 'error").is_err());
+    }
+
+    #[test]
+    fn test_python_args_come_before_script_path() {
+        let opts = VerifyOptions {
+            python_bin: "python3".to_string(),
+            python_args: vec!["-X".to_string(), "dev".to_string()],
+            ..Default::default()
+        };
+        let cmd = build_python_command(Path::new("ex.py"), &opts);
+        assert_eq!(cmd.get_program(), "python3");
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["-X", "dev", "ex.py"]);
+    }
+
+    #[test]
+    fn test_module_exercise_command() {
+        let entry = crate::exercise::ExerciseEntry {
+            name: "dyn2".to_string(),
+            dir: "05_dynamic".to_string(),
+            module: Some("exercises.05_dynamic.runner".to_string()),
+            ..Default::default()
+        };
+        let exercise = Exercise::from_entry(&entry, Path::new("/pack"), None);
+        let opts = VerifyOptions {
+            python_args: vec!["-X".to_string(), "dev".to_string()],
+            working_dir: "/pack".into(),
+            ..Default::default()
+        }
+        .for_exercise(&exercise);
+
+        let cmd = build_python_command(&exercise.path, &opts);
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["-X", "dev", "-m", "exercises.05_dynamic.runner"]);
+        assert_eq!(cmd.get_current_dir(), Some(Path::new("/pack")));
+    }
+
+    #[test]
+    fn test_classify_zenml_error() {
+        let unreachable = "requests.exceptions.ConnectionError: HTTPConnectionPool(host='localhost', port=8237): \
+            Max retries exceeded with url: /api/v1/runs";
+        assert!(classify_zenml_error(unreachable).unwrap().starts_with("ZenML server unreachable"));
+        assert!(classify_zenml_error("Error: Could not connect to the ZenML server at https://zenml.example.com")
+            .unwrap()
+            .starts_with("ZenML server unreachable"));
+
+        let auth = "AuthorizationException: Authentication error: the token has expired";
+        assert!(classify_zenml_error(auth).unwrap().starts_with("ZenML authentication failed"));
+        assert!(classify_zenml_error("HTTP 401 Unauthorized").unwrap().starts_with("ZenML authentication failed"));
+        assert!(classify_zenml_error("Error: server returned status code 401").is_some());
+        let run_id = "Error: Pipeline run 'c0a84010-4017-4401-9e2b-000401aa0000' not found (port 4010)";
+        assert_eq!(classify_zenml_error(run_id), None);
+
+        let sqlite = "sqlalchemy.exc.OperationalError: (sqlite3.OperationalError) unable to open database file";
+        assert!(classify_zenml_error(sqlite).unwrap().starts_with("ZenML local store unavailable"));
+
+        assert_eq!(classify_zenml_error("Error: No pipeline found with name 'intro1_pipeline'"), None);
+    }
+
+    #[test]
+    fn test_cli_failure_message_uses_classification() {
+        let exercise = test_exercise(&["completed"]);
+        let check = classify_status_output(false, "", "ConnectionError: connection refused");
+        let result = judge_status_check(&exercise, check, true, String::new());
+        assert!(!result.passed());
+        assert!(result.message.contains("zenml login"));
+    }
+
+    #[test]
+    fn test_describe_command() {
+        let opts = VerifyOptions {
+            zenml_bin: "/opt/zen ml/zenml".to_string(),
+            ..Default::default()
+        };
+        let cmd = build_zenml_status_command("intro1_pipeline", &opts);
+        assert_eq!(
+            describe_command(&cmd),
+            "'/opt/zen ml/zenml' pipeline runs list --pipeline intro1_pipeline --size 1 \
+             --sort_by desc:created --output json"
+        );
+
+        let cmd = build_custom_command("sh", &["-c".to_string(), "echo it's".to_string()], &opts);
+        assert_eq!(describe_command(&cmd), r"sh -c 'echo it'\''s'");
+    }
+
+    #[test]
+    fn test_is_transient_zenml_error() {
+        assert!(is_transient_zenml_error("ConnectionError: [Errno 111] Connection refused"));
+        assert!(is_transient_zenml_error("ReadTimeout: HTTPSConnectionPool: Read timed out."));
+        assert!(is_transient_zenml_error("Error: HTTP 503 Service Unavailable"));
+        assert!(is_transient_zenml_error("RuntimeError: server returned status 502"));
+
+        assert!(!is_transient_zenml_error("Error: No pipeline found with name 'intro1_pipeline'"));
+        assert!(!is_transient_zenml_error("AuthorizationException: 401 Unauthorized"));
+        assert!(!is_transient_zenml_error("ValueError: expected 500 items"));
+    }
 }
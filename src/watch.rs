@@ -6,14 +6,16 @@
 use anyhow::{Context, Result};
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{self, Receiver, Sender};
-use std::time::Duration;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
 
 /// Events emitted by the file watcher
 #[derive(Debug)]
 pub enum WatchEvent {
     /// An exercise file was modified
     FileChanged(PathBuf),
+    /// A file under the watched solutions directory was modified
+    SolutionChanged(PathBuf),
     /// An error occurred while watching
     Error(String),
 }
@@ -23,12 +25,14 @@ pub struct WatchHandle {
     _watcher: RecommendedWatcher,
 }
 
-/// Start watching a directory for file changes
+/// Start watching directories for file changes
 ///
 /// Returns a handle that keeps the watcher alive, and a receiver
-/// for watch events.
+/// for watch events. Changes under `solutions_root` (which should be one
+/// of `watch_roots`) are reported as `SolutionChanged`.
 pub fn start_watch(
-    watch_root: &Path,
+    watch_roots: &[PathBuf],
+    solutions_root: Option<PathBuf>,
     tx: Sender<WatchEvent>,
 ) -> Result<WatchHandle> {
     // Create a channel for notify events
@@ -43,94 +47,290 @@ pub fn start_watch(
     )
     .context("Failed to create file watcher")?;
 
-    // Watch the exercises directory recursively
-    watcher
-        .watch(watch_root, RecursiveMode::Recursive)
-        .with_context(|| format!("Failed to watch directory: {:?}", watch_root))?;
+    // Watch each directory recursively; a missing one just isn't watched
+    for watch_root in watch_roots {
+        if !watch_root.is_dir() {
+            log::warn!("Not watching {:?}: directory does not exist", watch_root);
+            continue;
+        }
+        watcher
+            .watch(watch_root, RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch directory: {:?}", watch_root))?;
+    }
 
     // Spawn a thread to convert notify events to our WatchEvents
-    let watch_root_owned = watch_root.to_path_buf();
     std::thread::spawn(move || {
-        process_notify_events(notify_rx, tx, &watch_root_owned);
+        process_notify_events(notify_rx, solutions_root, tx);
     });
 
     Ok(WatchHandle { _watcher: watcher })
 }
 
+/// How long to wait for further events before emitting a batch.
+///
+/// Editors that save via "write temp file, then rename" produce several
+/// raw events for a single save; collapsing them within this window means
+/// one save yields one `FileChanged` per path.
+const COALESCE_WINDOW: Duration = Duration::from_millis(100);
+
 /// Process raw notify events and emit WatchEvents
 fn process_notify_events(
     notify_rx: Receiver<notify::Result<Event>>,
+    solutions_root: Option<PathBuf>,
     tx: Sender<WatchEvent>,
-    _watch_root: &Path,
 ) {
-    for res in notify_rx {
+    let mut pending: Vec<PathBuf> = Vec::new();
+
+    loop {
+        // Block until something happens, but only wait briefly while a batch is open
+        let received = if pending.is_empty() {
+            notify_rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            notify_rx.recv_timeout(COALESCE_WINDOW)
+        };
+
+        let res = match received {
+            Ok(res) => res,
+            Err(err) => {
+                for path in pending.drain(..) {
+                    log::debug!("watch: changed {}", path.display());
+                    let event = match &solutions_root {
+                        Some(root) if path.starts_with(root) => WatchEvent::SolutionChanged(path),
+                        _ => WatchEvent::FileChanged(path),
+                    };
+                    if tx.send(event).is_err() {
+                        // Receiver dropped, exit thread
+                        return;
+                    }
+                }
+                if err == RecvTimeoutError::Disconnected {
+                    return;
+                }
+                continue;
+            }
+        };
+
         match res {
             Ok(event) => {
+                log::trace!("watch: {:?} {:?}", event.kind, event.paths);
                 // Only care about modify/create events
                 if matches!(
                     event.kind,
                     notify::EventKind::Modify(_) | notify::EventKind::Create(_)
                 ) {
                     for path in event.paths {
-                        // Only watch .py files
-                        if path.extension().map(|e| e == "py").unwrap_or(false) {
-                            if tx.send(WatchEvent::FileChanged(path)).is_err() {
-                                // Receiver dropped, exit thread
-                                return;
-                            }
+                        if is_watched_path(&path) && !pending.contains(&path) {
+                            pending.push(path);
                         }
                     }
                 }
             }
             Err(e) => {
+                log::warn!("watch error: {}", e);
                 let _ = tx.send(WatchEvent::Error(e.to_string()));
             }
         }
     }
 }
 
-/// Simple debouncer for watch events
+/// Whether a changed path should trigger re-verification
+///
+/// Only `.py` files count; bytecode caches and editor lock/backup files
+/// are ignored.
+fn is_watched_path(path: &Path) -> bool {
+    if path.extension().map(|e| e != "py").unwrap_or(true) {
+        return false;
+    }
+
+    if path.components().any(|c| c.as_os_str() == "__pycache__") {
+        return false;
+    }
+
+    let file_name = match path.file_name() {
+        Some(name) => name.to_string_lossy(),
+        None => return false,
+    };
+
+    // Emacs lock files (.#foo.py) and backup files (foo.py~)
+    !(file_name.starts_with(".#") || file_name.ends_with('~'))
+}
+
+/// Trailing-edge debouncer for watch events
+///
+/// Each change (`record`) restarts a quiet period of the debounce window.
+/// `poll` returns true exactly once, on the first call after the window has
+/// passed with no further change; a burst of saves therefore fires a single
+/// verification, one window after the last save. Changes recorded after
+/// that start a new quiet period.
 pub struct Debouncer {
-    last_event_time: Option<std::time::Instant>,
-    debounce_duration: Duration,
+    /// When the pending trigger fires, if a change is waiting
+    deadline: Option<Instant>,
+    window: Duration,
 }
 
 impl Debouncer {
     pub fn new(debounce_ms: u64) -> Self {
         Self {
-            last_event_time: None,
-            debounce_duration: Duration::from_millis(debounce_ms),
+            deadline: None,
+            window: Duration::from_millis(debounce_ms),
         }
     }
 
-    /// Record an event and return true if it should be processed
-    /// (i.e., enough time has passed since the last event)
-    pub fn should_process(&mut self) -> bool {
-        let now = std::time::Instant::now();
+    /// Record a change now
+    pub fn record(&mut self) {
+        self.record_at(Instant::now());
+    }
+
+    /// Record a change at `now`, pushing the trigger back to `now + window`
+    pub fn record_at(&mut self, now: Instant) {
+        self.deadline = Some(now + self.window);
+    }
 
-        if let Some(last) = self.last_event_time {
-            if now.duration_since(last) < self.debounce_duration {
-                // Too soon, update timestamp but don't process
-                self.last_event_time = Some(now);
-                return false;
+    /// Whether the quiet period after the last change has just ended
+    pub fn poll(&mut self) -> bool {
+        self.poll_at(Instant::now())
+    }
+
+    /// `poll` as of `now`; consumes the trigger when it fires
+    pub fn poll_at(&mut self, now: Instant) -> bool {
+        match self.deadline {
+            Some(deadline) if now >= deadline => {
+                self.deadline = None;
+                true
             }
+            _ => false,
         }
+    }
 
-        self.last_event_time = Some(now);
-        true
+    /// Whether a change is waiting for its quiet period to end
+    pub fn is_pending(&self) -> bool {
+        self.deadline.is_some()
     }
 
-    /// Check if we should trigger (enough time passed since last event)
-    pub fn ready_to_trigger(&self) -> bool {
-        if let Some(last) = self.last_event_time {
-            std::time::Instant::now().duration_since(last) >= self.debounce_duration
-        } else {
-            false
+    /// Drop any waiting change without firing
+    pub fn reset(&mut self) {
+        self.deadline = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_watched_path_accepts_python_files() {
+        assert!(is_watched_path(Path::new("/pack/exercises/01_loading/load1.py")));
+        assert!(is_watched_path(Path::new("helpers.py")));
+    }
+
+    #[test]
+    fn test_is_watched_path_rejects_non_python() {
+        assert!(!is_watched_path(Path::new("/pack/exercises/README.md")));
+        assert!(!is_watched_path(Path::new("/pack/exercises/load1.py.swp")));
+        assert!(!is_watched_path(Path::new("/pack/exercises/load1.py.12345")));
+        assert!(!is_watched_path(Path::new("/pack/exercises/01_loading")));
+    }
+
+    #[test]
+    fn test_is_watched_path_rejects_pycache() {
+        assert!(!is_watched_path(Path::new(
+            "/pack/exercises/__pycache__/load1.cpython-311.pyc"
+        )));
+        assert!(!is_watched_path(Path::new("/pack/exercises/__pycache__/shim.py")));
+    }
+
+    #[test]
+    fn test_is_watched_path_rejects_editor_files() {
+        assert!(!is_watched_path(Path::new("/pack/exercises/.#load1.py")));
+        assert!(!is_watched_path(Path::new("/pack/exercises/load1.py~")));
+    }
+
+    /// Feed changes at the given offsets (ms), polling every 10ms until
+    /// `until_ms`; returns the offsets at which the debouncer fired
+    fn simulate(window_ms: u64, changes_ms: &[u64], until_ms: u64) -> Vec<u64> {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(window_ms);
+        let mut fired = Vec::new();
+        for t in (0..=until_ms).step_by(10) {
+            let now = start + Duration::from_millis(t);
+            if changes_ms.contains(&t) {
+                debouncer.record_at(now);
+            }
+            if debouncer.poll_at(now) {
+                fired.push(t);
+            }
         }
+        fired
     }
 
-    /// Reset the debouncer
-    pub fn reset(&mut self) {
-        self.last_event_time = None;
+    #[test]
+    fn test_debouncer_single_change_fires_once_after_window() {
+        assert_eq!(simulate(300, &[0], 2000), vec![300]);
+        assert_eq!(simulate(0, &[50], 2000), vec![50]);
+        assert!(!Debouncer::new(300).is_pending());
+    }
+
+    #[test]
+    fn test_debouncer_burst_fires_once_after_last_change() {
+        // Rapid saves, each within the window of the one before
+        assert_eq!(simulate(300, &[0, 50, 100, 150, 200], 2000), vec![500]);
+        // Saves just inside the window keep pushing the trigger back
+        assert_eq!(simulate(300, &[0, 290, 580, 870], 3000), vec![1170]);
+    }
+
+    #[test]
+    fn test_debouncer_separate_bursts_fire_separately() {
+        // A change just after the first trigger starts a new quiet period;
+        // one landing exactly on the deadline still counts as the same burst
+        assert_eq!(simulate(300, &[0, 310], 2000), vec![300, 610]);
+        assert_eq!(simulate(300, &[0, 300], 2000), vec![600]);
+        assert_eq!(simulate(300, &[0, 20, 1000, 1010, 1020], 3000), vec![320, 1320]);
+    }
+
+    #[test]
+    fn test_debouncer_reset_drops_pending_change() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(300);
+        debouncer.record_at(start);
+        assert!(debouncer.is_pending());
+        debouncer.reset();
+        assert!(!debouncer.poll_at(start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_process_notify_events_coalesces_burst() {
+        let (notify_tx, notify_rx) = mpsc::channel();
+        let (tx, rx) = mpsc::channel();
+        let handle = std::thread::spawn(move || {
+            process_notify_events(notify_rx, Some(PathBuf::from("/pack/solutions")), tx);
+        });
+
+        use notify::event::{CreateKind, ModifyKind, RenameMode};
+        use notify::EventKind;
+        let send = |kind: EventKind, path: &str| {
+            notify_tx
+                .send(Ok(Event::new(kind).add_path(PathBuf::from(path))))
+                .unwrap();
+        };
+
+        // Simulate an editor writing a temp file then renaming it into place
+        send(EventKind::Create(CreateKind::File), "/pack/exercises/load1.py.tmp");
+        send(EventKind::Modify(ModifyKind::Any), "/pack/exercises/load1.py.tmp");
+        send(EventKind::Modify(ModifyKind::Name(RenameMode::To)), "/pack/exercises/load1.py");
+        send(EventKind::Modify(ModifyKind::Any), "/pack/exercises/load1.py");
+        send(EventKind::Modify(ModifyKind::Any), "/pack/solutions/load1.py");
+        drop(notify_tx);
+        handle.join().unwrap();
+
+        let events: Vec<_> = rx.try_iter().collect();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            WatchEvent::FileChanged(p) if p == Path::new("/pack/exercises/load1.py")
+        ));
+        assert!(matches!(
+            &events[1],
+            WatchEvent::SolutionChanged(p) if p == Path::new("/pack/solutions/load1.py")
+        ));
     }
 }
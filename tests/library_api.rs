@@ -0,0 +1,58 @@
+//! Exercises the public library API without going through the binary.
+
+use std::fs;
+use std::path::Path;
+
+use zenlings::app_state::AppState;
+use zenlings::verify::{self, VerifyOptions};
+
+/// Write a minimal one-exercise pack into `root`
+fn write_pack(root: &Path, script: &str) {
+    fs::write(
+        root.join("info.toml"),
+        r#"
+        format_version = 1
+
+        [[exercises]]
+        name = "intro1"
+        dir = "00_intro"
+        "#,
+    )
+    .unwrap();
+    let dir = root.join("exercises/00_intro");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("intro1.py"), script).unwrap();
+}
+
+#[test]
+fn test_load_pack() {
+    let dir = tempfile::tempdir().unwrap();
+    write_pack(dir.path(), "print('hello')\n");
+
+    let state = AppState::load(dir.path().to_path_buf()).unwrap();
+    assert_eq!(state.exercises.len(), 1);
+    assert_eq!(state.current_exercise().name, "intro1");
+    assert!(!state.is_completed("intro1"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_simple_verification() {
+    let dir = tempfile::tempdir().unwrap();
+    // The exercise is run with `sh` so the test doesn't need Python
+    write_pack(dir.path(), "echo hello\n");
+    let state = AppState::load(dir.path().to_path_buf()).unwrap();
+    let opts = VerifyOptions {
+        python_bin: "sh".to_string(),
+        working_dir: dir.path().to_path_buf(),
+        ..Default::default()
+    };
+
+    let result = verify::verify_exercise_simple(state.current_exercise(), &opts).unwrap();
+    assert!(result.passed());
+    assert!(result.python_output.contains("hello"));
+
+    let (ok, output) = verify::run_python_capture(&state.current_exercise().path, &opts).unwrap();
+    assert!(ok);
+    assert!(output.contains("hello"));
+}
@@ -0,0 +1,477 @@
+//! End-to-end tests of `verify_exercise` against stub `python` and `zenml`
+//! executables, so the real tools aren't needed.
+
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use zenlings::app_state::AppState;
+use zenlings::verify::{self, OutputLine, VerifyOptions, VerifyOutcome, VerifyResult};
+
+/// A temp pack with one exercise and stub binaries next to it
+struct Harness {
+    dir: tempfile::TempDir,
+}
+
+impl Harness {
+    fn new(verify_status: &str) -> Self {
+        Self::with_settings(&format!("verify_status = {}", verify_status))
+    }
+
+    /// A pack whose exercise has extra info.toml `settings`
+    fn with_settings(settings: &str) -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("info.toml"),
+            format!(
+                r#"
+                format_version = 1
+
+                [[exercises]]
+                name = "intro1"
+                dir = "00_intro"
+                {}
+                "#,
+                settings
+            ),
+        )
+        .unwrap();
+        let exercise_dir = dir.path().join("exercises/00_intro");
+        fs::create_dir_all(&exercise_dir).unwrap();
+        fs::write(exercise_dir.join("intro1.py"), "print('hello')\n").unwrap();
+        Self { dir }
+    }
+
+    /// Stub interpreter that prints a line and exits with `exit_code`
+    fn python(&self, exit_code: i32) -> &Self {
+        self.stub(
+            "python",
+            &format!("echo \"ran $1\"\necho 'Traceback' >&2\nexit {}\n", exit_code),
+        );
+        self
+    }
+
+    /// Stub ZenML CLI that records its arguments, prints `stdout` and
+    /// exits with `exit_code`
+    fn zenml(&self, stdout: &str, exit_code: i32) -> &Self {
+        let log = self.dir.path().join("zenml-args.log");
+        self.stub(
+            "zenml",
+            &format!(
+                "echo \"$@\" >> '{}'\ncat <<'JSON'\n{}\nJSON\nexit {}\n",
+                log.display(),
+                stdout,
+                exit_code
+            ),
+        );
+        self
+    }
+
+    /// Stub ZenML CLI that answers `pipeline runs list` with `runs` and
+    /// `pipeline list` with `pipelines`
+    fn zenml_with_pipelines(&self, runs: &str, pipelines: &str) -> &Self {
+        let log = self.dir.path().join("zenml-args.log");
+        self.stub(
+            "zenml",
+            &format!(
+                "echo \"$@\" >> '{log}'\n\
+                 if [ \"$2\" = runs ]; then echo '{runs}'; else echo '{pipelines}'; fi\n",
+                log = log.display(),
+            ),
+        );
+        self
+    }
+
+    /// Stub ZenML CLI that fails with `stderr` for its first `failures`
+    /// calls, then prints `stdout` and succeeds
+    fn flaky_zenml(&self, failures: u32, stderr: &str, stdout: &str) -> &Self {
+        let log = self.dir.path().join("zenml-args.log");
+        self.stub(
+            "zenml",
+            &format!(
+                "echo \"$@\" >> '{log}'\n\
+                 if [ \"$(wc -l < '{log}')\" -le {failures} ]; then echo '{stderr}' >&2; exit 1; fi\n\
+                 cat <<'JSON'\n{stdout}\nJSON\n",
+                log = log.display(),
+            ),
+        );
+        self
+    }
+
+    fn stub(&self, name: &str, body: &str) {
+        let path = self.bin_path(name);
+        fs::write(&path, format!("#!/bin/sh\n{}", body)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn bin_path(&self, name: &str) -> PathBuf {
+        self.dir.path().join(format!("stub-{}", name))
+    }
+
+    fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Arguments the zenml stub was called with, one call per line
+    fn zenml_calls(&self) -> Vec<String> {
+        fs::read_to_string(self.root().join("zenml-args.log"))
+            .map(|s| s.lines().map(|l| l.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    fn options(&self) -> VerifyOptions {
+        VerifyOptions {
+            python_bin: self.bin_path("python").to_string_lossy().to_string(),
+            zenml_bin: self.bin_path("zenml").to_string_lossy().to_string(),
+            working_dir: self.root().to_path_buf(),
+            zenml_backoff: Duration::from_millis(1),
+            // Stubs answer every call alike; the stale-run check has its own tests
+            require_new_run: false,
+            ..Default::default()
+        }
+    }
+
+    fn verify(&self) -> VerifyResult {
+        self.verify_with(&self.options())
+    }
+
+    fn verify_with(&self, opts: &VerifyOptions) -> VerifyResult {
+        let state = AppState::load(self.root().to_path_buf()).unwrap();
+        verify::verify_exercise(state.current_exercise(), opts).unwrap()
+    }
+
+    fn verify_simple(&self) -> VerifyResult {
+        let state = AppState::load(self.root().to_path_buf()).unwrap();
+        verify::verify_exercise_simple(state.current_exercise(), &self.options()).unwrap()
+    }
+}
+
+fn runs_json(status: &str) -> String {
+    format!(
+        r#"{{"items":[{{"id":"run-1","name":"intro1_pipeline-1","status":"{}"}}]}}"#,
+        status
+    )
+}
+
+#[test]
+fn test_completed_run_passes() {
+    let h = Harness::new("\"completed\"");
+    h.python(0).zenml(&runs_json("completed"), 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Passed);
+    assert!(result.python_exit_ok);
+    assert!(result.zenml_checked);
+    assert!(result.python_output.contains("ran"));
+    assert!(result.python_output.contains("intro1.py"));
+
+    let calls = h.zenml_calls();
+    assert_eq!(calls.len(), 1);
+    assert!(calls[0].starts_with("pipeline runs list --pipeline "));
+    assert!(calls[0].contains("--output json"));
+}
+
+#[test]
+fn test_status_mismatch_fails() {
+    let h = Harness::new("\"completed\"");
+    h.python(0).zenml(&runs_json("running"), 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert!(result.zenml_checked);
+    assert_eq!(result.message, "Pipeline status 'running', expected 'completed'");
+}
+
+#[test]
+fn test_no_runs_fails() {
+    let h = Harness::new("\"completed\"");
+    h.python(0).zenml(r#"{"items":[]}"#, 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert!(result.zenml_output.starts_with("No runs found"));
+}
+
+#[test]
+fn test_no_runs_of_registered_pipeline() {
+    let h = Harness::new("\"completed\"");
+    h.python(0)
+        .zenml_with_pipelines(r#"{"items":[]}"#, r#"{"items":[{"name":"intro1_pipeline"}]}"#);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(result.message, "Pipeline 'intro1_pipeline' has never run");
+    assert_eq!(h.zenml_calls()[1], "pipeline list --output json");
+}
+
+#[test]
+fn test_no_runs_of_unknown_pipeline_suggests_closest() {
+    let h = Harness::new("\"completed\"");
+    h.python(0)
+        .zenml_with_pipelines(r#"{"items":[]}"#, r#"{"items":[{"name":"intro_pipeline"}]}"#);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert!(result.message.contains("No pipeline named 'intro1_pipeline'"), "{}", result.message);
+    assert!(result.message.contains("did you mean 'intro_pipeline'?"), "{}", result.message);
+}
+
+#[test]
+fn test_required_tags_are_checked_on_the_run() {
+    let runs = r#"{"items":[{"id":"run-1","status":"completed","tags":["baseline"]}]}"#;
+
+    let h = Harness::with_settings(r#"verify_tags = ["baseline"]"#);
+    h.python(0).zenml(runs, 0);
+    assert_eq!(h.verify().outcome, VerifyOutcome::Passed);
+    assert_eq!(h.zenml_calls()[1], "pipeline runs describe run-1 --output json");
+
+    let h = Harness::with_settings(r#"verify_tags = ["baseline", "v2"]"#);
+    h.python(0).zenml(runs, 0);
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(result.message, "Run is missing the tag 'v2'");
+}
+
+/// Stub ZenML CLI whose latest run is `before` until the exercise script
+/// has run (and created `after`)
+fn zenml_with_new_run(h: &Harness, before: &str, after: &str) {
+    let marker = h.root().join("script-ran");
+    h.stub("python", &format!("touch '{}'\n", marker.display()));
+    h.stub(
+        "zenml",
+        &format!(
+            "if [ -e '{marker}' ]; then echo '{after}'; else echo '{before}'; fi\n",
+            marker = marker.display(),
+        ),
+    );
+}
+
+#[test]
+fn test_stale_run_fails() {
+    let h = Harness::new("\"completed\"");
+    let run = runs_json("completed");
+    zenml_with_new_run(&h, &run, &run);
+
+    let opts = VerifyOptions { require_new_run: true, ..h.options() };
+    let result = h.verify_with(&opts);
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(result.message, "No new pipeline run was created — did your script call the pipeline?");
+}
+
+#[test]
+fn test_new_run_passes() {
+    let h = Harness::new("\"completed\"");
+    let newer = r#"{"items":[{"id":"run-2","status":"completed"}]}"#;
+    zenml_with_new_run(&h, &runs_json("completed"), newer);
+
+    let opts = VerifyOptions { require_new_run: true, ..h.options() };
+    assert_eq!(h.verify_with(&opts).outcome, VerifyOutcome::Passed);
+
+    // A pipeline's first run has nothing to compare against
+    let h = Harness::new("\"completed\"");
+    zenml_with_new_run(&h, r#"{"items":[]}"#, &runs_json("completed"));
+    let opts = VerifyOptions { require_new_run: true, ..h.options() };
+    assert_eq!(h.verify_with(&opts).outcome, VerifyOutcome::Passed);
+}
+
+#[test]
+fn test_python_failure_skips_zenml_check() {
+    let h = Harness::new("\"completed\"");
+    h.python(1).zenml(&runs_json("completed"), 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert!(!result.python_exit_ok);
+    assert!(!result.zenml_checked);
+    assert_eq!(result.message, "Python script failed");
+    assert!(result.python_output.contains("Traceback"));
+    assert!(h.zenml_calls().is_empty());
+}
+
+#[test]
+fn test_zenml_cli_failure_fails() {
+    let h = Harness::new("\"completed\"");
+    h.python(0).zenml("Error: no such pipeline", 2);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(result.message, "ZenML status check failed");
+    assert!(result.zenml_raw_output.contains("no such pipeline"));
+}
+
+#[test]
+fn test_expected_failed_run_passes_despite_python_failure() {
+    let h = Harness::new("\"failed\"");
+    h.python(1).zenml(&runs_json("failed"), 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Passed);
+    assert!(!result.python_exit_ok);
+    assert_eq!(h.zenml_calls().len(), 1);
+}
+
+#[test]
+fn test_transient_zenml_errors_are_retried() {
+    let h = Harness::new("\"completed\"");
+    h.python(0)
+        .flaky_zenml(2, "ConnectionError: Connection refused", &runs_json("completed"));
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Passed);
+    assert_eq!(h.zenml_calls().len(), 3);
+}
+
+#[test]
+fn test_retries_are_bounded() {
+    let h = Harness::new("\"completed\"");
+    h.python(0)
+        .flaky_zenml(5, "ConnectionError: Connection refused", &runs_json("completed"));
+
+    let opts = VerifyOptions {
+        zenml_attempts: 2,
+        ..h.options()
+    };
+    let result = h.verify_with(&opts);
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(h.zenml_calls().len(), 2);
+}
+
+#[test]
+fn test_other_zenml_errors_are_not_retried() {
+    let h = Harness::new("\"completed\"");
+    h.python(0).flaky_zenml(1, "Error: no such pipeline", &runs_json("completed"));
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(h.zenml_calls().len(), 1);
+}
+
+#[test]
+fn test_expected_stdout_is_checked_before_zenml() {
+    // The stub prints "Traceback" on stderr, which doesn't count
+    let h = Harness::with_settings("expect_stdout = \"Traceback\"");
+    h.python(0).zenml(&runs_json("completed"), 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(result.message, "Expected output containing 'Traceback' not found");
+    assert!(h.zenml_calls().is_empty());
+}
+
+#[test]
+fn test_expected_stdout_regex_passes() {
+    let h = Harness::with_settings("expect_stdout = 'ran .*intro1\\.py'\nexpect_regex = true");
+    h.python(0).zenml(&runs_json("completed"), 0);
+
+    assert_eq!(h.verify().outcome, VerifyOutcome::Passed);
+    assert_eq!(h.verify_simple().outcome, VerifyOutcome::Passed);
+}
+
+#[test]
+fn test_simple_verify_checks_expected_stdout() {
+    let h = Harness::with_settings("expect_stdout = \"goodbye\"");
+    h.python(0);
+
+    let result = h.verify_simple();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(result.message, "Expected output containing 'goodbye' not found");
+}
+
+#[test]
+fn test_missing_integration_fails_before_running() {
+    let h = Harness::with_settings("requires_integrations = [\"sklearn\", \"mlflow\"]");
+    // The integration probe is the only `python -c` call
+    h.stub("python", "if [ \"$1\" = -c ]; then echo '[\"sklearn\"]'; exit 0; fi\necho ran > ran.log\n");
+    h.zenml(&runs_json("completed"), 0);
+
+    let result = h.verify();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+    assert_eq!(
+        result.message,
+        "This exercise needs the 'mlflow' integration: run `zenml integration install mlflow`"
+    );
+    assert!(!h.root().join("ran.log").exists());
+    assert!(h.zenml_calls().is_empty());
+}
+
+#[test]
+fn test_installed_integrations_pass() {
+    let h = Harness::with_settings("requires_integrations = [\"sklearn\"]");
+    h.stub("python", "if [ \"$1\" = -c ]; then echo '[\"sklearn\"]'; fi\n");
+    h.zenml(&runs_json("completed"), 0);
+
+    assert_eq!(h.verify().outcome, VerifyOutcome::Passed);
+}
+
+#[test]
+fn test_hung_verify_command_times_out() {
+    let h = Harness::with_settings(r#"verify_command = ["sh", "-c", "sleep 30"]"#);
+    h.python(0);
+
+    let state = AppState::load(h.root().to_path_buf()).unwrap();
+    let opts = VerifyOptions { zenml_timeout: Some(Duration::from_millis(300)), ..h.options() };
+    let started = std::time::Instant::now();
+    let err = verify::verify_exercise(state.current_exercise(), &opts).unwrap_err();
+    assert!(err.to_string().contains("timed out"), "{err:#}");
+    assert!(started.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_finished_run_is_judged_without_rerunning_python() {
+    let h = Harness::new("\"completed\"");
+    let marker = h.root().join("script-ran");
+    h.stub("python", &format!("touch '{}'\n", marker.display()));
+    let newer = r#"{"items":[{"id":"run-2","status":"completed"}]}"#;
+    h.zenml(newer, 0);
+
+    let state = AppState::load(h.root().to_path_buf()).unwrap();
+    let exercise = state.current_exercise();
+    let opts = VerifyOptions { require_new_run: true, ..h.options() };
+    let baseline = verify::ZenmlRun { id: Some("run-1".to_string()), ..Default::default() };
+
+    let result = verify::check_finished_run(exercise, &opts, true, String::new(), "", Some(&baseline)).unwrap();
+    assert_eq!(result.outcome, VerifyOutcome::Passed);
+    assert_eq!(result.run_id.as_deref(), Some("run-2"));
+    assert!(!marker.exists(), "python must not run again");
+
+    // The same run as the baseline is stale
+    h.zenml(&runs_json("completed"), 0);
+    let result = verify::check_finished_run(exercise, &opts, true, String::new(), "", Some(&baseline)).unwrap();
+    assert_eq!(result.outcome, VerifyOutcome::Failed);
+}
+
+#[test]
+fn test_streamed_output_keeps_non_utf8_lines() {
+    let h = Harness::new("\"completed\"");
+    // "café" in latin-1 on stdout, a stray byte on stderr
+    h.stub("python", "printf 'caf\\351 ok\\n'\nprintf 'bad \\377 byte\\n' >&2\n");
+
+    let (tx, rx) = mpsc::channel();
+    let exercise = h.root().join("exercises/00_intro/intro1.py");
+    let (ok, stdout) = verify::run_python_streaming(&exercise, &h.options(), tx, || false)
+        .unwrap()
+        .unwrap();
+    assert!(ok);
+    assert_eq!(stdout, "caf\u{fffd} ok\n");
+
+    let lines: Vec<OutputLine> = rx.iter().collect();
+    assert!(lines.contains(&OutputLine::Stdout("caf\u{fffd} ok".to_string())));
+    assert!(lines.contains(&OutputLine::Stderr("bad \u{fffd} byte".to_string())));
+}
+
+#[test]
+fn test_run_zenml_init() {
+    let h = Harness::new("\"completed\"");
+    h.stub("zenml", "[ \"$1\" = init ] && mkdir .zen\n");
+    assert!(!verify::check_zenml_init(h.root()));
+    verify::run_zenml_init(&h.options()).unwrap();
+    assert!(verify::check_zenml_init(h.root()));
+
+    h.stub("zenml", "echo 'Repository already initialized' >&2\nexit 1\n");
+    let err = verify::run_zenml_init(&h.options()).unwrap_err();
+    assert_eq!(err.to_string(), "zenml init failed: Repository already initialized");
+}